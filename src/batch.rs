@@ -0,0 +1,70 @@
+use crate::error::Result;
+use crate::pickledb::PickleDb;
+use std::ops::{Deref, DerefMut};
+
+/// A struct for making multiple changes to PickleDB with a single dump. Returned in
+/// [PickleDb::batch()](struct.PickleDb.html#method.batch)
+///
+/// This object dereferences to [PickleDb](struct.PickleDb.html), so all of the DB methods can be
+/// called on it. Changes made through it don't trigger a DB dump until the batch is committed
+/// or dropped.
+pub struct PickleDbBatch<'a> {
+    pub(crate) db: &'a mut PickleDb,
+    pub(crate) finished: bool,
+}
+
+impl<'a> PickleDbBatch<'a> {
+    /// Finish the batch and dump all of its changes.
+    ///
+    /// A single DB dump is triggered (which is decided according to the dump policy), and only
+    /// if any change was made during the batch. If this batch is nested in another batch, nothing is
+    /// dumped and its changes are dumped together with the changes of the outermost batch.
+    ///
+    /// This method returns `Ok` if the dump is successful or if no dump was needed, or an
+    /// `Err(`[Error](error/struct.Error.html)`)` otherwise. Unlike dropping the batch object, which
+    /// ignores dump failures, this method lets the user handle them.
+    ///
+    pub fn commit(mut self) -> Result<()> {
+        self.finish()
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        if self.finished {
+            return Ok(());
+        }
+
+        self.finished = true;
+        self.db.batch_depth -= 1;
+        if self.db.batch_depth > 0 {
+            return Ok(());
+        }
+
+        if self.db.batch_changed {
+            self.db.batch_changed = false;
+            self.db.dump_changes()
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<'a> Deref for PickleDbBatch<'a> {
+    type Target = PickleDb;
+
+    fn deref(&self) -> &PickleDb {
+        self.db
+    }
+}
+
+impl<'a> DerefMut for PickleDbBatch<'a> {
+    fn deref_mut(&mut self) -> &mut PickleDb {
+        self.db
+    }
+}
+
+impl<'a> Drop for PickleDbBatch<'a> {
+    fn drop(&mut self) {
+        // try to dump, ignore if fails
+        let _ = self.finish();
+    }
+}
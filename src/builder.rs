@@ -0,0 +1,313 @@
+use std::path::{Path, PathBuf};
+
+use crate::error::Result;
+#[cfg(feature = "lock")]
+use crate::lock::FileLock;
+use crate::pickledb::{PickleDb, PickleDbDumpPolicy};
+use crate::serialization::{SerializationMethod, Serializer};
+
+/// A builder for creating a new [PickleDb](struct.PickleDb.html) instance or loading one from a file.
+///
+/// This is an alternative to the different `new_*` and `load_*` methods of `PickleDb` which allows
+/// setting only the options that differ from the defaults. The defaults are:
+/// * Dump policy: [PickleDbDumpPolicy::AutoDump](enum.PickleDbDumpPolicy.html#variant.AutoDump)
+/// * Serialization method: [SerializationMethod::Json](enum.SerializationMethod.html#variant.Json)
+/// * Compression (when the `compression` feature is enabled): disabled for new DBs and detected
+///   automatically for loaded DBs
+/// * Encryption (when the `crypt` feature is enabled): disabled
+/// * Pretty JSON (when the `json` feature is enabled): disabled
+/// * Write-ahead log: disabled
+/// * Creating the directory of the DB file: disabled
+/// * Size limit: none
+/// * Locking (when the `lock` feature is enabled): disabled
+///
+/// # Examples
+///
+/// ```no_run
+/// use pickledb::{PickleDbBuilder, PickleDbDumpPolicy, SerializationMethod};
+///
+/// // create a new DB
+/// let mut db = PickleDbBuilder::new("example.db")
+///     .dump_policy(PickleDbDumpPolicy::DumpUponRequest)
+///     .serialization(SerializationMethod::Bin)
+///     .build();
+///
+/// // load a DB from a file
+/// let db = PickleDbBuilder::new("example.db")
+///     .serialization(SerializationMethod::Bin)
+///     .load()
+///     .unwrap();
+/// ```
+///
+pub struct PickleDbBuilder {
+    db_path: PathBuf,
+    dump_policy: PickleDbDumpPolicy,
+    serialization_method: SerializationMethod,
+    #[cfg(feature = "compression")]
+    compression: Option<bool>,
+    #[cfg(feature = "crypt")]
+    encryption_key: Option<[u8; 32]>,
+    #[cfg(feature = "json")]
+    pretty_json: bool,
+    temp_dir: Option<PathBuf>,
+    create_dirs: bool,
+    wal: bool,
+    max_bytes: Option<usize>,
+    #[cfg(feature = "lock")]
+    lock: bool,
+    #[cfg(feature = "lock")]
+    wait_for_lock: bool,
+}
+
+impl PickleDbBuilder {
+    /// Constructs a new `PickleDbBuilder` with the default options.
+    ///
+    /// # Arguments
+    ///
+    /// * `db_path` - a path where the DB will be stored or loaded from
+    ///
+    pub fn new<P: AsRef<Path>>(db_path: P) -> PickleDbBuilder {
+        PickleDbBuilder {
+            db_path: db_path.as_ref().to_path_buf(),
+            dump_policy: PickleDbDumpPolicy::AutoDump,
+            serialization_method: SerializationMethod::Json,
+            #[cfg(feature = "compression")]
+            compression: None,
+            #[cfg(feature = "crypt")]
+            encryption_key: None,
+            #[cfg(feature = "json")]
+            pretty_json: false,
+            temp_dir: None,
+            create_dirs: false,
+            wal: false,
+            max_bytes: None,
+            #[cfg(feature = "lock")]
+            lock: false,
+            #[cfg(feature = "lock")]
+            wait_for_lock: false,
+        }
+    }
+
+    /// Set the path where the DB will be stored or loaded from.
+    ///
+    pub fn path<P: AsRef<Path>>(mut self, db_path: P) -> PickleDbBuilder {
+        self.db_path = db_path.as_ref().to_path_buf();
+        self
+    }
+
+    /// Set the policy of dumping DB changes into the file. Please see
+    /// [PickleDb::load()](struct.PickleDb.html#method.load) to understand the different policy options.
+    ///
+    pub fn dump_policy(mut self, dump_policy: PickleDbDumpPolicy) -> PickleDbBuilder {
+        self.dump_policy = dump_policy;
+        self
+    }
+
+    /// Set the serialization method to use for storing the data to memory and file.
+    ///
+    pub fn serialization(mut self, serialization_method: SerializationMethod) -> PickleDbBuilder {
+        self.serialization_method = serialization_method;
+        self
+    }
+
+    /// Set whether the DB file is compressed when it's dumped. Please see
+    /// [PickleDb::new_with_compression()](struct.PickleDb.html#method.new_with_compression) for more information.
+    ///
+    /// When loading a DB, setting this option overrides the automatic detection, so it can be used for
+    /// compressing a DB that was stored in a plain file or vice versa.
+    ///
+    #[cfg(feature = "compression")]
+    pub fn compression(mut self, compression: bool) -> PickleDbBuilder {
+        self.compression = Some(compression);
+        self
+    }
+
+    /// Set the key the DB file is encrypted with. Please see
+    /// [PickleDb::new_with_encryption()](struct.PickleDb.html#method.new_with_encryption) for more information.
+    ///
+    /// When loading a DB, the file is decrypted with this key, and it's encrypted with it when it's dumped.
+    ///
+    #[cfg(feature = "crypt")]
+    pub fn encryption_key(mut self, encryption_key: [u8; 32]) -> PickleDbBuilder {
+        self.encryption_key = Some(encryption_key);
+        self
+    }
+
+    /// Set whether the DB file is written as indented JSON, which is easier to read and edit by hand.
+    ///
+    /// By default a DB that uses [SerializationMethod::Json](enum.SerializationMethod.html#variant.Json) is
+    /// written to the file in a compact form, on a single line. This option only changes the whitespace in the
+    /// file, so a DB can always be loaded regardless of whether it was written with this option or not. It has
+    /// no effect on DBs that use other serialization methods.
+    ///
+    #[cfg(feature = "json")]
+    pub fn pretty_json(mut self, pretty_json: bool) -> PickleDbBuilder {
+        self.pretty_json = pretty_json;
+        self
+    }
+
+    /// Set the directory where temp files are written when the DB is dumped.
+    ///
+    /// When dumping the DB, the data is first written to a temp file which is then renamed to the
+    /// DB file, so the DB file is never left partially written. By default the temp file is written
+    /// next to the DB file, and this option enables writing it to a different directory, for example
+    /// if the directory of the DB file doesn't allow creating other files.
+    ///
+    /// **Please note:** renaming a file is only possible (and atomic) within the same file system, so this
+    /// directory must be on the same file system as the DB file, otherwise dumping the DB fails.
+    ///
+    pub fn temp_dir<P: AsRef<Path>>(mut self, temp_dir: P) -> PickleDbBuilder {
+        self.temp_dir = Some(temp_dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Set whether the directory of the DB file is created when the DB is dumped, if it doesn't exist.
+    ///
+    /// By default dumping the DB fails if the directory of the DB file doesn't exist. When this option is
+    /// enabled, the directory and all of its missing parent directories are created before the DB file is
+    /// written, so a new DB can be stored in a directory that doesn't exist yet.
+    ///
+    pub fn create_dirs(mut self, create_dirs: bool) -> PickleDbBuilder {
+        self.create_dirs = create_dirs;
+        self
+    }
+
+    /// Set whether changes are appended to a write-ahead log instead of rewriting the whole DB file.
+    ///
+    /// Without the log, every dump serializes the entire DB and rewrites the file, which may be slow for
+    /// large DBs that change often. When the log is enabled, each dump appends a small record for every key
+    /// that changed since the previous dump to a log file next to the DB file (the DB file name followed by
+    /// `.wal`), and only changes that affect many keys at once (such as [PickleDb::clear()](struct.PickleDb.html#method.clear))
+    /// rewrite the whole file. The log is folded back into the DB file when it becomes larger than the
+    /// DB file or when [PickleDb::compact()](struct.PickleDb.html#method.compact) is called.
+    ///
+    /// When loading a DB, the log is applied on top of the DB file regardless of this option, so a DB
+    /// that was written with the log can always be loaded. Please note that the log isn't compressed, and
+    /// since it isn't encrypted either it's never used for encrypted DBs.
+    ///
+    pub fn wal(mut self, wal: bool) -> PickleDbBuilder {
+        self.wal = wal;
+        self
+    }
+
+    /// Set a soft limit on the size of the DB.
+    ///
+    /// When a limit is set, changes that add data to the DB, such as [PickleDb::set()](struct.PickleDb.html#method.set),
+    /// [PickleDb::ladd()](struct.PickleDb.html#method.ladd) or [PickleDb::lextend()](struct.PickleDb.html#method.lextend),
+    /// fail if they would make the DB larger than `max_bytes`, and the DB is left unchanged. Methods that return a
+    /// `Result` return an error of type [ErrorType::SizeLimitExceeded](error/enum.ErrorType.html#variant.SizeLimitExceeded),
+    /// and list methods that return an `Option` return `None`. [PickleDb::try_ladd()](struct.PickleDb.html#method.try_ladd)
+    /// and [PickleDb::try_lextend()](struct.PickleDb.html#method.try_lextend) can be used instead of
+    /// [PickleDb::ladd()](struct.PickleDb.html#method.ladd) and [PickleDb::lextend()](struct.PickleDb.html#method.lextend)
+    /// to tell this failure apart from a list that doesn't exist. Changes that make the DB smaller are always allowed.
+    ///
+    /// The size of the DB is estimated using [PickleDb::memory_footprint()](struct.PickleDb.html#method.memory_footprint)
+    /// rather than by serializing it, so the DB file may be somewhat larger than the limit.
+    ///
+    pub fn max_bytes(mut self, max_bytes: usize) -> PickleDbBuilder {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Set whether the DB file is locked, so it can't be written by more than one DB instance at a time.
+    ///
+    /// When this option is enabled, the DB takes an advisory lock on a lock file next to the DB file (the DB file
+    /// name followed by `.lock`) and holds it until the DB is dropped. A DB that writes to the file takes an exclusive
+    /// lock, so no other DB can lock the file, and a DB loaded with the
+    /// [PickleDbDumpPolicy::NeverDump](enum.PickleDbDumpPolicy.html#variant.NeverDump) policy takes a shared lock, which
+    /// still allows other read-only DBs to lock the file. A loaded DB takes the lock before reading the file, and a new DB
+    /// takes it when it's first dumped, since it doesn't touch the file before that.
+    ///
+    /// If the file is already locked, loading or dumping the DB fails with an error of type
+    /// [ErrorType::Locked](error/enum.ErrorType.html#variant.Locked), unless [wait_for_lock()](#method.wait_for_lock)
+    /// is set. Please note that the lock is advisory, so it only affects DBs that were opened with this option.
+    ///
+    #[cfg(feature = "lock")]
+    pub fn lock(mut self, lock: bool) -> PickleDbBuilder {
+        self.lock = lock;
+        self
+    }
+
+    /// Set whether taking the lock waits until the lock is released by the DB that holds it, instead of failing.
+    /// Please see [lock()](#method.lock) for more information.
+    ///
+    #[cfg(feature = "lock")]
+    pub fn wait_for_lock(mut self, wait_for_lock: bool) -> PickleDbBuilder {
+        self.wait_for_lock = wait_for_lock;
+        self
+    }
+
+    /// Create a new DB with the options set in this builder.
+    ///
+    /// This is the same as calling [PickleDb::new()](struct.PickleDb.html#method.new), meaning the
+    /// DB is empty and nothing is written to the file until the first dump.
+    ///
+    pub fn build(self) -> PickleDb {
+        let mut db = PickleDb::new(self.db_path, self.dump_policy, self.serialization_method);
+        #[cfg(feature = "compression")]
+        if let Some(compression) = self.compression {
+            db.compression = compression;
+        }
+        #[cfg(feature = "crypt")]
+        {
+            db.encryption_key = self.encryption_key;
+        }
+        #[cfg(feature = "json")]
+        db.serializer.set_pretty_json(self.pretty_json);
+        db.temp_dir = self.temp_dir;
+        db.create_dirs = self.create_dirs;
+        db.wal = self.wal;
+        db.max_bytes = self.max_bytes;
+        #[cfg(feature = "lock")]
+        if self.lock {
+            db.file_lock = Some(FileLock::new(self.wait_for_lock));
+        }
+        db
+    }
+
+    /// Load a DB from a file with the options set in this builder.
+    ///
+    /// This is the same as calling [PickleDb::load()](struct.PickleDb.html#method.load): upon success
+    /// an instance of `PickleDb` is returned, otherwise an [Error](error/struct.Error.html) object is returned.
+    ///
+    pub fn load(self) -> Result<PickleDb> {
+        #[cfg(feature = "crypt")]
+        let encryption_key = self.encryption_key;
+        #[cfg(not(feature = "crypt"))]
+        let encryption_key = None;
+
+        #[cfg(feature = "lock")]
+        let file_lock = if self.lock {
+            // read-only DBs never write to the file, so a shared lock is enough for them
+            let mut file_lock = FileLock::new(self.wait_for_lock);
+            let exclusive = !matches!(self.dump_policy, PickleDbDumpPolicy::NeverDump);
+            file_lock.acquire(&self.db_path, exclusive)?;
+            Some(file_lock)
+        } else {
+            None
+        };
+
+        let mut db = PickleDb::load_file(
+            &self.db_path,
+            self.dump_policy,
+            Serializer::new(self.serialization_method),
+            encryption_key,
+        )?;
+        PickleDb::cleanup_temp_files(&self.db_path, self.dump_policy, self.temp_dir.as_deref());
+        #[cfg(feature = "compression")]
+        if let Some(compression) = self.compression {
+            db.compression = compression;
+        }
+        #[cfg(feature = "json")]
+        db.serializer.set_pretty_json(self.pretty_json);
+        db.temp_dir = self.temp_dir;
+        db.create_dirs = self.create_dirs;
+        db.wal = self.wal;
+        db.max_bytes = self.max_bytes;
+        #[cfg(feature = "lock")]
+        {
+            db.file_lock = file_lock;
+        }
+        Ok(db)
+    }
+}
@@ -0,0 +1,45 @@
+/// A header that is written at the beginning of DB files, followed by the CRC32 of the rest of the
+/// file as 8 hex digits and a new line. It's used for detecting corrupted or partially written
+/// files when loading them. Files without this header (for example files that were written by
+/// older versions) are loaded as is. Plain DB files can't start with this header in any of the
+/// serialization methods.
+const CHECKSUM_MAGIC: &[u8] = b"PICKLEDB-CRC32 ";
+const CHECKSUM_LEN: usize = 8;
+const HEADER_LEN: usize = CHECKSUM_MAGIC.len() + CHECKSUM_LEN + 1;
+
+pub(crate) fn has_checksum(data: &[u8]) -> bool {
+    data.starts_with(CHECKSUM_MAGIC)
+}
+
+pub(crate) fn add_checksum(data: Vec<u8>) -> Vec<u8> {
+    let mut content = Vec::with_capacity(HEADER_LEN + data.len());
+    content.extend_from_slice(CHECKSUM_MAGIC);
+    content.extend_from_slice(format!("{:08x}\n", crc32fast::hash(&data)).as_bytes());
+    content.extend(data);
+    content
+}
+
+pub(crate) fn verify_checksum(mut data: Vec<u8>) -> Result<Vec<u8>, String> {
+    if data.len() < HEADER_LEN || data[HEADER_LEN - 1] != b'\n' {
+        return Err(String::from("The DB file header is truncated or invalid"));
+    }
+
+    let expected_checksum = std::str::from_utf8(&data[CHECKSUM_MAGIC.len()..HEADER_LEN - 1])
+        .ok()
+        .and_then(|checksum| u32::from_str_radix(checksum, 16).ok());
+    let expected_checksum = match expected_checksum {
+        Some(expected_checksum) => expected_checksum,
+        None => return Err(String::from("The DB file header is truncated or invalid")),
+    };
+
+    let payload = data.split_off(HEADER_LEN);
+    let checksum = crc32fast::hash(&payload);
+    if checksum != expected_checksum {
+        return Err(format!(
+            "The DB file is corrupted: expected checksum {:08x} but found {:08x}",
+            expected_checksum, checksum
+        ));
+    }
+
+    Ok(payload)
+}
@@ -0,0 +1,35 @@
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::io::{Read, Write};
+
+/// A header that is written at the beginning of compressed DB files. It's used for detecting
+/// compressed files when loading them, so plain files can still be loaded as is.
+/// Plain DB files can't start with this header in any of the serialization methods.
+const COMPRESSION_MAGIC: &[u8] = b"PICKLEDB-GZIP\x00";
+
+pub(crate) fn is_compressed(data: &[u8]) -> bool {
+    data.starts_with(COMPRESSION_MAGIC)
+}
+
+pub(crate) fn compress(data: &[u8]) -> Result<Vec<u8>, String> {
+    let mut encoder = GzEncoder::new(COMPRESSION_MAGIC.to_vec(), Compression::default());
+    match encoder.write_all(data) {
+        Ok(_) => (),
+        Err(err) => return Err(err.to_string()),
+    }
+
+    match encoder.finish() {
+        Ok(compressed_data) => Ok(compressed_data),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+pub(crate) fn decompress(data: &[u8]) -> Result<Vec<u8>, String> {
+    let mut decoder = GzDecoder::new(&data[COMPRESSION_MAGIC.len()..]);
+    let mut decompressed_data = Vec::new();
+    match decoder.read_to_end(&mut decompressed_data) {
+        Ok(_) => Ok(decompressed_data),
+        Err(err) => Err(err.to_string()),
+    }
+}
@@ -0,0 +1,42 @@
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+
+/// A header that is written at the beginning of encrypted DB files. It's used for detecting
+/// encrypted files when loading them, so a clear error can be returned if no key is provided.
+/// It's followed by a random nonce which is generated for every dump, and then by the encrypted data.
+const ENCRYPTION_MAGIC: &[u8] = b"PICKLEDB-AES256GCM\x00";
+const NONCE_LEN: usize = 12;
+
+pub(crate) fn is_encrypted(data: &[u8]) -> bool {
+    data.starts_with(ENCRYPTION_MAGIC)
+}
+
+pub(crate) fn encrypt(key: &[u8; 32], data: &[u8]) -> Result<Vec<u8>, String> {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let encrypted_data = match cipher.encrypt(&nonce, data) {
+        Ok(encrypted_data) => encrypted_data,
+        Err(err) => return Err(err.to_string()),
+    };
+
+    let mut result = ENCRYPTION_MAGIC.to_vec();
+    result.extend_from_slice(&nonce);
+    result.extend_from_slice(&encrypted_data);
+    Ok(result)
+}
+
+pub(crate) fn decrypt(key: &[u8; 32], data: &[u8]) -> Result<Vec<u8>, String> {
+    let data = &data[ENCRYPTION_MAGIC.len()..];
+    if data.len() < NONCE_LEN {
+        return Err(String::from("The encrypted DB file is truncated"));
+    }
+
+    let (nonce, encrypted_data) = data.split_at(NONCE_LEN);
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    match cipher.decrypt(Nonce::from_slice(nonce), encrypted_data) {
+        Ok(decrypted_data) => Ok(decrypted_data),
+        Err(_) => Err(String::from(
+            "Cannot decrypt the DB file, the encryption key is wrong or the file is corrupted",
+        )),
+    }
+}
@@ -1,5 +1,6 @@
 use std::fmt;
 use std::io;
+use std::path::PathBuf;
 use std::result;
 
 /// An enum that represents all types of errors that can occur when using PickleDB
@@ -9,11 +10,27 @@ pub enum ErrorType {
     Io,
     /// An error when trying to serialize or deserialize data
     Serialization,
+    /// An error when trying to access a list that doesn't exist
+    ListNotFound,
+    /// An error when trying to decrypt an encrypted DB file, for example: a wrong encryption key
+    Decryption,
+    /// An error when the content of the DB file doesn't match its checksum, for example: a file that was
+    /// only partially written
+    Corrupted,
+    /// An error when a change would make the DB larger than its size limit. Please see
+    /// [PickleDbBuilder::max_bytes()](../struct.PickleDbBuilder.html#method.max_bytes) for more information
+    SizeLimitExceeded,
+    /// An error when the DB file is locked by another DB instance, usually in another process. Please see
+    /// [PickleDbBuilder::lock()](../struct.PickleDbBuilder.html#method.lock) for more information
+    Locked,
+    /// An error when trying to access a set that doesn't exist
+    SetNotFound,
 }
 
 /// A struct that represents all possible errors that can occur when using PickleDB
 pub struct Error {
     err_code: ErrorCode,
+    key: Option<String>,
 }
 
 /// Alias for a `Result` with the error type [Error](struct.Error.html).
@@ -21,7 +38,15 @@ pub type Result<T> = result::Result<T, Error>;
 
 impl Error {
     pub(crate) fn new(err_code: ErrorCode) -> Error {
-        Error { err_code }
+        Error {
+            err_code,
+            key: None,
+        }
+    }
+
+    pub(crate) fn with_key(mut self, key: &str) -> Error {
+        self.key = Some(String::from(key));
+        self
     }
 
     /// Get the error type
@@ -29,8 +54,23 @@ impl Error {
         match self.err_code {
             ErrorCode::Io(_) => ErrorType::Io,
             ErrorCode::Serialization(_) => ErrorType::Serialization,
+            ErrorCode::ListNotFound(_) => ErrorType::ListNotFound,
+            ErrorCode::Decryption(_) => ErrorType::Decryption,
+            ErrorCode::Corrupted(_) => ErrorType::Corrupted,
+            ErrorCode::SizeLimitExceeded(_) => ErrorType::SizeLimitExceeded,
+            ErrorCode::Locked(_) => ErrorType::Locked,
+            ErrorCode::SetNotFound(_) => ErrorType::SetNotFound,
         }
     }
+
+    /// Get the key (or list name) the error relates to, if it's known.
+    ///
+    /// For example, when a value can't be serialized in [PickleDb::set()](../struct.PickleDb.html#method.set)
+    /// this is the key the value was set to. Returns `None` for errors that don't relate to a specific key,
+    /// such as I/O errors when dumping the DB.
+    pub fn get_key(&self) -> Option<&str> {
+        self.key.as_deref()
+    }
 }
 
 impl fmt::Display for Error {
@@ -38,6 +78,20 @@ impl fmt::Display for Error {
         match self.err_code {
             ErrorCode::Io(ref err) => fmt::Display::fmt(err, f),
             ErrorCode::Serialization(ref err_str) => f.write_str(err_str),
+            ErrorCode::ListNotFound(ref name) => write!(f, "List '{}' doesn't exist", name),
+            ErrorCode::Decryption(ref err_str) => f.write_str(err_str),
+            ErrorCode::Corrupted(ref err_str) => f.write_str(err_str),
+            ErrorCode::SizeLimitExceeded(max_bytes) => {
+                write!(
+                    f,
+                    "The DB size limit of {} bytes would be exceeded",
+                    max_bytes
+                )
+            }
+            ErrorCode::Locked(ref path) => {
+                write!(f, "The DB file is locked by '{}'", path.display())
+            }
+            ErrorCode::SetNotFound(ref name) => write!(f, "Set '{}' doesn't exist", name),
         }
     }
 }
@@ -49,6 +103,14 @@ impl fmt::Debug for Error {
             match self.err_code {
                 ErrorCode::Io(ref err) => err.to_string(),
                 ErrorCode::Serialization(ref err_str) => err_str.to_string(),
+                ErrorCode::ListNotFound(ref name) => format!("List '{}' doesn't exist", name),
+                ErrorCode::Decryption(ref err_str) => err_str.to_string(),
+                ErrorCode::Corrupted(ref err_str) => err_str.to_string(),
+                ErrorCode::SizeLimitExceeded(max_bytes) =>
+                    format!("The DB size limit of {} bytes would be exceeded", max_bytes),
+                ErrorCode::Locked(ref path) =>
+                    format!("The DB file is locked by '{}'", path.display()),
+                ErrorCode::SetNotFound(ref name) => format!("Set '{}' doesn't exist", name),
             }
         ))
     }
@@ -59,4 +121,12 @@ impl std::error::Error for Error {}
 pub(crate) enum ErrorCode {
     Io(io::Error),
     Serialization(String),
+    ListNotFound(String),
+    #[cfg_attr(not(feature = "crypt"), allow(dead_code))]
+    Decryption(String),
+    Corrupted(String),
+    SizeLimitExceeded(usize),
+    #[cfg_attr(not(feature = "lock"), allow(dead_code))]
+    Locked(PathBuf),
+    SetNotFound(String),
 }
@@ -1,4 +1,5 @@
 use crate::pickledb::PickleDb;
+use crate::value::PickleValue;
 use serde::Serialize;
 
 /// A struct for extending PickleDB lists and adding more items to them
@@ -34,13 +35,40 @@ impl<'a> PickleDbListExtender<'a> {
     ///   .ladd(&vec!["aa", "bb", "cc"]);
     /// ```
     ///
-    pub fn ladd<V>(&mut self, value: &V) -> PickleDbListExtender
+    pub fn ladd<V>(&mut self, value: &V) -> PickleDbListExtender<'_>
     where
         V: Serialize,
     {
         self.db.ladd(&self.list_name, value).unwrap()
     }
 
+    /// Add a single tagged item to an existing list.
+    ///
+    /// This method is similar to [ladd()](#method.ladd) but the item is stored as a
+    /// [PickleValue](enum.PickleValue.html), so it can be read back without knowing its type.
+    /// The method returns another `PickleDbListExtender` object that enables to continue adding
+    /// items to the list.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - a reference of the item to add to the list
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use pickledb::PickleValue;
+    ///
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// // create a new list and add tagged items of different types to it
+    /// db.lcreate("list1").unwrap()
+    ///   .ladd_value(&PickleValue::from(100))
+    ///   .ladd_value(&PickleValue::from(vec!["aa", "bb", "cc"]));
+    /// ```
+    ///
+    pub fn ladd_value(&mut self, value: &PickleValue) -> PickleDbListExtender<'_> {
+        self.db.ladd_value(&self.list_name, value).unwrap()
+    }
+
     /// Add multiple items to an existing list.
     ///
     /// As mentioned before, the lists are heterogeneous, meaning a single list can contain
@@ -73,11 +101,109 @@ impl<'a> PickleDbListExtender<'a> {
     /// // now the list contains 6 items and looks like this: [100, 200, 300, "aa, "bb", "cc"]
     /// ```
     ///
-    pub fn lextend<'i, V, I>(&mut self, seq: I) -> PickleDbListExtender
+    pub fn lextend<'i, V, I>(&mut self, seq: I) -> PickleDbListExtender<'_>
     where
         V: 'i + Serialize,
         I: IntoIterator<Item = &'i V>,
     {
         self.db.lextend(&self.list_name, seq).unwrap()
     }
+
+    /// Insert a single item to an existing list in a certain position.
+    ///
+    /// The item is inserted before the item currently in position `pos`, meaning that after
+    /// the insertion the new item will be in position `pos`.
+    /// The method returns another `PickleDbListExtender` object that enables to continue adding
+    /// items to the list.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - the position to insert the item in. Must not be greater than the length of the list
+    /// * `value` - a reference of the item to insert to the list
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// // create a new list
+    /// db.lcreate("list1").unwrap()
+    ///
+    /// // add items and insert an item between them
+    ///   .lextend(&vec![1, 3])
+    ///   .linsert(1, &2);
+    ///
+    /// // now the list looks like this: [1, 2, 3]
+    /// ```
+    ///
+    pub fn linsert<V>(&mut self, pos: usize, value: &V) -> PickleDbListExtender<'_>
+    where
+        V: Serialize,
+    {
+        self.db.linsert(&self.list_name, pos, value).unwrap()
+    }
+
+    /// Add a single item to the front of an existing list.
+    ///
+    /// The item is added before all the other items in the list, meaning that after this call
+    /// it will be in position 0.
+    /// The method returns another `PickleDbListExtender` object that enables to continue adding
+    /// items to the list.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - a reference of the item to add to the list
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// // create a new list
+    /// db.lcreate("list1").unwrap()
+    ///
+    /// // add items to the front of the list
+    ///   .lpush_front(&3)
+    ///   .lpush_front(&2)
+    ///   .lpush_front(&1);
+    ///
+    /// // now the list looks like this: [1, 2, 3]
+    /// ```
+    ///
+    pub fn lpush_front<V>(&mut self, value: &V) -> PickleDbListExtender<'_>
+    where
+        V: Serialize,
+    {
+        self.db.lpush_front(&self.list_name, value).unwrap()
+    }
+
+    /// Add multiple items to the front of an existing list.
+    ///
+    /// The items are added before all the other items in the list and their order is preserved.
+    /// The method returns another `PickleDbListExtender` object that enables to continue adding
+    /// items to the list.
+    ///
+    /// # Arguments
+    ///
+    /// * `seq` - an iterator containing references to the new items to add to the list
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// // create a new list
+    /// db.lcreate("list1").unwrap()
+    ///
+    /// // add items to the front of the list
+    ///   .lextend_front(&vec![3, 4])
+    ///   .lextend_front(&vec![1, 2]);
+    ///
+    /// // now the list looks like this: [1, 2, 3, 4]
+    /// ```
+    ///
+    pub fn lextend_front<'i, V, I>(&mut self, seq: I) -> PickleDbListExtender<'_>
+    where
+        V: 'i + Serialize,
+        I: IntoIterator<Item = &'i V>,
+    {
+        self.db.lextend_front(&self.list_name, seq).unwrap()
+    }
 }
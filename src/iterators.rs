@@ -1,12 +1,19 @@
 use serde::de::DeserializeOwned;
-use std::collections::hash_map;
+use std::collections::{hash_map, HashMap};
 use std::slice;
+use std::time::SystemTime;
 
+use crate::error::{Error, ErrorCode, Result};
 use crate::serialization::Serializer;
 
 /// Iterator object for iterating over keys and values in PickleDB. Returned in [PickleDb::iter()](struct.PickleDb.html#method.iter)
 pub struct PickleDbIterator<'a> {
     pub(crate) map_iter: hash_map::Iter<'a, String, Vec<u8>>,
+    // expired keys are skipped, they're checked against the time the iterator was created
+    pub(crate) expiry_map: &'a HashMap<String, SystemTime>,
+    pub(crate) now: SystemTime,
+    // the number of keys that weren't returned yet, not including expired keys
+    pub(crate) remaining: usize,
     pub(crate) serializer: &'a Serializer,
 }
 
@@ -14,22 +21,32 @@ impl<'a> Iterator for PickleDbIterator<'a> {
     type Item = PickleDbIteratorItem<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.map_iter.next() {
-            Some((key, value)) => Some(PickleDbIteratorItem {
+        for (key, value) in self.map_iter.by_ref() {
+            if matches!(self.expiry_map.get(key), Some(expiry) if *expiry <= self.now) {
+                continue;
+            }
+            self.remaining = self.remaining.saturating_sub(1);
+            return Some(PickleDbIteratorItem {
                 key,
                 value,
                 serializer: self.serializer,
-            }),
-            None => None,
+            });
         }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
     }
 }
 
+impl<'a> ExactSizeIterator for PickleDbIterator<'a> {}
+
 /// The object returned in each iteration when iterating over keys and values in PickleDB
 pub struct PickleDbIteratorItem<'a> {
-    key: &'a str,
-    value: &'a Vec<u8>,
-    serializer: &'a Serializer,
+    pub(crate) key: &'a str,
+    pub(crate) value: &'a Vec<u8>,
+    pub(crate) serializer: &'a Serializer,
 }
 
 impl<'a> PickleDbIteratorItem<'a> {
@@ -54,11 +71,30 @@ impl<'a> PickleDbIteratorItem<'a> {
     {
         self.serializer.deserialize_data::<V>(self.value)
     }
+
+    /// Get the value of the key, or the deserialization error if it fails.
+    ///
+    /// This method is similar to [get_value()](#method.get_value), but instead of returning `None`
+    /// if the value can't be deserialized to `V`, it returns an `Err(`[Error](error/struct.Error.html)`)`
+    /// of type [ErrorType::Serialization](error/enum.ErrorType.html#variant.Serialization) that contains
+    /// the message of the underlying serialization library. This is useful for debugging type mismatches.
+    ///
+    pub fn try_get_value<V>(&self) -> Result<V>
+    where
+        V: DeserializeOwned,
+    {
+        match self.serializer.deserialize_data_result::<V>(self.value) {
+            Ok(value) => Ok(value),
+            Err(err_str) => Err(Error::new(ErrorCode::Serialization(err_str))),
+        }
+    }
 }
 
 /// Iterator object for iterating over items in a PickleDB list. Returned in [PickleDb::liter()](struct.PickleDb.html#method.liter)
 pub struct PickleDbListIterator<'a> {
     pub(crate) list_iter: slice::Iter<'a, Vec<u8>>,
+    // the position in the list of the next item from the front
+    pub(crate) pos: usize,
     pub(crate) serializer: &'a Serializer,
 }
 
@@ -67,8 +103,31 @@ impl<'a> Iterator for PickleDbListIterator<'a> {
 
     fn next(&mut self) -> Option<Self::Item> {
         match self.list_iter.next() {
+            Some(value) => {
+                self.pos += 1;
+                Some(PickleDbListIteratorItem {
+                    value,
+                    index: self.pos - 1,
+                    serializer: self.serializer,
+                })
+            }
+            None => None,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.list_iter.size_hint()
+    }
+}
+
+impl<'a> ExactSizeIterator for PickleDbListIterator<'a> {}
+
+impl<'a> DoubleEndedIterator for PickleDbListIterator<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.list_iter.next_back() {
             Some(value) => Some(PickleDbListIteratorItem {
                 value,
+                index: self.pos + self.list_iter.len(),
                 serializer: self.serializer,
             }),
             None => None,
@@ -79,10 +138,21 @@ impl<'a> Iterator for PickleDbListIterator<'a> {
 /// The object returned in each iteration when iterating over a PickleDB list
 pub struct PickleDbListIteratorItem<'a> {
     value: &'a Vec<u8>,
+    index: usize,
     serializer: &'a Serializer,
 }
 
 impl<'a> PickleDbListIteratorItem<'a> {
+    /// Get the position of the item in the list.
+    ///
+    /// The position is the same regardless of the direction of the iteration, so it can be used later
+    /// in methods such as [PickleDb::lset()](struct.PickleDb.html#method.lset) or
+    /// [PickleDb::lpop()](struct.PickleDb.html#method.lpop).
+    ///
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
     /// Get the item in the current position.
     ///
     /// This method retrieves the item in the current position. It's the user's responsibility
@@ -97,4 +167,82 @@ impl<'a> PickleDbListIteratorItem<'a> {
     {
         self.serializer.deserialize_data(self.value)
     }
+
+    /// Get the item in the current position, or the deserialization error if it fails.
+    ///
+    /// This method is similar to [get_item()](#method.get_item), but instead of returning `None`
+    /// if the item can't be deserialized to `V`, it returns an `Err(`[Error](error/struct.Error.html)`)`
+    /// of type [ErrorType::Serialization](error/enum.ErrorType.html#variant.Serialization) that contains
+    /// the message of the underlying serialization library. This is useful for debugging type mismatches.
+    ///
+    pub fn try_get_item<V>(&self) -> Result<V>
+    where
+        V: DeserializeOwned,
+    {
+        match self.serializer.deserialize_data_result::<V>(self.value) {
+            Ok(item) => Ok(item),
+            Err(err_str) => Err(Error::new(ErrorCode::Serialization(err_str))),
+        }
+    }
+}
+
+/// Iterator object for iterating over all lists in PickleDB. Returned in [PickleDb::liter_all()](struct.PickleDb.html#method.liter_all)
+pub struct PickleDbListsIterator<'a> {
+    pub(crate) lists_iter: hash_map::Iter<'a, String, Vec<Vec<u8>>>,
+    pub(crate) serializer: &'a Serializer,
+}
+
+impl<'a> Iterator for PickleDbListsIterator<'a> {
+    type Item = PickleDbListsIteratorItem<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.lists_iter.next() {
+            Some((name, list)) => Some(PickleDbListsIteratorItem {
+                name,
+                list,
+                serializer: self.serializer,
+            }),
+            None => None,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.lists_iter.size_hint()
+    }
+}
+
+impl<'a> ExactSizeIterator for PickleDbListsIterator<'a> {}
+
+/// The object returned in each iteration when iterating over all lists in PickleDB.
+///
+/// This object can be iterated itself in order to go over the items in the list.
+pub struct PickleDbListsIteratorItem<'a> {
+    name: &'a str,
+    list: &'a [Vec<u8>],
+    serializer: &'a Serializer,
+}
+
+impl<'a> PickleDbListsIteratorItem<'a> {
+    /// Get the list name
+    pub fn name(&self) -> &str {
+        self.name
+    }
+
+    /// Return an iterator over the items in the list
+    pub fn iter(&self) -> PickleDbListIterator<'a> {
+        PickleDbListIterator {
+            list_iter: self.list.iter(),
+            pos: 0,
+            serializer: self.serializer,
+        }
+    }
+}
+
+impl<'a> IntoIterator for PickleDbListsIteratorItem<'a> {
+    type Item = PickleDbListIteratorItem<'a>;
+    type IntoIter = PickleDbListIterator<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
 }
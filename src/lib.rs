@@ -28,6 +28,9 @@
 //! * Manage lists. Every list has a name (which is its key in the key-value store) and a list of items it stores. PickleDB provides APIs to
 //!   create and delete lists and to add or remove items from them. Lists are also heterogeneous, meaning each list can store objects of different
 //!   types. Please see more details below
+//! * Manage sets. Like lists, every set has a name and stores items of any type, but each item is stored only once and
+//!   the items aren't kept in any order
+//! * Manage hashes. Every hash has a name and maps fields to values of any type, and each field can be set and read separately
 //! * Iterate over keys and values in the DB and over items in a list
 //!
 //! Please take a look at the API documentation to get more details.
@@ -46,6 +49,9 @@
 //! * Tuples
 //! * Structs and Enums that are serializable (please read more below)
 //!
+//! Reading a value or a list item requires knowing its type. When the types of list items aren't known in advance they can be stored as
+//! [PickleValue](enum.PickleValue.html), a tagged value that can be read back and matched on.
+//!
 //! ## Serialization
 //!
 //! Serialization is an important part of PickleDB. It is the way heterogeneous data structures are enabled: instead of saving the actual object,
@@ -58,18 +64,27 @@
 //! heterogeneous data structures.
 //!
 //! In order to achieve this magic, all objects must be serializable. PickleDB uses the [Serde](https://serde.rs/) library for serialization.
-//! Currently 4 types of serialization are supported:
+//! Currently 6 types of serialization are supported:
 //! * [JSON serialization](https://crates.io/crates/serde_json)
 //! * [Bincode serialization](https://crates.io/crates/bincode)
 //! * [YAML serialization](https://crates.io/crates/serde_yaml)
 //! * [CBOR serialization](https://crates.io/crates/serde_cbor)
+//! * [MessagePack serialization](https://crates.io/crates/rmp-serde)
+//! * [TOML serialization](https://crates.io/crates/toml)
 //!
-//! The serialization types are enabled and disabled with features (`json` (enabled by default), `bincode`, `yaml`, and `cbor`).
+//! The serialization types are enabled and disabled with features (`json` (enabled by default), `bincode`, `yaml`, `cbor`,
+//! `msgpack` and `toml`).
 //! To enable them, just add their names to the `features` list when declaring the dependency. To disable JSON, set `default-features` to false.
 //! For instance, `pickledb = { version = "0.5", features = ["cbor", "yaml"], default-features = false }` would enable CBOR and YAML only.
+//! The enabled serialization types can be checked at runtime using
+//! [SerializationMethod::is_enabled()](enum.SerializationMethod.html#method.is_enabled) and
+//! [SerializationMethod::enabled()](enum.SerializationMethod.html#method.enabled).
 //!
 //! The user can choose a serialization type to use upon creating a DB or loading it from a file.
 //!
+//! Other serialization formats can be used by implementing the [PickleDbSerializer](trait.PickleDbSerializer.html) trait and creating
+//! the DB with [PickleDb::new_with_serializer()](struct.PickleDb.html#method.new_with_serializer).
+//!
 //! So what does it mean that all objects must be serializable? That means that all objects that you use must be serializable.
 //! Fortunately Serde already provides out-of-the-box serialization for most of the common objects: all primitive types, strings, vectors and tuples
 //! are already serializable and you don't need to do anything to use them. But if you want to define your own structs or enums, you need to make sure
@@ -98,20 +113,85 @@
 //! * [PickleDbDumpPolicy::PeriodicDump(Duration)](enum.PickleDbDumpPolicy.html#variant.PeriodicDump) - changes will be dumped to the file periodically,
 //!   no sooner than the Duration provided by the user. The way this mechanism works is as follows: each time there is a DB change the last DB dump time
 //!   is checked. If the time that has passed since the last dump is higher than Duration, changes will be dumped, otherwise changes will not be dumped.
+//! * [PickleDbDumpPolicy::AutoDumpEvery(usize)](enum.PickleDbDumpPolicy.html#variant.AutoDumpEvery) - changes will be dumped to the file once every
+//!   N changes, where N is the number provided by the user. This gives a predictable number of writes for bursts of changes.
 //!
 //! Apart from this dump policy, persistency is also kept by a implementing the `Drop` trait for the `PickleDB` object which ensures all in-memory data
 //! is dumped to the file upon destruction of the object.
 //!
+//! Binary DB files (Bincode, CBOR and MessagePack files, and compressed or encrypted files) start with a short header line that holds a checksum
+//! of the rest of the file. When loading a file its content is verified against this checksum, so a file that was only partially written or was
+//! corrupted returns an [ErrorType::Corrupted](error/enum.ErrorType.html#variant.Corrupted) error instead of silently loading a truncated DB.
+//! JSON, YAML and TOML files are written without this header so they remain valid files that other tools can read and that can be edited by hand.
+//! Files without this header, for example files written by older versions, are loaded as is.
+//!
+//! For large DBs that change often, rewriting the whole file on every dump may become the bottleneck. In this case a write-ahead log can be
+//! enabled using [PickleDbBuilder::wal()](struct.PickleDbBuilder.html#method.wal): each dump then appends only the keys that changed to a log file,
+//! which is folded back into the DB file from time to time or when calling [compact()](struct.PickleDb.html#method.compact).
+//!
+//! ## Sharing a DB between threads
+//!
+//! `PickleDb` is a single-threaded object, but when the `sync` feature is enabled it can be wrapped in a
+//! [SyncPickleDb](struct.SyncPickleDb.html), which can be cloned and shared between threads. Each of its methods locks
+//! the DB for the duration of a single operation.
+//!
+//! ## Compression
+//!
+//! When the `compression` feature is enabled, DB files can be compressed with [gzip](https://crates.io/crates/flate2) before they're
+//! written, which can make them much smaller, especially for text based serialization methods such as JSON and YAML. To use it, create the DB with
+//! [PickleDb::new_with_compression()](struct.PickleDb.html#method.new_with_compression) or with
+//! [PickleDbBuilder::compression()](struct.PickleDbBuilder.html#method.compression). There is no need to do anything special when loading
+//! a DB: compressed files are detected automatically, and a DB that was loaded from a compressed file keeps being compressed when it's dumped.
+//!
+//! ## Encryption
+//!
+//! When the `crypt` feature is enabled, DB files can be encrypted with [AES-256-GCM](https://crates.io/crates/aes-gcm) using a 32 bytes key
+//! provided by the user, so sensitive data isn't stored on the disk as plain text. To use it, create the DB with
+//! [PickleDb::new_with_encryption()](struct.PickleDb.html#method.new_with_encryption) and load it with
+//! [PickleDb::load_with_encryption()](struct.PickleDb.html#method.load_with_encryption) and the same key, or use
+//! [PickleDbBuilder::encryption_key()](struct.PickleDbBuilder.html#method.encryption_key). Loading an encrypted file with a wrong key
+//! fails with an error of type [ErrorType::Decryption](error/enum.ErrorType.html#variant.Decryption).
+//!
+//! ## Sharing a DB file between processes
+//!
+//! Two processes that dump the same DB file override each other's changes. When the `lock` feature is enabled, this can be
+//! prevented by opening the DB with [PickleDbBuilder::lock()](struct.PickleDbBuilder.html#method.lock), which takes an advisory
+//! lock on the DB file (using a lock file next to it). A DB that writes to the file holds an exclusive lock, a read-only DB holds a
+//! shared lock, and opening a DB whose file is locked by another process fails with an error of type
+//! [ErrorType::Locked](error/enum.ErrorType.html#variant.Locked), or waits for the lock to be released.
+//!
+pub use self::batch::PickleDbBatch;
+pub use self::builder::PickleDbBuilder;
 pub use self::extenders::PickleDbListExtender;
 pub use self::iterators::{
     PickleDbIterator, PickleDbIteratorItem, PickleDbListIterator, PickleDbListIteratorItem,
+    PickleDbListsIterator, PickleDbListsIteratorItem,
 };
-pub use self::pickledb::{PickleDb, PickleDbDumpPolicy};
-pub use self::serialization::SerializationMethod;
+pub use self::pickledb::{ChangeEvent, PickleDb, PickleDbDumpPolicy, PickleDbMergePolicy};
+pub use self::prefixed::{PrefixedDb, PrefixedDbIterator};
+pub use self::serialization::{PickleDbSerializer, SerializationMethod, ValueKind};
+#[cfg(feature = "sync")]
+pub use self::sync::SyncPickleDb;
+pub use self::value::PickleValue;
+pub use erased_serde;
 
+mod batch;
+mod builder;
+mod checksum;
+#[cfg(feature = "compression")]
+mod compression;
+#[cfg(feature = "crypt")]
+mod crypt;
 mod extenders;
 mod iterators;
+#[cfg(feature = "lock")]
+mod lock;
 mod pickledb;
+mod prefixed;
 mod serialization;
+#[cfg(feature = "sync")]
+mod sync;
+mod value;
+mod wal;
 
 pub mod error;
@@ -0,0 +1,62 @@
+use fs2::FileExt;
+use std::fs::{File, OpenOptions};
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, ErrorCode, Result};
+
+// The lock is taken on a separate file next to the DB file rather than on the DB file itself,
+// because the DB file is replaced (by renaming a temp file over it) every time it's dumped
+pub(crate) fn lock_path(db_path: &Path) -> PathBuf {
+    let mut lock_file_name = db_path.file_name().unwrap_or_default().to_os_string();
+    lock_file_name.push(".lock");
+    db_path.with_file_name(lock_file_name)
+}
+
+// An advisory lock on the DB file which is held until it's dropped
+pub(crate) struct FileLock {
+    file: Option<File>,
+    wait: bool,
+}
+
+impl FileLock {
+    pub(crate) fn new(wait: bool) -> FileLock {
+        FileLock { file: None, wait }
+    }
+
+    // Take the lock if it isn't held yet. A shared lock allows other shared locks but no exclusive
+    // locks, an exclusive lock doesn't allow any other lock
+    pub(crate) fn acquire(&mut self, db_path: &Path, exclusive: bool) -> Result<()> {
+        if self.file.is_some() {
+            return Ok(());
+        }
+
+        let lock_path = lock_path(db_path);
+        let file = match OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&lock_path)
+        {
+            Ok(file) => file,
+            Err(err) => return Err(Error::new(ErrorCode::Io(err))),
+        };
+
+        let result = match (exclusive, self.wait) {
+            (true, true) => FileExt::lock_exclusive(&file),
+            (true, false) => FileExt::try_lock_exclusive(&file),
+            (false, true) => FileExt::lock_shared(&file),
+            (false, false) => FileExt::try_lock_shared(&file),
+        };
+        match result {
+            Ok(_) => (),
+            Err(err) if err.raw_os_error() == fs2::lock_contended_error().raw_os_error() => {
+                return Err(Error::new(ErrorCode::Locked(lock_path)))
+            }
+            Err(err) => return Err(Error::new(ErrorCode::Io(err))),
+        }
+
+        self.file = Some(file);
+        Ok(())
+    }
+}
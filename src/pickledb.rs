@@ -1,16 +1,42 @@
 use serde::{de::DeserializeOwned, Serialize};
-use std::collections::HashMap;
+use std::any::{Any, TypeId};
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
 use std::fs;
+use std::hash::Hasher;
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{self, AtomicUsize};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
+use crate::batch::PickleDbBatch;
+use crate::checksum;
+#[cfg(feature = "compression")]
+use crate::compression;
+#[cfg(feature = "crypt")]
+use crate::crypt;
 use crate::error::{Error, ErrorCode, Result};
 use crate::extenders::PickleDbListExtender;
-use crate::iterators::{PickleDbIterator, PickleDbListIterator};
+use crate::iterators::{PickleDbIterator, PickleDbListIterator, PickleDbListsIterator};
+#[cfg(feature = "lock")]
+use crate::lock::FileLock;
+use crate::prefixed::PrefixedDb;
+use crate::serialization::PickleDbSerializer;
 use crate::serialization::SerializationMethod;
 use crate::serialization::Serializer;
+use crate::serialization::{DeserializedValueKind, GenericValue, ValueKind};
+use crate::value::PickleValue;
+use crate::wal;
+
+static TEMP_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+// a temp file that wasn't modified for this long is considered left over by a dump that didn't complete
+const STALE_TEMP_FILE_AGE: Duration = Duration::from_secs(60 * 60);
 
 /// An enum that determines the policy of dumping PickleDb changes into the file
+#[derive(Clone, Copy)]
 pub enum PickleDbDumpPolicy {
     /// Never dump any change, file will always remain read-only
     NeverDump,
@@ -23,16 +49,89 @@ pub enum PickleDbDumpPolicy {
     /// If the time that has passed since the last dump is higher than Duration, changes will be dumped,
     /// otherwise changes will not be dumped
     PeriodicDump(Duration),
+    /// Changes will be dumped to the file once every N changes, where N is the number provided by the user.
+    /// Each change (for example setting a value or adding an item to a list) is counted, and when the count reaches N
+    /// changes will be dumped and the count starts over. A batch of changes is counted as a single change
+    AutoDumpEvery(usize),
+}
+
+/// An enum that determines how conflicts are resolved when merging another DB using
+/// [PickleDb::merge()](struct.PickleDb.html#method.merge)
+pub enum PickleDbMergePolicy {
+    /// Keys that exist in both DBs get the value or list of the merged DB
+    Overwrite,
+    /// Keys that exist in both DBs keep their current value or list
+    SkipExisting,
+}
+
+/// An enum that describes a change of a key in the DB. Passed to the callback registered with
+/// [PickleDb::on_change()](struct.PickleDb.html#method.on_change)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangeEvent {
+    /// A value was set to the key
+    Set(String),
+    /// The key was removed, either a value or a list
+    Removed(String),
+    /// A list was created or modified
+    ListModified(String),
+    /// A set was created or modified
+    SetModified(String),
+    /// A hash was created or modified
+    HashModified(String),
+}
+
+// the data of a DB file after it was parsed
+struct FileContent {
+    map: HashMap<String, Vec<u8>>,
+    list_map: HashMap<String, Vec<Vec<u8>>>,
+    expiry_map: HashMap<String, SystemTime>,
+    set_map: HashMap<String, HashSet<Vec<u8>>>,
+    hash_map: HashMap<String, HashMap<String, Vec<u8>>>,
+    #[cfg(feature = "compression")]
+    compression: bool,
 }
 
+type DumpCallback = Box<dyn FnMut(&Path, usize) + Send>;
+type ChangeCallback = Box<dyn FnMut(ChangeEvent) + Send>;
+// deserialized values returned by get_cached(), by key and by the type they were deserialized into
+type ValueCache = HashMap<String, HashMap<TypeId, Box<dyn Any + Send>>>;
+
 /// A struct that represents a PickleDb object
 pub struct PickleDb {
     map: HashMap<String, Vec<u8>>,
     list_map: HashMap<String, Vec<Vec<u8>>>,
-    serializer: Serializer,
+    expiry_map: HashMap<String, SystemTime>,
+    set_map: HashMap<String, HashSet<Vec<u8>>>,
+    hash_map: HashMap<String, HashMap<String, Vec<u8>>>,
+    pub(crate) serializer: Serializer,
     db_file_path: PathBuf,
     dump_policy: PickleDbDumpPolicy,
     last_dump: Instant,
+    changes_since_dump: usize,
+    pub(crate) temp_dir: Option<PathBuf>,
+    pub(crate) create_dirs: bool,
+    flush_on_drop: bool,
+    dirty: bool,
+    pub(crate) wal: bool,
+    wal_keys: HashSet<String>,
+    wal_full_dump: bool,
+    wal_size: u64,
+    db_file_size: u64,
+    db_file_crc: u32,
+    last_dump_hash: Option<u64>,
+    pub(crate) max_bytes: Option<usize>,
+    used_bytes: usize,
+    #[cfg(feature = "lock")]
+    pub(crate) file_lock: Option<FileLock>,
+    #[cfg(feature = "compression")]
+    pub(crate) compression: bool,
+    #[cfg(feature = "crypt")]
+    pub(crate) encryption_key: Option<[u8; 32]>,
+    pub(crate) batch_depth: usize,
+    pub(crate) batch_changed: bool,
+    on_dump: Option<DumpCallback>,
+    on_change: Option<ChangeCallback>,
+    value_cache: Mutex<ValueCache>,
 }
 
 impl PickleDb {
@@ -42,7 +141,7 @@ impl PickleDb {
     ///
     /// * `db_path` - a path where the DB will be stored
     /// * `dump_policy` - an enum value that determines the policy of dumping DB changes into the file. Please see
-    ///    [PickleDb::load()](#method.load) to understand the different policy options
+    ///   [PickleDb::load()](#method.load) to understand the different policy options
     /// * `serialization_method` - the serialization method to use for storing the data to memory and file
     ///
     /// # Examples
@@ -64,20 +163,157 @@ impl PickleDb {
         PickleDb {
             map: HashMap::new(),
             list_map: HashMap::new(),
+            expiry_map: HashMap::new(),
+            set_map: HashMap::new(),
+            hash_map: HashMap::new(),
             serializer: Serializer::new(serialization_method),
             db_file_path: db_path_buf,
             dump_policy,
             last_dump: Instant::now(),
+            changes_since_dump: 0,
+            temp_dir: None,
+            create_dirs: false,
+            flush_on_drop: true,
+            dirty: true,
+            wal: false,
+            wal_keys: HashSet::new(),
+            wal_full_dump: true,
+            wal_size: 0,
+            db_file_size: 0,
+            db_file_crc: 0,
+            last_dump_hash: None,
+            max_bytes: None,
+            used_bytes: 0,
+            #[cfg(feature = "lock")]
+            file_lock: None,
+            #[cfg(feature = "compression")]
+            compression: false,
+            #[cfg(feature = "crypt")]
+            encryption_key: None,
+            batch_depth: 0,
+            batch_changed: false,
+            on_dump: None,
+            on_change: None,
+            value_cache: Mutex::new(HashMap::new()),
         }
     }
 
+    /// Constructs a new `PickleDb` instance that compresses the DB file.
+    ///
+    /// This method is similar to [new()](#method.new) but the data is compressed using
+    /// [gzip](https://crates.io/crates/flate2) every time it's dumped to the file, which makes the file
+    /// smaller at the cost of a slower dump. Compressed files are detected automatically when they're
+    /// loaded, so they can be loaded with [load()](#method.load) or any of the other load methods.
+    ///
+    /// # Arguments
+    ///
+    /// * `db_path` - a path where the DB will be stored
+    /// * `dump_policy` - an enum value that determines the policy of dumping DB changes into the file. Please see
+    ///   [PickleDb::load()](#method.load) to understand the different policy options
+    /// * `serialization_method` - the serialization method to use for storing the data to memory and file
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use pickledb::{PickleDb, PickleDbDumpPolicy, SerializationMethod};
+    ///
+    /// let mut db = PickleDb::new_with_compression("example.db", PickleDbDumpPolicy::AutoDump, SerializationMethod::Json);
+    /// ```
+    ///
+    #[cfg(feature = "compression")]
+    pub fn new_with_compression<P: AsRef<Path>>(
+        db_path: P,
+        dump_policy: PickleDbDumpPolicy,
+        serialization_method: SerializationMethod,
+    ) -> PickleDb {
+        let mut db = PickleDb::new(db_path, dump_policy, serialization_method);
+        db.compression = true;
+        db
+    }
+
+    /// Constructs a new `PickleDb` instance that encrypts the DB file.
+    ///
+    /// This method is similar to [new()](#method.new) but the data is encrypted using
+    /// [AES-256-GCM](https://crates.io/crates/aes-gcm) with the provided key every time it's dumped to the file,
+    /// so the data is never stored as plain text. A new random nonce is generated for every dump and it's
+    /// stored at the beginning of the file. Encrypted DBs should be loaded with
+    /// [load_with_encryption()](#method.load_with_encryption) and the same key.
+    ///
+    /// Please note that the write-ahead log (see [PickleDbBuilder::wal()](struct.PickleDbBuilder.html#method.wal))
+    /// isn't encrypted, so it's never used for encrypted DBs.
+    ///
+    /// # Arguments
+    ///
+    /// * `db_path` - a path where the DB will be stored
+    /// * `dump_policy` - an enum value that determines the policy of dumping DB changes into the file. Please see
+    ///   [PickleDb::load()](#method.load) to understand the different policy options
+    /// * `serialization_method` - the serialization method to use for storing the data to memory and file
+    /// * `encryption_key` - a 32 bytes key to encrypt the file with. It's the user's responsibility to generate
+    ///   this key and keep it safe
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use pickledb::{PickleDb, PickleDbDumpPolicy, SerializationMethod};
+    ///
+    /// let key = [7u8; 32];
+    /// let mut db = PickleDb::new_with_encryption("example.db", PickleDbDumpPolicy::AutoDump, SerializationMethod::Json, key);
+    /// ```
+    ///
+    #[cfg(feature = "crypt")]
+    pub fn new_with_encryption<P: AsRef<Path>>(
+        db_path: P,
+        dump_policy: PickleDbDumpPolicy,
+        serialization_method: SerializationMethod,
+        encryption_key: [u8; 32],
+    ) -> PickleDb {
+        let mut db = PickleDb::new(db_path, dump_policy, serialization_method);
+        db.encryption_key = Some(encryption_key);
+        db
+    }
+
+    /// Constructs a new `PickleDb` instance that uses a custom serializer for storing the data.
+    ///
+    /// This method is similar to [new()](#method.new) but instead of one of the serialization methods PickleDB
+    /// provides, the data is serialized using a user implementation of the
+    /// [PickleDbSerializer](trait.PickleDbSerializer.html) trait. The DB should be loaded with
+    /// [load_with_serializer()](#method.load_with_serializer) and the same serializer.
+    /// [serialization_method()](#method.serialization_method) of this DB returns
+    /// [SerializationMethod::Custom](enum.SerializationMethod.html#variant.Custom).
+    ///
+    /// # Arguments
+    ///
+    /// * `db_path` - a path where the DB will be stored
+    /// * `dump_policy` - an enum value that determines the policy of dumping DB changes into the file. Please see
+    ///   [PickleDb::load()](#method.load) to understand the different policy options
+    /// * `serializer` - the serializer to use for storing the data to memory and file
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use pickledb::{PickleDb, PickleDbDumpPolicy};
+    ///
+    /// // MySerializer implements PickleDbSerializer
+    /// let mut db = PickleDb::new_with_serializer("example.db", PickleDbDumpPolicy::AutoDump, Box::new(MySerializer));
+    /// ```
+    ///
+    pub fn new_with_serializer<P: AsRef<Path>>(
+        db_path: P,
+        dump_policy: PickleDbDumpPolicy,
+        serializer: Box<dyn PickleDbSerializer>,
+    ) -> PickleDb {
+        let mut db = PickleDb::new(db_path, dump_policy, SerializationMethod::Custom);
+        db.serializer = Serializer::new_custom(Arc::from(serializer));
+        db
+    }
+
     /// Constructs a new `PickleDb` instance that uses [JSON serialization](https://crates.io/crates/serde_json) for storing the data.
     ///
     /// # Arguments
     ///
     /// * `db_path` - a path where the DB will be stored
     /// * `dump_policy` - an enum value that determines the policy of dumping DB changes into the file. Please see
-    ///    [PickleDb::load()](#method.load) to understand the different policy options
+    ///   [PickleDb::load()](#method.load) to understand the different policy options
     ///
     /// # Examples
     ///
@@ -98,7 +334,7 @@ impl PickleDb {
     ///
     /// * `db_path` - a path where the DB will be stored
     /// * `dump_policy` - an enum value that determines the policy of dumping DB changes into the file. Please see
-    ///    [PickleDb::load()](#method.load) to understand the different policy options
+    ///   [PickleDb::load()](#method.load) to understand the different policy options
     ///
     /// # Examples
     ///
@@ -119,7 +355,7 @@ impl PickleDb {
     ///
     /// * `db_path` - a path where the DB will be stored
     /// * `dump_policy` - an enum value that determines the policy of dumping DB changes into the file. Please see
-    ///    [PickleDb::load()](#method.load) to understand the different policy options
+    ///   [PickleDb::load()](#method.load) to understand the different policy options
     ///
     /// # Examples
     ///
@@ -140,7 +376,7 @@ impl PickleDb {
     ///
     /// * `db_path` - a path where the DB will be stored
     /// * `dump_policy` - an enum value that determines the policy of dumping DB changes into the file. Please see
-    ///    [PickleDb::load()](#method.load) to understand the different policy options
+    ///   [PickleDb::load()](#method.load) to understand the different policy options
     ///
     /// # Examples
     ///
@@ -155,11 +391,196 @@ impl PickleDb {
         PickleDb::new(db_path, dump_policy, SerializationMethod::Cbor)
     }
 
+    /// Constructs a new `PickleDb` instance that uses [MessagePack serialization](https://crates.io/crates/rmp-serde) for storing the data.
+    ///
+    /// # Arguments
+    ///
+    /// * `db_path` - a path where the DB will be stored
+    /// * `dump_policy` - an enum value that determines the policy of dumping DB changes into the file. Please see
+    ///   [PickleDb::load()](#method.load) to understand the different policy options
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use pickledb::{PickleDb, PickleDbDumpPolicy};
+    ///
+    /// let mut db = PickleDb::new_msgpack("example.db", PickleDbDumpPolicy::AutoDump);
+    /// ```
+    ///
+    #[cfg(feature = "msgpack")]
+    pub fn new_msgpack<P: AsRef<Path>>(db_path: P, dump_policy: PickleDbDumpPolicy) -> PickleDb {
+        PickleDb::new(db_path, dump_policy, SerializationMethod::MsgPack)
+    }
+
+    /// Constructs a new `PickleDb` instance that uses [TOML serialization](https://crates.io/crates/toml) for storing the data.
+    ///
+    /// TOML files are easy to read and edit by hand, but TOML can't represent every value: for example
+    /// `None` and unit values can't be stored.
+    ///
+    /// # Arguments
+    ///
+    /// * `db_path` - a path where the DB will be stored
+    /// * `dump_policy` - an enum value that determines the policy of dumping DB changes into the file. Please see
+    ///   [PickleDb::load()](#method.load) to understand the different policy options
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use pickledb::{PickleDb, PickleDbDumpPolicy};
+    ///
+    /// let mut db = PickleDb::new_toml("example.db", PickleDbDumpPolicy::AutoDump);
+    /// ```
+    ///
+    #[cfg(feature = "toml")]
+    pub fn new_toml<P: AsRef<Path>>(db_path: P, dump_policy: PickleDbDumpPolicy) -> PickleDb {
+        PickleDb::new(db_path, dump_policy, SerializationMethod::Toml)
+    }
+
+    /// Constructs a new `PickleDb` instance that is kept only in memory.
+    ///
+    /// The DB isn't associated with any file and its dump policy is
+    /// [PickleDbDumpPolicy::NeverDump](enum.PickleDbDumpPolicy.html#variant.NeverDump), so creating it,
+    /// changing it and dropping it never touches the file system, and [dump()](#method.dump) does nothing.
+    /// This is useful for tests and for temporary caches. The data can still be written to a file
+    /// explicitly by calling [save_as()](#method.save_as).
+    ///
+    /// # Arguments
+    ///
+    /// * `serialization_method` - the serialization method to use for storing the data to memory
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pickledb::{PickleDb, SerializationMethod};
+    ///
+    /// let mut db = PickleDb::new_in_memory(SerializationMethod::Json);
+    /// db.set("key", &100).unwrap();
+    /// assert_eq!(db.get::<i32>("key").unwrap(), 100);
+    /// ```
+    ///
+    pub fn new_in_memory(serialization_method: SerializationMethod) -> PickleDb {
+        PickleDb::new(
+            PathBuf::new(),
+            PickleDbDumpPolicy::NeverDump,
+            serialization_method,
+        )
+    }
+
+    /// Constructs a new `PickleDb` instance that contains the key-value pairs of a map.
+    ///
+    /// This method is similar to [new()](#method.new) but the DB is seeded with all the entries of `map`.
+    /// All values are serialized before the DB is created and a single DB dump (which is decided according
+    /// to the dump policy) is triggered afterwards, which is much faster than calling [set()](#method.set)
+    /// for each entry. An empty map creates an empty DB.
+    ///
+    /// This method returns the new DB upon success or an `Err(`[Error](error/struct.Error.html)`)` if
+    /// serializing one of the values or dumping the DB fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `db_path` - a path where the DB will be stored
+    /// * `dump_policy` - an enum value that determines the policy of dumping DB changes into the file. Please see
+    ///   [PickleDb::load()](#method.load) to understand the different policy options
+    /// * `serialization_method` - the serialization method to use for storing the data to memory and file
+    /// * `map` - the key-value pairs to store in the DB
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use pickledb::{PickleDb, PickleDbDumpPolicy, SerializationMethod};
+    /// use std::collections::HashMap;
+    ///
+    /// let mut map = HashMap::new();
+    /// map.insert(String::from("key1"), 100);
+    /// map.insert(String::from("key2"), 200);
+    ///
+    /// let db = PickleDb::from_map("example.db", PickleDbDumpPolicy::AutoDump, SerializationMethod::Json, &map).unwrap();
+    /// ```
+    ///
+    pub fn from_map<P: AsRef<Path>, V: Serialize>(
+        db_path: P,
+        dump_policy: PickleDbDumpPolicy,
+        serialization_method: SerializationMethod,
+        map: &HashMap<String, V>,
+    ) -> Result<PickleDb> {
+        // the DB isn't dumped (also when it's dropped) until all values are serialized
+        let mut db = PickleDb::new(db_path, PickleDbDumpPolicy::NeverDump, serialization_method);
+        for (key, value) in map.iter() {
+            match db.serializer.serialize_data(value) {
+                Ok(ser_data) => db.map.insert(key.to_string(), ser_data),
+                Err(err_str) => return Err(PickleDb::serialization_error(key, err_str)),
+            };
+        }
+        db.used_bytes = db.count_used_bytes();
+
+        db.dump_policy = dump_policy;
+        db.dumpdb()?;
+        Ok(db)
+    }
+
+    /// Constructs a new `PickleDb` instance from raw key-value pairs and lists.
+    ///
+    /// This method is the counterpart of [entries()](#method.entries) and [list_entries()](#method.list_entries):
+    /// it creates a DB that holds the given values and lists as they are, without serializing them again, which
+    /// is useful for transferring a DB or replicating it. Like [new()](#method.new), nothing is written to the
+    /// file until the DB is first dumped. If the same name appears both in `values` and in `lists`, the list is kept.
+    ///
+    /// **Please note:** the values and list items are stored as is, so it's the caller's responsibility to make
+    /// sure they were serialized with `serialization_method`, for example by taking them from a DB that uses the
+    /// same serialization method. Otherwise they can't be read back.
+    ///
+    /// # Arguments
+    ///
+    /// * `db_path` - a path where the DB will be stored
+    /// * `dump_policy` - an enum value that determines the policy of dumping DB changes into the file. Please see
+    ///   [PickleDb::load()](#method.load) to understand the different policy options
+    /// * `serialization_method` - the serialization method the values and list items were serialized with
+    /// * `values` - the keys and raw values to store in the DB
+    /// * `lists` - the names and raw items of the lists to store in the DB
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use pickledb::{PickleDb, PickleDbDumpPolicy, SerializationMethod};
+    ///
+    /// # let db = PickleDb::new_bin("1.db", PickleDbDumpPolicy::AutoDump);
+    /// let copy = PickleDb::from_entries(
+    ///     "copy.db",
+    ///     PickleDbDumpPolicy::AutoDump,
+    ///     SerializationMethod::Bin,
+    ///     db.entries(),
+    ///     db.list_entries(),
+    /// );
+    /// ```
+    ///
+    pub fn from_entries<P: AsRef<Path>>(
+        db_path: P,
+        dump_policy: PickleDbDumpPolicy,
+        serialization_method: SerializationMethod,
+        values: Vec<(String, Vec<u8>)>,
+        lists: Vec<(String, Vec<Vec<u8>>)>,
+    ) -> PickleDb {
+        let mut db = PickleDb::new(db_path, dump_policy, serialization_method);
+        db.map.extend(values);
+        for (name, list) in lists {
+            db.map.remove(&name);
+            db.list_map.insert(name, list);
+        }
+        db.used_bytes = db.count_used_bytes();
+        db
+    }
+
     /// Load a DB from a file.
     ///
     /// This method tries to load a DB from a file. Upon success an instance of `PickleDb` is returned,
     /// otherwise an [Error](error/struct.Error.html) object is returned.
     ///
+    /// Temp files that were left next to the DB file by dumps that didn't complete (for example if the
+    /// process crashed in the middle of a dump) are removed when the DB is loaded. A temp file is only removed
+    /// if the process that wrote it is no longer running or if it wasn't modified for an hour, so the dump of
+    /// another process that is writing the same file isn't affected. Nothing is removed if `dump_policy` is
+    /// [PickleDbDumpPolicy::NeverDump](enum.PickleDbDumpPolicy.html#variant.NeverDump).
+    ///
     /// # Arguments
     ///
     /// * `db_path` - a path where the DB is loaded from
@@ -176,6 +597,8 @@ impl PickleDb {
     ///     dumped to the file periodically, no sooner than the Duration provided by the user. The way this mechanism works is
     ///     as follows: each time there is a DB change the last DB dump time is checked. If the time that has passed
     ///     since the last dump is higher than Duration, changes will be dumped, otherwise changes will not be dumped.
+    ///   * [PickleDbDumpPolicy::AutoDumpEvery(usize)](enum.PickleDbDumpPolicy.html#variant.AutoDumpEvery) - changes will be
+    ///     dumped to the file once every N changes, where N is the number provided by the user.
     /// * `serialization_method` - the serialization method used to store the data in the file
     ///
     /// # Examples
@@ -191,29 +614,288 @@ impl PickleDb {
         dump_policy: PickleDbDumpPolicy,
         serialization_method: SerializationMethod,
     ) -> Result<PickleDb> {
-        let content = match fs::read(db_path.as_ref()) {
+        let db = PickleDb::load_file(
+            db_path.as_ref(),
+            dump_policy,
+            Serializer::new(serialization_method),
+            None,
+        )?;
+        PickleDb::cleanup_temp_files(db_path.as_ref(), dump_policy, None);
+        Ok(db)
+    }
+
+    /// Load a DB that uses a custom serializer from a file.
+    ///
+    /// This method is similar to [load()](#method.load) but the data is deserialized using a user implementation
+    /// of the [PickleDbSerializer](trait.PickleDbSerializer.html) trait, and the DB keeps using it when it's dumped.
+    /// Please see [new_with_serializer()](#method.new_with_serializer) for more details.
+    ///
+    /// # Arguments
+    ///
+    /// * `db_path` - a path where the DB is loaded from
+    /// * `dump_policy` - an enum value that determines the policy of dumping DB changes into the file. Please see
+    ///   [PickleDb::load()](#method.load) to understand the different policy options
+    /// * `serializer` - the serializer the data in the file was stored with
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// use pickledb::{PickleDb, PickleDbDumpPolicy};
+    ///
+    /// // MySerializer implements PickleDbSerializer
+    /// let db = PickleDb::load_with_serializer("example.db", PickleDbDumpPolicy::AutoDump, Box::new(MySerializer));
+    /// ```
+    ///
+    pub fn load_with_serializer<P: AsRef<Path>>(
+        db_path: P,
+        dump_policy: PickleDbDumpPolicy,
+        serializer: Box<dyn PickleDbSerializer>,
+    ) -> Result<PickleDb> {
+        let db = PickleDb::load_file(
+            db_path.as_ref(),
+            dump_policy,
+            Serializer::new_custom(Arc::from(serializer)),
+            None,
+        )?;
+        PickleDb::cleanup_temp_files(db_path.as_ref(), dump_policy, None);
+        Ok(db)
+    }
+
+    /// Load an encrypted DB from a file.
+    ///
+    /// This method is similar to [load()](#method.load) but the DB file is decrypted using `encryption_key`,
+    /// and the DB keeps being encrypted with the same key when it's dumped. Please see
+    /// [new_with_encryption()](#method.new_with_encryption) for more details about encryption.
+    ///
+    /// If the file isn't encrypted it's loaded as is, and it will be encrypted the next time it's dumped. If
+    /// the file is encrypted with a different key, an [Error](error/struct.Error.html) of type
+    /// [ErrorType::Decryption](error/enum.ErrorType.html#variant.Decryption) is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `db_path` - a path where the DB is loaded from
+    /// * `dump_policy` - an enum value that determines the policy of dumping DB changes into the file. Please see
+    ///   [PickleDb::load()](#method.load) to understand the different policy options
+    /// * `serialization_method` - the serialization method used to store the data in the file
+    /// * `encryption_key` - the 32 bytes key the file is encrypted with
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use pickledb::{PickleDb, PickleDbDumpPolicy, SerializationMethod};
+    ///
+    /// let key = [7u8; 32];
+    /// let db = PickleDb::load_with_encryption("example.db", PickleDbDumpPolicy::AutoDump, SerializationMethod::Json, key);
+    /// ```
+    ///
+    #[cfg(feature = "crypt")]
+    pub fn load_with_encryption<P: AsRef<Path>>(
+        db_path: P,
+        dump_policy: PickleDbDumpPolicy,
+        serialization_method: SerializationMethod,
+        encryption_key: [u8; 32],
+    ) -> Result<PickleDb> {
+        let db = PickleDb::load_file(
+            db_path.as_ref(),
+            dump_policy,
+            Serializer::new(serialization_method),
+            Some(encryption_key),
+        )?;
+        PickleDb::cleanup_temp_files(db_path.as_ref(), dump_policy, None);
+        Ok(db)
+    }
+
+    pub(crate) fn load_file(
+        db_path: &Path,
+        dump_policy: PickleDbDumpPolicy,
+        serializer: Serializer,
+        encryption_key: Option<[u8; 32]>,
+    ) -> Result<PickleDb> {
+        let content = match fs::read(db_path) {
             Ok(file_content) => file_content,
             Err(err) => return Err(Error::new(ErrorCode::Io(err))),
         };
 
-        let serializer = Serializer::new(serialization_method);
+        let db_file_size = content.len() as u64;
+        let db_file_crc = crc32fast::hash(&content);
+        let last_dump_hash = PickleDb::content_hash(&content);
 
-        let maps_from_file: (_, _) = match serializer.deserialize_db(&content) {
-            Ok(maps) => maps,
-            Err(err_str) => return Err(Error::new(ErrorCode::Serialization(err_str))),
+        let mut file_content =
+            PickleDb::parse_file_content(content, &serializer, encryption_key.as_ref())?;
+
+        // apply the changes that were written to the write-ahead log after the last full dump. A log
+        // that was written on top of a different DB file is stale and is ignored, it's removed by the
+        // next dump which must write the entire DB
+        let (wal_size, wal_complete) = match fs::read(wal::wal_path(db_path)) {
+            Ok(wal_content) => (
+                wal_content.len() as u64,
+                match wal::records_for_base(&wal_content, db_file_crc, db_file_size) {
+                    Some(records) => wal::replay(
+                        records,
+                        &mut file_content.map,
+                        &mut file_content.list_map,
+                        &mut file_content.expiry_map,
+                        &mut file_content.set_map,
+                        &mut file_content.hash_map,
+                    ),
+                    None => false,
+                },
+            ),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => (0, true),
+            Err(err) => return Err(Error::new(ErrorCode::Io(err))),
         };
 
         let mut db_path_buf = PathBuf::new();
         db_path_buf.push(db_path);
 
-        Ok(PickleDb {
-            map: maps_from_file.0,
-            list_map: maps_from_file.1,
+        let mut db = PickleDb {
+            map: file_content.map,
+            list_map: file_content.list_map,
+            expiry_map: file_content.expiry_map,
+            set_map: file_content.set_map,
+            hash_map: file_content.hash_map,
             serializer,
             db_file_path: db_path_buf,
             dump_policy,
             last_dump: Instant::now(),
-        })
+            changes_since_dump: 0,
+            temp_dir: None,
+            create_dirs: false,
+            flush_on_drop: true,
+            dirty: false,
+            wal: false,
+            wal_keys: HashSet::new(),
+            wal_full_dump: !wal_complete,
+            wal_size,
+            db_file_size,
+            db_file_crc,
+            last_dump_hash: Some(last_dump_hash),
+            max_bytes: None,
+            used_bytes: 0,
+            #[cfg(feature = "lock")]
+            file_lock: None,
+            #[cfg(feature = "compression")]
+            compression: file_content.compression,
+            #[cfg(feature = "crypt")]
+            encryption_key,
+            batch_depth: 0,
+            batch_changed: false,
+            on_dump: None,
+            on_change: None,
+            value_cache: Mutex::new(HashMap::new()),
+        };
+        db.used_bytes = db.count_used_bytes();
+        Ok(db)
+    }
+
+    /// Load a DB from a reader.
+    ///
+    /// This method is similar to [load()](#method.load) but the DB content is read from any source that
+    /// implements [Read](https://doc.rust-lang.org/std/io/trait.Read.html), for example a buffer, an embedded
+    /// resource or a network stream, instead of a file. The reader is read until its end.
+    ///
+    /// Since the DB isn't associated with any file its dump policy is
+    /// [PickleDbDumpPolicy::NeverDump](enum.PickleDbDumpPolicy.html#variant.NeverDump). The data can still
+    /// be written explicitly using [dump_to_writer()](#method.dump_to_writer) or [save_as()](#method.save_as).
+    ///
+    /// This method returns the loaded DB upon success or an `Err(`[Error](error/struct.Error.html)`)` if
+    /// reading or deserializing the content fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - the source to read the DB content from
+    /// * `serialization_method` - the serialization method used to store the data
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use pickledb::{PickleDb, SerializationMethod};
+    /// use std::net::TcpStream;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080").unwrap();
+    /// let db = PickleDb::load_from_reader(stream, SerializationMethod::Json).unwrap();
+    /// ```
+    ///
+    pub fn load_from_reader<R: Read>(
+        mut reader: R,
+        serialization_method: SerializationMethod,
+    ) -> Result<PickleDb> {
+        let mut content = Vec::new();
+        if let Err(err) = reader.read_to_end(&mut content) {
+            return Err(Error::new(ErrorCode::Io(err)));
+        }
+
+        let mut db = PickleDb::new_in_memory(serialization_method);
+        let file_content = PickleDb::parse_file_content(content, &db.serializer, None)?;
+        db.map = file_content.map;
+        db.list_map = file_content.list_map;
+        db.expiry_map = file_content.expiry_map;
+        db.set_map = file_content.set_map;
+        db.hash_map = file_content.hash_map;
+        db.used_bytes = db.count_used_bytes();
+        #[cfg(feature = "compression")]
+        {
+            db.compression = file_content.compression;
+        }
+        db.dirty = false;
+        Ok(db)
+    }
+
+    #[cfg_attr(not(feature = "crypt"), allow(unused_variables))]
+    fn parse_file_content(
+        content: Vec<u8>,
+        serializer: &Serializer,
+        encryption_key: Option<&[u8; 32]>,
+    ) -> Result<FileContent> {
+        let content = if checksum::has_checksum(&content) {
+            match checksum::verify_checksum(content) {
+                Ok(verified_content) => verified_content,
+                Err(err_str) => return Err(Error::new(ErrorCode::Corrupted(err_str))),
+            }
+        } else {
+            content
+        };
+
+        #[cfg(feature = "crypt")]
+        let content = if crypt::is_encrypted(&content) {
+            let decrypted_content = match encryption_key {
+                Some(encryption_key) => crypt::decrypt(encryption_key, &content),
+                None => Err(String::from(
+                    "The DB file is encrypted but no encryption key was provided",
+                )),
+            };
+            match decrypted_content {
+                Ok(decrypted_content) => decrypted_content,
+                Err(err_str) => return Err(Error::new(ErrorCode::Decryption(err_str))),
+            }
+        } else {
+            content
+        };
+
+        #[cfg(feature = "compression")]
+        let compression = compression::is_compressed(&content);
+        #[cfg(feature = "compression")]
+        let content = if compression {
+            match compression::decompress(&content) {
+                Ok(decompressed_content) => decompressed_content,
+                Err(err_str) => return Err(Error::new(ErrorCode::Serialization(err_str))),
+            }
+        } else {
+            content
+        };
+
+        match serializer.deserialize_db(&content) {
+            Ok((map, list_map, expiry_map, set_map, hash_map)) => Ok(FileContent {
+                map,
+                list_map,
+                expiry_map,
+                set_map,
+                hash_map,
+                #[cfg(feature = "compression")]
+                compression,
+            }),
+            Err(err_str) => Err(Error::new(ErrorCode::Serialization(err_str))),
+        }
     }
 
     /// Load a DB from a file stored in a Json format
@@ -324,12 +1006,66 @@ impl PickleDb {
         PickleDb::load(db_path, dump_policy, SerializationMethod::Cbor)
     }
 
-    /// Load a DB from a file in read-only mode.
+    /// Load a DB from a file stored in MessagePack format
     ///
-    /// This method is similar to the [PickleDb::load()](#method.load) method with the only difference
-    /// that the file is loaded from DB with a dump policy of
-    /// [PickleDbDumpPolicy::NeverDump](enum.PickleDbDumpPolicy.html#variant.NeverDump), meaning
-    /// changes will not be saved to the file, even when calling [dump()](#method.dump).
+    /// This method tries to load a DB from a file serialized in MessagePack format. Upon success an instance of `PickleDb` is returned,
+    /// otherwise an [Error](error/struct.Error.html) object is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `db_path` - a path where the DB is loaded from
+    /// * `dump_policy` - an enum value that determines the policy of dumping DB changes into the file.
+    ///   See [PickleDb::load()](#method.load) for more information
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use pickledb::{PickleDb, PickleDbDumpPolicy};
+    ///
+    /// let db = PickleDb::load_msgpack("example.db", PickleDbDumpPolicy::AutoDump);
+    /// ```
+    ///
+    #[cfg(feature = "msgpack")]
+    pub fn load_msgpack<P: AsRef<Path>>(
+        db_path: P,
+        dump_policy: PickleDbDumpPolicy,
+    ) -> Result<PickleDb> {
+        PickleDb::load(db_path, dump_policy, SerializationMethod::MsgPack)
+    }
+
+    /// Load a DB from a file stored in TOML format
+    ///
+    /// This method tries to load a DB from a file serialized in TOML format. Upon success an instance of `PickleDb` is returned,
+    /// otherwise an [Error](error/struct.Error.html) object is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `db_path` - a path where the DB is loaded from
+    /// * `dump_policy` - an enum value that determines the policy of dumping DB changes into the file.
+    ///   See [PickleDb::load()](#method.load) for more information
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use pickledb::{PickleDb, PickleDbDumpPolicy};
+    ///
+    /// let db = PickleDb::load_toml("example.db", PickleDbDumpPolicy::AutoDump);
+    /// ```
+    ///
+    #[cfg(feature = "toml")]
+    pub fn load_toml<P: AsRef<Path>>(
+        db_path: P,
+        dump_policy: PickleDbDumpPolicy,
+    ) -> Result<PickleDb> {
+        PickleDb::load(db_path, dump_policy, SerializationMethod::Toml)
+    }
+
+    /// Load a DB from a file in read-only mode.
+    ///
+    /// This method is similar to the [PickleDb::load()](#method.load) method with the only difference
+    /// that the file is loaded from DB with a dump policy of
+    /// [PickleDbDumpPolicy::NeverDump](enum.PickleDbDumpPolicy.html#variant.NeverDump), meaning
+    /// changes will not be saved to the file, even when calling [dump()](#method.dump).
     /// Upon success an instance of `PickleDb` is returned, otherwise an [Error](error/struct.Error.html)
     /// object is returned.
     ///
@@ -355,12 +1091,78 @@ impl PickleDb {
         PickleDb::load(db_path, PickleDbDumpPolicy::NeverDump, serialization_method)
     }
 
+    /// Reload the data of the DB from its file.
+    ///
+    /// The file (and the write-ahead log, if there is one) is read again and deserialized with the
+    /// current serializer, and its content replaces all the keys currently held by the DB. The path, dump
+    /// policy, serializer and the rest of the DB options stay the same. This is useful when another process
+    /// may rewrite the file and the DB should pick up its changes.
+    ///
+    /// Please note that any change that wasn't dumped yet is discarded: the data after the reload is
+    /// exactly what's stored in the file.
+    ///
+    /// This method returns `Ok` if the reload is successful, or an `Err(`[Error](error/struct.Error.html)`)`
+    /// if reading or deserializing the file fails, in which case the DB is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use pickledb::{PickleDb, SerializationMethod};
+    ///
+    /// let mut db = PickleDb::load_read_only("example.db", SerializationMethod::Json).unwrap();
+    ///
+    /// // ... the file is rewritten by another process ...
+    ///
+    /// db.reload().unwrap();
+    /// ```
+    ///
+    pub fn reload(&mut self) -> Result<()> {
+        #[cfg(feature = "crypt")]
+        let encryption_key = self.encryption_key;
+        #[cfg(not(feature = "crypt"))]
+        let encryption_key = None;
+
+        let mut loaded = PickleDb::load_file(
+            &self.db_file_path,
+            PickleDbDumpPolicy::NeverDump,
+            self.serializer.clone(),
+            encryption_key,
+        )?;
+
+        self.clear_cached(None);
+        self.map = std::mem::take(&mut loaded.map);
+        self.list_map = std::mem::take(&mut loaded.list_map);
+        self.expiry_map = std::mem::take(&mut loaded.expiry_map);
+        self.set_map = std::mem::take(&mut loaded.set_map);
+        self.hash_map = std::mem::take(&mut loaded.hash_map);
+        self.used_bytes = std::mem::take(&mut loaded.used_bytes);
+        #[cfg(feature = "compression")]
+        {
+            self.compression = loaded.compression;
+        }
+        self.wal_keys.clear();
+        self.wal_full_dump = loaded.wal_full_dump;
+        self.wal_size = loaded.wal_size;
+        self.db_file_size = loaded.db_file_size;
+        self.db_file_crc = loaded.db_file_crc;
+        self.last_dump_hash = loaded.last_dump_hash;
+        self.dirty = false;
+        self.changes_since_dump = 0;
+        self.last_dump = Instant::now();
+        Ok(())
+    }
+
     /// Dump the data to the file.
     ///
     /// Calling this method is necessary only if the DB is loaded or created with a dump policy other than
     /// [PickleDbDumpPolicy::AutoDump](enum.PickleDbDumpPolicy.html#variant.AutoDump), otherwise the data
     /// is dumped to the file upon every change.
     ///
+    /// If nothing changed since the last dump (see [is_dirty()](#method.is_dirty)) the file is already
+    /// up-to-date, so nothing is written. The file is also left untouched (and its modification time
+    /// isn't changed) if the changes cancel each other out, i.e. if the serialized DB is identical to the
+    /// content that was last written to the file.
+    ///
     /// This method returns `Ok` if dump is successful, Or an `Err(`[Error](error/struct.Error.html)`)` otherwise.
     ///
     pub fn dump(&mut self) -> Result<()> {
@@ -368,622 +1170,5255 @@ impl PickleDb {
             return Ok(());
         }
 
-        match self.serializer.serialize_db(&self.map, &self.list_map) {
-            Ok(ser_db) => {
-                let temp_file_path = format!(
-                    "{}.temp.{}",
-                    self.db_file_path.to_str().unwrap(),
-                    SystemTime::now()
-                        .duration_since(UNIX_EPOCH)
-                        .unwrap()
-                        .as_secs()
-                );
+        if !self.dirty {
+            return Ok(());
+        }
 
-                match fs::write(&temp_file_path, ser_db) {
-                    Ok(_) => (),
-                    Err(err) => return Err(Error::new(ErrorCode::Io(err))),
-                }
+        if self.create_dirs {
+            self.create_parent_dirs()?;
+        }
+        self.lock_for_writing()?;
 
-                match fs::rename(temp_file_path, &self.db_file_path) {
-                    Ok(_) => (),
-                    Err(err) => return Err(Error::new(ErrorCode::Io(err))),
-                }
+        // the log is folded back into the DB file once it becomes larger than the file
+        if self.wal_enabled() && !self.wal_full_dump && self.wal_size <= self.db_file_size {
+            self.append_to_wal()?;
+        } else {
+            self.write_full()?;
+        }
+        self.dirty = false;
+        self.last_dump = Instant::now();
+        self.changes_since_dump = 0;
+        Ok(())
+    }
 
-                if let PickleDbDumpPolicy::PeriodicDump(_dur) = self.dump_policy {
-                    self.last_dump = Instant::now();
-                }
-                Ok(())
-            }
-            Err(err_str) => Err(Error::new(ErrorCode::Serialization(err_str))),
+    /// Dump the data to the file only if there are changes that weren't dumped yet.
+    ///
+    /// This method is similar to [dump()](#method.dump) but it also tells whether the file was written:
+    /// it returns `Ok(true)` if the DB was dirty (see [is_dirty()](#method.is_dirty)) and was dumped,
+    /// `Ok(false)` if nothing changed since the last dump or if the dump policy is
+    /// [PickleDbDumpPolicy::NeverDump](enum.PickleDbDumpPolicy.html#variant.NeverDump), or an
+    /// `Err(`[Error](error/struct.Error.html)`)` if the dump fails. Together with
+    /// [last_dump_elapsed()](#method.last_dump_elapsed) it allows implementing custom dump scheduling,
+    /// for example with the [PickleDbDumpPolicy::DumpUponRequest](enum.PickleDbDumpPolicy.html#variant.DumpUponRequest) policy.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::DumpUponRequest);
+    /// db.set("key", &100).unwrap();
+    ///
+    /// // flush the changes if the file is more than 10 seconds old
+    /// if db.last_dump_elapsed() > Duration::from_secs(10) {
+    ///     db.dump_if_dirty().unwrap();
+    /// }
+    /// ```
+    ///
+    pub fn dump_if_dirty(&mut self) -> Result<bool> {
+        if let PickleDbDumpPolicy::NeverDump = self.dump_policy {
+            return Ok(false);
         }
+        if !self.dirty {
+            return Ok(false);
+        }
+
+        self.dump()?;
+        Ok(true)
     }
 
-    fn dumpdb(&mut self) -> Result<()> {
-        match self.dump_policy {
-            PickleDbDumpPolicy::AutoDump => self.dump(),
-            PickleDbDumpPolicy::PeriodicDump(duration) => {
-                let now = Instant::now();
-                if now.duration_since(self.last_dump) > duration {
-                    self.last_dump = Instant::now();
-                    self.dump()?;
-                }
-                Ok(())
-            }
+    /// Get the time that has passed since the data was last dumped to the file.
+    ///
+    /// If the data wasn't dumped yet, this is the time that has passed since the DB was created or loaded.
+    ///
+    pub fn last_dump_elapsed(&self) -> Duration {
+        self.last_dump.elapsed()
+    }
 
-            _ => Ok(()),
-        }
+    /// Save the DB to a different file.
+    ///
+    /// This method serializes all the in-memory data and writes it to `path`, in the same way
+    /// [dump()](#method.dump) writes it to the DB file. This is useful for creating a backup or a
+    /// snapshot of the DB. The DB itself isn't affected: it keeps using its original file and the
+    /// dump policy timing isn't changed.
+    ///
+    /// Unlike [dump()](#method.dump), this method writes the file regardless of the dump policy,
+    /// so it also works for DBs loaded with [PickleDbDumpPolicy::NeverDump](enum.PickleDbDumpPolicy.html#variant.NeverDump),
+    /// for example read-only DBs loaded with [load_read_only()](#method.load_read_only).
+    ///
+    /// This method returns `Ok` if the file is written successfully, Or an `Err(`[Error](error/struct.Error.html)`)` otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - the path of the file to write to. If it already exists it will be overridden
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// // back up the DB before changing it
+    /// db.save_as("1.db.backup").unwrap();
+    /// ```
+    ///
+    pub fn save_as<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        self.write_to_file(path.as_ref()).map(|_| ())
     }
 
-    /// Set a key-value pair.
+    /// Write the DB to a writer.
     ///
-    /// The key has to be a string but the value can be of any type that is serializable.
-    /// That includes all primitive types, vectors, tuples, enums and every struct that
-    /// has the `#[derive(Serialize, Deserialize)` attribute.
+    /// This method serializes all the in-memory data in the same way [dump()](#method.dump) does, but writes it
+    /// to any destination that implements [Write](https://doc.rust-lang.org/std/io/trait.Write.html), for example
+    /// a buffer or a network stream, instead of the DB file. The written data can be loaded back using
+    /// [load_from_reader()](#method.load_from_reader). Like [save_as()](#method.save_as), this method writes the
+    /// data regardless of the dump policy and doesn't affect the DB itself.
     ///
-    /// This method returns `Ok` if set is successful, Or an `Err(`[Error](error/struct.Error.html)`)`
-    /// otherwise. An error is not likely to happen but may occur mostly in cases where this
-    /// action triggers a DB dump (which is decided according to the dump policy)
+    /// This method returns `Ok` if the data is written successfully, Or an `Err(`[Error](error/struct.Error.html)`)` otherwise.
     ///
     /// # Arguments
     ///
-    /// * `key` - a string key
-    /// * `value` - a value of any serializable type
+    /// * `writer` - the destination to write the DB to
     ///
     /// # Examples
     ///
     /// ```no_run
-    /// # use serde::{Serialize, Deserialize};
-    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
-    /// // set a number
-    /// db.set("key1", &100).unwrap();
+    /// # let db = pickledb::PickleDb::new_json("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// let mut buf = Vec::new();
+    /// db.dump_to_writer(&mut buf).unwrap();
+    /// ```
     ///
-    /// // set a floating point number
-    /// db.set("key2", &1.234).unwrap();
+    pub fn dump_to_writer<W: Write>(&self, mut writer: W) -> Result<()> {
+        let content = self.file_content()?;
+        match writer.write_all(&content).and_then(|_| writer.flush()) {
+            Ok(_) => Ok(()),
+            Err(err) => Err(Error::new(ErrorCode::Io(err))),
+        }
+    }
+
+    /// Shrink the memory used by the DB, write the entire DB to the file and clear the write-ahead log.
     ///
-    /// // set a String
-    /// db.set("key3", &String::from("hello world")).unwrap();
+    /// Repeatedly adding and removing keys and list items leaves excess capacity in the in-memory maps and
+    /// lists, which isn't reclaimed automatically. This method shrinks all of them to fit their current content,
+    /// which reduces the memory footprint of long-running processes. It then dumps the DB once.
     ///
-    /// // set a Vec
-    /// db.set("key4", &vec![1,2,3]).unwrap();
+    /// When the write-ahead log is enabled (see [PickleDbBuilder::wal()](struct.PickleDbBuilder.html#method.wal)),
+    /// dumping the DB appends the changes to the log instead of rewriting the whole file. This method folds
+    /// the log back into the DB file: it writes all of the data to the file in a single dump and then
+    /// removes the log. The log is also folded automatically once it becomes larger than the DB file.
     ///
-    /// // set a struct
-    /// #[derive(Serialize, Deserialize)]
-    /// struct Coor {
-    ///     x: i32,
-    ///     y: i32,
+    /// When the write-ahead log isn't enabled this method simply dumps the entire DB. Unlike [dump()](#method.dump),
+    /// the file is written even if nothing changed since the last dump. In both cases nothing is written if the dump
+    /// policy is [PickleDbDumpPolicy::NeverDump](enum.PickleDbDumpPolicy.html#variant.NeverDump), but the memory is still shrunk.
+    ///
+    /// This method returns `Ok` if the dump is successful, Or an `Err(`[Error](error/struct.Error.html)`)` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use pickledb::PickleDbBuilder;
+    ///
+    /// let mut db = PickleDbBuilder::new("example.db").wal(true).build();
+    /// for i in 0..1000 {
+    ///     // each change is appended to the log
+    ///     db.set("key", &i).unwrap();
     /// }
-    /// let mycoor = Coor { x: 1, y : 2 };
-    /// db.set("key5", &mycoor).unwrap();
+    ///
+    /// // write the DB file once and remove the log
+    /// db.compact().unwrap();
     /// ```
     ///
-    pub fn set<V>(&mut self, key: &str, value: &V) -> Result<()>
-    where
-        V: Serialize,
-    {
-        if self.list_map.contains_key(key) {
-            self.list_map.remove(key);
+    pub fn compact(&mut self) -> Result<()> {
+        self.map.shrink_to_fit();
+        self.list_map.shrink_to_fit();
+        self.expiry_map.shrink_to_fit();
+        self.set_map.shrink_to_fit();
+        self.hash_map.shrink_to_fit();
+        for list in self.list_map.values_mut() {
+            list.shrink_to_fit();
+        }
+        for set in self.set_map.values_mut() {
+            set.shrink_to_fit();
+        }
+        for hash in self.hash_map.values_mut() {
+            hash.shrink_to_fit();
         }
-        let ser_data = match self.serializer.serialize_data(value) {
-            Ok(data) => data,
-            Err(err_str) => return Err(Error::new(ErrorCode::Serialization(err_str))),
-        };
 
-        let original_value = self.map.insert(String::from(key), ser_data);
-        match self.dumpdb() {
-            Ok(_) => Ok(()),
-            Err(err) => {
-                match original_value {
-                    None => {
-                        self.map.remove(key);
-                    }
-                    Some(orig_value) => {
-                        self.map.insert(String::from(key), orig_value.to_vec());
-                    }
-                }
+        if let PickleDbDumpPolicy::NeverDump = self.dump_policy {
+            return Ok(());
+        }
 
-                Err(err)
+        self.lock_for_writing()?;
+
+        // the file is written even if its content didn't change
+        self.last_dump_hash = None;
+        self.write_full()?;
+        self.dirty = false;
+        self.last_dump = Instant::now();
+        self.changes_since_dump = 0;
+        Ok(())
+    }
+
+    // take an exclusive lock on the DB file before writing to it, if locking is enabled
+    #[cfg(feature = "lock")]
+    fn lock_for_writing(&mut self) -> Result<()> {
+        match self.file_lock.as_mut() {
+            Some(file_lock) => file_lock.acquire(&self.db_file_path, true),
+            None => Ok(()),
+        }
+    }
+
+    #[cfg(not(feature = "lock"))]
+    fn lock_for_writing(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn write_full(&mut self) -> Result<()> {
+        // there's no reason to keep expired key-value pairs once the entire DB is written
+        self.remove_expired();
+        let content = self.file_content()?;
+        let content_hash = PickleDb::content_hash(&content);
+
+        // rewriting the file with the same content would only change its modification time, so it's
+        // skipped as long as the file still looks like the one that was last written
+        let unchanged = self.last_dump_hash == Some(content_hash)
+            && fs::metadata(&self.db_file_path)
+                .map(|metadata| metadata.len() == content.len() as u64)
+                .unwrap_or(false);
+        if !unchanged {
+            self.write_content(&self.db_file_path, &content)?;
+            self.db_file_size = content.len() as u64;
+            self.db_file_crc = crc32fast::hash(&content);
+            self.last_dump_hash = Some(content_hash);
+            if let Some(on_dump) = self.on_dump.as_mut() {
+                on_dump(&self.db_file_path, content.len());
+            }
+        }
+
+        // the log is applied on top of the DB file when loading it, so it must be removed once
+        // all of its changes are in the file
+        if self.wal_size > 0 {
+            match fs::remove_file(wal::wal_path(&self.db_file_path)) {
+                Ok(_) => (),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => (),
+                Err(err) => return Err(Error::new(ErrorCode::Io(err))),
             }
+            self.wal_size = 0;
         }
+
+        self.wal_keys.clear();
+        self.wal_full_dump = false;
+        Ok(())
     }
 
-    /// Get a value of a key.
-    ///
-    /// The key is always a string but the value can be of any type. It's the user's
-    /// responsibility to know the value type and give it while calling this method.
-    /// If the key doesn't exist or if the type is wrong, `None` will be returned.
-    /// Otherwise `Some(V)` will be returned.
-    /// Since the values are stored in a serialized way the returned object is
-    /// not a reference to the value stored in a DB but actually a new instance of it
-    ///
-    /// # Arguments
+    fn append_to_wal(&mut self) -> Result<()> {
+        let mut records = Vec::new();
+        if self.wal_size == 0 {
+            wal::encode_header(&mut records, self.db_file_crc, self.db_file_size);
+        }
+        for key in self.wal_keys.iter() {
+            wal::encode_record(
+                &mut records,
+                key,
+                self.map.get(key),
+                self.list_map.get(key),
+                self.set_map.get(key),
+                self.hash_map.get(key),
+                self.expiry_map.get(key),
+            );
+        }
+
+        let wal_path = wal::wal_path(&self.db_file_path);
+        match PickleDb::append_and_sync(&wal_path, &records) {
+            Ok(_) => (),
+            Err(err) => {
+                // the log may end with a partial record now, so nothing can be appended to it until
+                // the next full dump removes it. Its size is updated so it's removed even if it was
+                // created by this write
+                self.wal_full_dump = true;
+                self.wal_size = fs::metadata(&wal_path)
+                    .map(|metadata| metadata.len())
+                    .unwrap_or(1);
+                return Err(Error::new(ErrorCode::Io(err)));
+            }
+        }
+
+        if self.wal_size == 0 {
+            PickleDb::sync_parent_dir(&wal_path);
+        }
+        self.wal_size += records.len() as u64;
+        self.wal_keys.clear();
+        if let Some(on_dump) = self.on_dump.as_mut() {
+            on_dump(&wal_path, records.len());
+        }
+        Ok(())
+    }
+
+    /// Check if the DB has changes that weren't dumped to the file yet.
     ///
-    /// * `key` - a string key
+    /// A new DB is dirty until it's dumped for the first time, and a loaded DB is dirty once it's changed.
+    /// [dump()](#method.dump) clears this flag, while [save_as()](#method.save_as) doesn't because it
+    /// writes to a different file. This is useful for deciding whether a dump is needed, for example
+    /// with the [PickleDbDumpPolicy::DumpUponRequest](enum.PickleDbDumpPolicy.html#variant.DumpUponRequest) policy.
     ///
     /// # Examples
     ///
     /// ```no_run
-    /// # use serde::{Serialize, Deserialize};
-    /// # #[derive(Serialize, Deserialize)] struct Coor { x: i32, y: i32, }
-    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
-    /// // read a num
-    /// let num = db.get::<i32>("key1").unwrap();
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::DumpUponRequest);
+    /// db.set("key", &100).unwrap();
+    /// assert!(db.is_dirty());
     ///
-    /// // read a floating point number
-    /// let float_num = db.get::<f32>("key2").unwrap();
+    /// db.dump().unwrap();
+    /// assert!(!db.is_dirty());
+    /// ```
     ///
-    /// // read a String
-    /// let my_str = db.get::<String>("key3").unwrap();
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Get the serialization method the DB uses for storing the data.
     ///
-    /// // read a Vec
-    /// let vec = db.get::<Vec<i32>>("key4").unwrap();
+    /// This is the method that was given when the DB was created or loaded. It's useful for interpreting
+    /// the bytes returned by [get_raw()](#method.get_raw) or passed to [set_raw()](#method.set_raw).
     ///
-    /// // read a struct
-    /// let coor = db.get::<Coor>("key5").unwrap();
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use pickledb::{PickleDb, PickleDbDumpPolicy, SerializationMethod};
+    ///
+    /// let db = PickleDb::load("example.db", PickleDbDumpPolicy::AutoDump, SerializationMethod::Json).unwrap();
+    /// assert_eq!(db.serialization_method(), SerializationMethod::Json);
     /// ```
     ///
-    pub fn get<V>(&self, key: &str) -> Option<V>
-    where
-        V: DeserializeOwned,
-    {
-        match self.map.get(key) {
-            Some(val) => self.serializer.deserialize_data::<V>(val),
-            None => None,
-        }
+    pub fn serialization_method(&self) -> SerializationMethod {
+        self.serializer.ser_method()
     }
 
-    /// Check if a key exists.
+    /// Get the size in bytes of the DB file on the disk.
     ///
-    /// This method returns `true` if the key exists and `false` otherwise.
+    /// The size reflects the last dump, so changes that weren't dumped yet aren't counted. When the write-ahead
+    /// log is enabled (see [PickleDbBuilder::wal()](struct.PickleDbBuilder.html#method.wal)) the size of the log
+    /// isn't included either.
     ///
-    /// # Arguments
+    /// This method returns an `Err` if the file's metadata can't be read, for example if the DB wasn't dumped yet.
     ///
-    /// * `key` - the key to check
+    /// # Examples
     ///
-    pub fn exists(&self, key: &str) -> bool {
-        self.map.get(key).is_some() || self.list_map.get(key).is_some()
+    /// ```no_run
+    /// # let db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// println!("The DB file takes {} bytes", db.file_size().unwrap());
+    /// ```
+    ///
+    pub fn file_size(&self) -> std::io::Result<u64> {
+        fs::metadata(&self.db_file_path).map(|metadata| metadata.len())
     }
 
-    /// Get a vector of all the keys in the DB.
+    /// Get an estimate of the memory used by the data of the DB, in bytes.
     ///
-    /// The keys returned in the vector are not references to the actual key string
-    /// objects but rather a clone of them.
+    /// The estimate is the total length of all the keys, the serialized values, the names of lists, sets and
+    /// hashes, the serialized list items and set members, and the fields of hashes and their serialized values.
+    /// It doesn't include the overhead of the in-memory maps, lists and sets, so the actual memory usage is
+    /// somewhat higher.
     ///
-    pub fn get_all(&self) -> Vec<String> {
-        [
-            self.map.keys().cloned().collect::<Vec<String>>(),
-            self.list_map.keys().cloned().collect::<Vec<String>>(),
-        ]
-        .concat()
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// // refuse writes once the DB grows too large
+    /// if db.memory_footprint() < 1024 * 1024 {
+    ///     db.set("key", &100).unwrap();
+    /// }
+    /// ```
+    ///
+    pub fn memory_footprint(&self) -> usize {
+        self.used_bytes - self.expired_footprint()
     }
 
-    /// Get the total number of keys in the DB.
+    // count the memory footprint of all the data in memory, including expired key-value pairs. The result
+    // is kept in `used_bytes`, which is updated on every change, so this is only needed when the data is
+    // replaced all at once
+    fn count_used_bytes(&self) -> usize {
+        let values_size: usize = self
+            .map
+            .iter()
+            .map(|(key, value)| key.len() + value.len())
+            .sum();
+        let lists_size: usize = self
+            .list_map
+            .iter()
+            .map(|(name, list)| name.len() + list.iter().map(Vec::len).sum::<usize>())
+            .sum();
+        let sets_size: usize = self
+            .set_map
+            .iter()
+            .map(|(name, set)| name.len() + set.iter().map(Vec::len).sum::<usize>())
+            .sum();
+        let hashes_size: usize = self
+            .hash_map
+            .iter()
+            .map(|(name, hash)| name.len() + PickleDb::hash_footprint(hash))
+            .sum();
+        values_size + lists_size + sets_size + hashes_size
+    }
+
+    // update `used_bytes` after the data stored under `key` changed, where `counted` is the footprint that
+    // was counted for the key before the change
+    fn recount_key(&mut self, key: &str, counted: usize) {
+        self.used_bytes = self.used_bytes - counted + self.key_footprint(key);
+    }
+
+    // the number of bytes that can be added to the DB before it exceeds its size limit, or `None` if
+    // it has no limit
+    fn size_limit_room(&self) -> Option<usize> {
+        self.max_bytes
+            .map(|max_bytes| max_bytes.saturating_sub(self.memory_footprint()))
+    }
+
+    // make sure that replacing data of size `removed` with data of size `added` doesn't make the DB
+    // exceed its size limit
+    fn check_size_limit(&self, removed: usize, added: usize) -> Result<()> {
+        match (self.max_bytes, self.size_limit_room()) {
+            (Some(max_bytes), Some(room)) if added > room + removed => {
+                Err(Error::new(ErrorCode::SizeLimitExceeded(max_bytes)))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // the part of the memory footprint that belongs to the value, list, set or hash stored under a key
+    fn key_footprint(&self, key: &str) -> usize {
+        if let Some(value) = self.map.get(key) {
+            key.len() + value.len()
+        } else if let Some(list) = self.list_map.get(key) {
+            key.len() + list.iter().map(Vec::len).sum::<usize>()
+        } else if let Some(set) = self.set_map.get(key) {
+            key.len() + set.iter().map(Vec::len).sum::<usize>()
+        } else if let Some(hash) = self.hash_map.get(key) {
+            key.len() + PickleDb::hash_footprint(hash)
+        } else {
+            0
+        }
+    }
+
+    fn hash_footprint(hash: &HashMap<String, Vec<u8>>) -> usize {
+        hash.iter()
+            .map(|(field, value)| field.len() + value.len())
+            .sum()
+    }
+
+    /// Close the DB and dump its data to the file.
     ///
-    pub fn total_keys(&self) -> usize {
-        self.map.iter().len() + self.list_map.iter().len()
+    /// This method consumes the DB and performs a final [dump()](#method.dump). Unlike dropping the DB,
+    /// where dump errors are ignored, this method returns the error so it can be handled. Once this method
+    /// is called the DB isn't dumped again when it's dropped.
+    ///
+    /// As in [dump()](#method.dump), nothing is written if the dump policy is
+    /// [PickleDbDumpPolicy::NeverDump](enum.PickleDbDumpPolicy.html#variant.NeverDump).
+    ///
+    /// This method returns `Ok` if the final dump is successful, Or an `Err(`[Error](error/struct.Error.html)`)` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::DumpUponRequest);
+    /// db.set("key", &100).unwrap();
+    ///
+    /// if let Err(e) = db.close() {
+    ///     println!("Failed to save the last changes: {}", e);
+    /// }
+    /// ```
+    ///
+    pub fn close(mut self) -> Result<()> {
+        self.flush_on_drop = false;
+        self.dump()
     }
 
-    /// Remove a key-value pair or a list from the DB.
+    /// Determine whether the DB is dumped when it's dropped.
     ///
-    /// This methods returns `Ok(true)` if the key was found in the DB or `Ok(false)` if it wasn't found.
-    /// It may also return `Err(`[Error](error/struct.Error.html)`)` if key was found but removal failed.
-    /// Removal error is not likely to happen but may occur mostly in cases where this action triggers a DB dump
-    /// (which is decided according to the dump policy)
+    /// By default a DB whose dump policy is [PickleDbDumpPolicy::AutoDump](enum.PickleDbDumpPolicy.html#variant.AutoDump),
+    /// [PickleDbDumpPolicy::PeriodicDump](enum.PickleDbDumpPolicy.html#variant.PeriodicDump)
+    /// or [PickleDbDumpPolicy::AutoDumpEvery](enum.PickleDbDumpPolicy.html#variant.AutoDumpEvery) is dumped when
+    /// it goes out of scope, and any error in this dump is ignored. Setting this to `false` disables this
+    /// final dump, for example when the data was already dumped and there is no need to write it again.
     ///
     /// # Arguments
     ///
-    /// * `key` - the key or list name to remove
+    /// * `flush_on_drop` - whether to dump the DB when it's dropped
     ///
-    pub fn rem(&mut self, key: &str) -> Result<bool> {
-        let remove_map = match self.map.remove(key) {
-            None => None,
-            Some(val) => match self.dumpdb() {
-                Ok(_) => Some(val),
-                Err(err) => {
-                    self.map.insert(String::from(key), val);
-                    return Err(err);
+    pub fn set_flush_on_drop(&mut self, flush_on_drop: bool) {
+        self.flush_on_drop = flush_on_drop;
+    }
+
+    /// Register a callback that is called every time the DB is written to the file.
+    ///
+    /// The callback is called after every successful dump that actually writes data, including dumps
+    /// triggered automatically by the dump policy and by [compact()](#method.compact). It isn't called
+    /// when nothing is written, for example when the dump policy is
+    /// [PickleDbDumpPolicy::NeverDump](enum.PickleDbDumpPolicy.html#variant.NeverDump) or when nothing
+    /// changed since the last dump. This is useful for logging or collecting metrics about writes.
+    ///
+    /// The callback receives the path of the file that was written and the number of bytes that were
+    /// written to it. When the write-ahead log is enabled (see [PickleDbBuilder::wal()](struct.PickleDbBuilder.html#method.wal))
+    /// and the changes are appended to the log, the path is the path of the log and the number of bytes
+    /// is the size of the appended records.
+    ///
+    /// Only one callback can be registered, registering a new one replaces the previous one.
+    ///
+    /// # Arguments
+    ///
+    /// * `callback` - the callback to call after every dump
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_json("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// db.on_dump(Box::new(|path, size| {
+    ///     println!("Wrote {} bytes to {}", size, path.display());
+    /// }));
+    ///
+    /// db.set("key", &100).unwrap();
+    /// ```
+    ///
+    pub fn on_dump(&mut self, callback: DumpCallback) {
+        self.on_dump = Some(callback);
+    }
+
+    /// Register a callback that is called every time a key is changed.
+    ///
+    /// The callback is called synchronously with a [ChangeEvent](enum.ChangeEvent.html) that describes
+    /// the change, after the change is made and dumped successfully (according to the dump policy). If
+    /// the change fails, the callback isn't called. The methods that trigger the callback are the methods
+    /// that change a single key, such as [set()](#method.set), [rem()](#method.rem), [lcreate()](#method.lcreate),
+    /// [ladd()](#method.ladd), [lextend()](#method.lextend), [lpop()](#method.lpop), [lrem_value()](#method.lrem_value),
+    /// [lrem_list()](#method.lrem_list), [screate()](#method.screate), [sadd()](#method.sadd), [srem()](#method.srem),
+    /// [hset()](#method.hset) and [hdel()](#method.hdel).
+    /// Methods that change many keys at once, such as
+    /// [clear()](#method.clear), [rename()](#method.rename) or [merge()](#method.merge), don't trigger it.
+    ///
+    /// Only one callback can be registered, registering a new one replaces the previous one.
+    ///
+    /// # Arguments
+    ///
+    /// * `callback` - the callback to call after every change
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use pickledb::ChangeEvent;
+    ///
+    /// # let mut db = pickledb::PickleDb::new_json("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// db.on_change(|event| match event {
+    ///     ChangeEvent::Set(key) => println!("{} was set", key),
+    ///     ChangeEvent::Removed(key) => println!("{} was removed", key),
+    ///     ChangeEvent::ListModified(name) => println!("list {} was modified", name),
+    ///     ChangeEvent::SetModified(name) => println!("set {} was modified", name),
+    ///     ChangeEvent::HashModified(name) => println!("hash {} was modified", name),
+    /// });
+    ///
+    /// db.set("key", &100).unwrap();
+    /// ```
+    ///
+    pub fn on_change<F>(&mut self, callback: F)
+    where
+        F: FnMut(ChangeEvent) + Send + 'static,
+    {
+        self.on_change = Some(Box::new(callback));
+    }
+
+    fn write_to_file(&self, path: &Path) -> Result<usize> {
+        let content = self.file_content()?;
+        self.write_content(path, &content)?;
+        Ok(content.len())
+    }
+
+    fn write_content(&self, path: &Path, content: &[u8]) -> Result<()> {
+        let temp_file_path = self.temp_file_path(path);
+
+        // make sure the data is on the disk before renaming, otherwise a crash right after
+        // the rename may leave an empty or truncated DB file
+        match PickleDb::write_and_sync(&temp_file_path, content) {
+            Ok(_) => (),
+            Err(err) => {
+                let _ = fs::remove_file(&temp_file_path);
+                return Err(Error::new(ErrorCode::Io(err)));
+            }
+        }
+
+        match fs::rename(&temp_file_path, path) {
+            Ok(_) => (),
+            Err(err) => {
+                let _ = fs::remove_file(&temp_file_path);
+                return Err(Error::new(ErrorCode::Io(err)));
+            }
+        }
+
+        PickleDb::sync_parent_dir(path);
+
+        Ok(())
+    }
+
+    fn content_hash(content: &[u8]) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        hasher.write(content);
+        hasher.finish()
+    }
+
+    // the content of the DB file: the serialized data, compressed and encrypted if needed, with a
+    // checksum header. Plain text files are written without the header, so they stay valid files of
+    // their format and can be edited by hand
+    fn file_content(&self) -> Result<Vec<u8>> {
+        #[cfg_attr(
+            not(any(feature = "compression", feature = "crypt")),
+            allow(unused_mut)
+        )]
+        let mut is_text = self.serializer.is_text();
+        let ser_db = match self.serializer.serialize_db(
+            &self.map,
+            &self.list_map,
+            &self.expiry_map,
+            &self.set_map,
+            &self.hash_map,
+        ) {
+            Ok(ser_db) => ser_db,
+            Err(err_str) => return Err(Error::new(ErrorCode::Serialization(err_str))),
+        };
+
+        #[cfg(feature = "compression")]
+        let ser_db = if self.compression {
+            is_text = false;
+            match compression::compress(&ser_db) {
+                Ok(compressed_db) => compressed_db,
+                Err(err_str) => return Err(Error::new(ErrorCode::Serialization(err_str))),
+            }
+        } else {
+            ser_db
+        };
+
+        #[cfg(feature = "crypt")]
+        let ser_db = match &self.encryption_key {
+            Some(encryption_key) => {
+                is_text = false;
+                match crypt::encrypt(encryption_key, &ser_db) {
+                    Ok(encrypted_db) => encrypted_db,
+                    Err(err_str) => return Err(Error::new(ErrorCode::Serialization(err_str))),
                 }
-            },
+            }
+            None => ser_db,
         };
 
-        let remove_list = match self.list_map.remove(key) {
-            None => None,
-            Some(list) => match self.dumpdb() {
-                Ok(_) => Some(list),
-                Err(err) => {
-                    self.list_map.insert(String::from(key), list);
-                    return Err(err);
+        if is_text {
+            Ok(ser_db)
+        } else {
+            Ok(checksum::add_checksum(ser_db))
+        }
+    }
+
+    // remove temp files that were left next to the DB file by dumps that didn't complete,
+    // for example if the process crashed in the middle of a dump. Only stale temp files are removed,
+    // a temp file of a dump that is still in progress is left alone. A read-only DB doesn't own the
+    // file, so it never removes anything: the temp files may belong to a process that is writing it
+    pub(crate) fn cleanup_temp_files(
+        path: &Path,
+        dump_policy: PickleDbDumpPolicy,
+        temp_dir: Option<&Path>,
+    ) {
+        if matches!(dump_policy, PickleDbDumpPolicy::NeverDump) {
+            return;
+        }
+
+        let temp_file_prefix = match path.file_name().and_then(|name| name.to_str()) {
+            Some(file_name) => format!("{}.temp.", file_name),
+            None => return,
+        };
+        let parent_dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+
+        for dir in std::iter::once(parent_dir).chain(temp_dir) {
+            if let Ok(entries) = fs::read_dir(dir) {
+                for entry in entries.flatten() {
+                    let is_temp_file = entry
+                        .file_name()
+                        .to_str()
+                        .and_then(|name| name.strip_prefix(&temp_file_prefix))
+                        .is_some_and(|suffix| PickleDb::is_stale_temp_file(&entry.path(), suffix));
+                    if is_temp_file {
+                        let _ = fs::remove_file(entry.path());
+                    }
+                }
+            }
+        }
+    }
+
+    // a temp file is stale if the process that wrote it is no longer running, or if it wasn't
+    // modified for a long time. `suffix` is the part of the name after ".temp.", which starts with
+    // the ID of the process that wrote it
+    fn is_stale_temp_file(temp_file_path: &Path, suffix: &str) -> bool {
+        let writer_pid = suffix
+            .split('.')
+            .next()
+            .and_then(|pid| pid.parse::<u32>().ok());
+        if cfg!(target_os = "linux") {
+            if let Some(pid) = writer_pid {
+                if pid != std::process::id() && !Path::new("/proc").join(pid.to_string()).exists() {
+                    return true;
                 }
+            }
+        }
+
+        fs::metadata(temp_file_path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age >= STALE_TEMP_FILE_AGE)
+    }
+
+    // a serialization error of a value that is stored in a key or list
+    fn serialization_error(key: &str, err_str: String) -> Error {
+        Error::new(ErrorCode::Serialization(format!(
+            "Cannot serialize the value of '{}': {}",
+            key, err_str
+        )))
+        .with_key(key)
+    }
+
+    // a deserialization error of a value that is stored in a key or list
+    fn deserialization_error(key: &str, err_str: String) -> Error {
+        Error::new(ErrorCode::Serialization(format!(
+            "Cannot deserialize the value of '{}': {}",
+            key, err_str
+        )))
+        .with_key(key)
+    }
+
+    fn create_parent_dirs(&self) -> Result<()> {
+        match self.db_file_path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => match fs::create_dir_all(dir) {
+                Ok(_) => Ok(()),
+                Err(err) => Err(Error::new(ErrorCode::Io(err))),
             },
+            _ => Ok(()),
+        }
+    }
+
+    fn temp_file_path(&self, path: &Path) -> PathBuf {
+        // the process ID, the time in nanoseconds and a counter make the name unique, so two
+        // dumps never write to the same temp file
+        let mut temp_file_name = path.file_name().unwrap_or_default().to_os_string();
+        temp_file_name.push(format!(
+            ".temp.{}.{}.{}",
+            std::process::id(),
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_nanos(),
+            TEMP_FILE_COUNTER.fetch_add(1, atomic::Ordering::Relaxed)
+        ));
+
+        match &self.temp_dir {
+            Some(temp_dir) => temp_dir.join(temp_file_name),
+            None => path.with_file_name(temp_file_name),
+        }
+    }
+
+    fn append_and_sync(file_path: &Path, data: &[u8]) -> std::io::Result<()> {
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(file_path)?;
+        file.write_all(data)?;
+        file.sync_data()
+    }
+
+    fn write_and_sync(file_path: &Path, data: &[u8]) -> std::io::Result<()> {
+        let mut file = fs::File::create(file_path)?;
+        file.write_all(data)?;
+        file.sync_all()
+    }
+
+    // make the rename of the DB file durable. The file is already in place at this point,
+    // so failures are ignored rather than reporting a dump that actually succeeded as failed
+    #[cfg(unix)]
+    fn sync_parent_dir(path: &Path) {
+        let parent_dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
         };
+        if let Ok(dir) = fs::File::open(parent_dir) {
+            let _ = dir.sync_all();
+        }
+    }
+
+    // directories can't be opened as files on non-Unix systems, so there's nothing to do
+    #[cfg(not(unix))]
+    fn sync_parent_dir(_path: &Path) {}
 
-        Ok(remove_map.is_some() || remove_list.is_some())
+    pub(crate) fn dumpdb(&mut self) -> Result<()> {
+        // the changed keys aren't known, so the next dump must write the entire DB
+        self.wal_full_dump = true;
+        self.clear_cached(None);
+        self.dump_changes()
     }
 
-    /// Create a new list.
+    // the log isn't encrypted, so it's never used for encrypted DBs
+    fn wal_enabled(&self) -> bool {
+        #[cfg(feature = "crypt")]
+        if self.encryption_key.is_some() {
+            return false;
+        }
+        self.wal
+    }
+
+    pub(crate) fn dumpdb_key(&mut self, key: &str) -> Result<()> {
+        self.clear_cached(Some(key));
+        if self.wal_enabled() {
+            self.wal_keys.insert(key.to_string());
+        }
+        self.dump_changes()?;
+        self.notify_change(key);
+        Ok(())
+    }
+
+    // drop the cached values of a key that changed, or of all keys if it's unknown which keys changed.
+    // This is done before dumping, so values that are restored if the dump fails aren't cached either
+    fn clear_cached(&mut self, key: Option<&str>) {
+        let value_cache = match self.value_cache.get_mut() {
+            Ok(value_cache) => value_cache,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        match key {
+            Some(key) => {
+                value_cache.remove(key);
+            }
+            None => value_cache.clear(),
+        }
+    }
+
+    fn notify_change(&mut self, key: &str) {
+        if self.on_change.is_none() {
+            return;
+        }
+
+        let event = if self.map.contains_key(key) {
+            ChangeEvent::Set(key.to_string())
+        } else if self.list_map.contains_key(key) {
+            ChangeEvent::ListModified(key.to_string())
+        } else if self.set_map.contains_key(key) {
+            ChangeEvent::SetModified(key.to_string())
+        } else if self.hash_map.contains_key(key) {
+            ChangeEvent::HashModified(key.to_string())
+        } else {
+            ChangeEvent::Removed(key.to_string())
+        };
+
+        if let Some(on_change) = self.on_change.as_mut() {
+            on_change(event);
+        }
+    }
+
+    pub(crate) fn dump_changes(&mut self) -> Result<()> {
+        self.dirty = true;
+
+        if self.batch_depth > 0 {
+            self.batch_changed = true;
+            return Ok(());
+        }
+
+        match self.dump_policy {
+            PickleDbDumpPolicy::AutoDump => self.dump(),
+            PickleDbDumpPolicy::PeriodicDump(duration) => {
+                let now = Instant::now();
+                if now.duration_since(self.last_dump) > duration {
+                    self.last_dump = Instant::now();
+                    self.dump()?;
+                }
+                Ok(())
+            }
+            PickleDbDumpPolicy::AutoDumpEvery(count) => {
+                self.changes_since_dump += 1;
+                if self.changes_since_dump >= count {
+                    self.dump()?;
+                }
+                Ok(())
+            }
+
+            _ => Ok(()),
+        }
+    }
+
+    /// Start a batch of changes that will be dumped to the file only once.
     ///
-    /// This method just creates a new list, it doesn't add any elements to it.
-    /// If another list or value is already set under this key, they will be overridden,
-    /// meaning the new list will override the old list or value.
+    /// This method returns a [PickleDbBatch](struct.PickleDbBatch.html) object which provides access
+    /// to all of the DB methods (it dereferences to `PickleDb`). While this object is alive, changes
+    /// don't trigger a DB dump. Instead, a single DB dump (which is decided according to the dump
+    /// policy) is triggered when calling [PickleDbBatch::commit()](struct.PickleDbBatch.html#method.commit),
+    /// or when the batch object is dropped, and only if any change was made during the batch.
     ///
-    /// Upon success, the method returns an object of type
-    /// [PickleDbListExtender](struct.PickleDbListExtender.html) that enables to add
-    /// items to the newly created list. Alternatively you can use [ladd()](#method.ladd)
-    /// or [lextend()](#method.lextend) to add items to the list.
+    /// This is much faster than dumping the DB after every change, especially when using the
+    /// [PickleDbDumpPolicy::AutoDump](enum.PickleDbDumpPolicy.html#variant.AutoDump) policy.
+    /// Please note that since changes aren't dumped while the batch is alive, they aren't rolled
+    /// back if the final dump fails.
     ///
-    /// In case of a failure an
-    /// `Err(`[Error](error/struct.Error.html)`)` is returned. Failures
-    /// are not likely to happen but may occur mostly in cases where this action triggers a DB dump
-    /// (which is decided according to the dump policy)
+    /// Batches can be nested, for example by calling this method on a batch object. The changes of all the
+    /// nested batches are dumped only once the outermost batch is committed or dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// let mut batch = db.batch();
+    ///
+    /// // none of these changes is dumped to the file
+    /// batch.set("key1", &100).unwrap();
+    /// batch.rem("key2").unwrap();
+    /// batch.lcreate("list1").unwrap().lextend(&vec![1, 2, 3]);
+    ///
+    /// // dump all changes at once
+    /// batch.commit().unwrap();
+    /// ```
+    ///
+    pub fn batch(&mut self) -> PickleDbBatch<'_> {
+        if self.batch_depth == 0 {
+            self.batch_changed = false;
+        }
+        self.batch_depth += 1;
+        PickleDbBatch {
+            db: self,
+            finished: false,
+        }
+    }
+
+    /// Set a key-value pair.
+    ///
+    /// The key has to be a string but the value can be of any type that is serializable.
+    /// That includes all primitive types, vectors, tuples, enums and every struct that
+    /// has the `#[derive(Serialize, Deserialize)` attribute.
+    ///
+    /// This method returns `Ok` if set is successful, Or an `Err(`[Error](error/struct.Error.html)`)`
+    /// otherwise. An error is not likely to happen but may occur mostly in cases where this
+    /// action triggers a DB dump (which is decided according to the dump policy)
     ///
     /// # Arguments
     ///
-    /// * `name` - the key of the list that will be created
+    /// * `key` - a string key
+    /// * `value` - a value of any serializable type
     ///
-    pub fn lcreate(&mut self, name: &str) -> Result<PickleDbListExtender> {
-        let new_list: Vec<Vec<u8>> = Vec::new();
-        if self.map.contains_key(name) {
-            self.map.remove(name);
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use serde::{Serialize, Deserialize};
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// // set a number
+    /// db.set("key1", &100).unwrap();
+    ///
+    /// // set a floating point number
+    /// db.set("key2", &1.234).unwrap();
+    ///
+    /// // set a String
+    /// db.set("key3", &String::from("hello world")).unwrap();
+    ///
+    /// // set a Vec
+    /// db.set("key4", &vec![1,2,3]).unwrap();
+    ///
+    /// // set a struct
+    /// #[derive(Serialize, Deserialize)]
+    /// struct Coor {
+    ///     x: i32,
+    ///     y: i32,
+    /// }
+    /// let mycoor = Coor { x: 1, y : 2 };
+    /// db.set("key5", &mycoor).unwrap();
+    /// ```
+    ///
+    pub fn set<V>(&mut self, key: &str, value: &V) -> Result<()>
+    where
+        V: Serialize,
+    {
+        let ser_data = match self.serializer.serialize_data(value) {
+            Ok(data) => data,
+            Err(err_str) => return Err(PickleDb::serialization_error(key, err_str)),
+        };
+
+        self.set_raw(key, ser_data)
+    }
+
+    /// Set a key-value pair where the value is already serialized.
+    ///
+    /// This method is similar to [set()](#method.set) but instead of serializing the value it stores
+    /// the given bytes as is. This is useful for values that were already serialized elsewhere,
+    /// for example values read using [get_raw()](#method.get_raw) from another DB.
+    ///
+    /// **Please note:** the bytes must be a valid serialization of a value according to the
+    /// [SerializationMethod](enum.SerializationMethod.html) this DB uses, because they are stored
+    /// and dumped to the file exactly as they are. If they aren't, later calls to [get()](#method.get)
+    /// on this key will silently return `None`, and dumping the DB may fail or write a file that
+    /// can't be loaded.
+    ///
+    /// This method returns `Ok` if set is successful or an `Err(`[Error](error/struct.Error.html)`)`
+    /// if the DB dump (which is decided according to the dump policy) fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - a string key
+    /// * `bytes` - the serialized value
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_json("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// // this DB uses JSON serialization, so this is the same as db.set("key1", &100)
+    /// db.set_raw("key1", b"100".to_vec()).unwrap();
+    /// ```
+    ///
+    pub fn set_raw(&mut self, key: &str, bytes: Vec<u8>) -> Result<()> {
+        let counted = self.key_footprint(key);
+        self.check_size_limit(counted, key.len() + bytes.len())?;
+
+        let original_list = self.list_map.remove(key);
+        let original_set = self.set_map.remove(key);
+        let original_hash = self.hash_map.remove(key);
+        let original_value = self.map.insert(String::from(key), bytes);
+        let original_expiry = self.expiry_map.remove(key);
+        self.recount_key(key, counted);
+        match self.dumpdb_key(key) {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                let counted = self.key_footprint(key);
+                match original_value {
+                    None => {
+                        self.map.remove(key);
+                    }
+                    Some(orig_value) => {
+                        self.map.insert(String::from(key), orig_value.to_vec());
+                    }
+                }
+                if let Some(orig_expiry) = original_expiry {
+                    self.expiry_map.insert(String::from(key), orig_expiry);
+                }
+                if let Some(orig_list) = original_list {
+                    self.list_map.insert(String::from(key), orig_list);
+                }
+                if let Some(orig_set) = original_set {
+                    self.set_map.insert(String::from(key), orig_set);
+                }
+                if let Some(orig_hash) = original_hash {
+                    self.hash_map.insert(String::from(key), orig_hash);
+                }
+                self.recount_key(key, counted);
+
+                Err(err)
+            }
+        }
+    }
+
+    /// Set a key-value pair that expires after a given time.
+    ///
+    /// This method is similar to [set()](#method.set) but the key-value pair is stored together with
+    /// an expiry time, which is the current time plus `ttl`. Once this time has passed the key is treated
+    /// as if it doesn't exist: [get()](#method.get) returns `None` and [exists()](#method.exists) returns `false`.
+    /// Expiry times are stored in the DB file, so they're kept when the DB is loaded again.
+    ///
+    /// Expired keys are skipped by all methods that go over or count keys, such as [get_all()](#method.get_all),
+    /// [iter()](#method.iter), [total_keys()](#method.total_keys) or [memory_footprint()](#method.memory_footprint).
+    /// They're removed from memory when they're overridden or removed, when the entire DB is dumped, or when
+    /// calling [purge_expired()](#method.purge_expired).
+    ///
+    /// Setting the same key again with [set()](#method.set) removes its TTL.
+    ///
+    /// This method returns `Ok` if set is successful or an `Err(`[Error](error/struct.Error.html)`)`
+    /// if serialization or the DB dump (which is decided according to the dump policy) fails.
+    /// In case of a failure the DB is left unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - a string key
+    /// * `value` - a value of any serializable type
+    /// * `ttl` - the time after which the key expires
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// // this key will expire in 10 minutes
+    /// db.set_with_ttl("key1", &100, Duration::from_secs(600)).unwrap();
+    /// ```
+    ///
+    pub fn set_with_ttl<V>(&mut self, key: &str, value: &V, ttl: Duration) -> Result<()>
+    where
+        V: Serialize,
+    {
+        let ser_data = match self.serializer.serialize_data(value) {
+            Ok(data) => data,
+            Err(err_str) => return Err(PickleDb::serialization_error(key, err_str)),
+        };
+        let counted = self.key_footprint(key);
+        self.check_size_limit(counted, key.len() + ser_data.len())?;
+
+        let original_list = self.list_map.remove(key);
+        let original_set = self.set_map.remove(key);
+        let original_hash = self.hash_map.remove(key);
+        let original_value = self.map.insert(String::from(key), ser_data);
+        // a TTL that's too long to be represented means the key never expires
+        let original_expiry = match SystemTime::now().checked_add(ttl) {
+            Some(expiry) => self.expiry_map.insert(String::from(key), expiry),
+            None => self.expiry_map.remove(key),
+        };
+        self.recount_key(key, counted);
+
+        match self.dumpdb_key(key) {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                let counted = self.key_footprint(key);
+                match original_value {
+                    None => {
+                        self.map.remove(key);
+                    }
+                    Some(orig_value) => {
+                        self.map.insert(String::from(key), orig_value);
+                    }
+                }
+                match original_expiry {
+                    None => {
+                        self.expiry_map.remove(key);
+                    }
+                    Some(orig_expiry) => {
+                        self.expiry_map.insert(String::from(key), orig_expiry);
+                    }
+                }
+                if let Some(orig_list) = original_list {
+                    self.list_map.insert(String::from(key), orig_list);
+                }
+                if let Some(orig_set) = original_set {
+                    self.set_map.insert(String::from(key), orig_set);
+                }
+                if let Some(orig_hash) = original_hash {
+                    self.hash_map.insert(String::from(key), orig_hash);
+                }
+                self.recount_key(key, counted);
+
+                Err(err)
+            }
+        }
+    }
+
+    /// Set multiple key-value pairs at once.
+    ///
+    /// This method is similar to calling [set()](#method.set) for each pair, but it triggers only a
+    /// single DB dump (which is decided according to the dump policy) after all pairs are set, which
+    /// is much faster than setting them one by one. Since the values are given in a slice, all of them
+    /// are of the same type. Like in [set()](#method.set), lists with the same names are overridden.
+    ///
+    /// This method returns `Ok` if all pairs are set successfully, or an `Err(`[Error](error/struct.Error.html)`)`
+    /// if serializing one of the values or dumping the DB fails. In case of a failure none of the pairs is set
+    /// and the DB is left unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `entries` - a slice of key-value pairs to set
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// // set 3 keys with a single dump
+    /// db.set_many(&[("key1", &100), ("key2", &200), ("key3", &300)]).unwrap();
+    /// ```
+    ///
+    pub fn set_many<V>(&mut self, entries: &[(&str, &V)]) -> Result<()>
+    where
+        V: Serialize,
+    {
+        let mut ser_entries = Vec::with_capacity(entries.len());
+        for (key, value) in entries {
+            match self.serializer.serialize_data(value) {
+                Ok(data) => ser_entries.push((*key, data)),
+                Err(err_str) => return Err(PickleDb::serialization_error(key, err_str)),
+            }
+        }
+
+        if self.max_bytes.is_some() {
+            // a key that appears more than once is set to its last value
+            let mut new_sizes = HashMap::new();
+            for (key, ser_data) in ser_entries.iter() {
+                new_sizes.insert(*key, key.len() + ser_data.len());
+            }
+            let removed = new_sizes.keys().map(|key| self.key_footprint(key)).sum();
+            self.check_size_limit(removed, new_sizes.values().sum())?;
+        }
+
+        let mut originals = Vec::with_capacity(ser_entries.len());
+        for (key, ser_data) in ser_entries {
+            let counted = self.key_footprint(key);
+            let orig_list = self.list_map.remove(key);
+            let orig_set = self.set_map.remove(key);
+            let orig_hash = self.hash_map.remove(key);
+            let orig_value = self.map.insert(String::from(key), ser_data);
+            let orig_expiry = self.expiry_map.remove(key);
+            self.recount_key(key, counted);
+            originals.push((key, orig_value, orig_list, orig_set, orig_hash, orig_expiry));
+        }
+
+        match self.dumpdb() {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                // restore in reverse order so keys that were set more than once get their first original value
+                for (key, orig_value, orig_list, orig_set, orig_hash, orig_expiry) in
+                    originals.into_iter().rev()
+                {
+                    let counted = self.key_footprint(key);
+                    match orig_value {
+                        None => {
+                            self.map.remove(key);
+                        }
+                        Some(orig_value) => {
+                            self.map.insert(String::from(key), orig_value);
+                        }
+                    }
+                    if let Some(orig_list) = orig_list {
+                        self.list_map.insert(String::from(key), orig_list);
+                    }
+                    if let Some(orig_set) = orig_set {
+                        self.set_map.insert(String::from(key), orig_set);
+                    }
+                    if let Some(orig_hash) = orig_hash {
+                        self.hash_map.insert(String::from(key), orig_hash);
+                    }
+                    if let Some(orig_expiry) = orig_expiry {
+                        self.expiry_map.insert(String::from(key), orig_expiry);
+                    }
+                    self.recount_key(key, counted);
+                }
+                Err(err)
+            }
+        }
+    }
+
+    /// Set a key-value pair only if the key doesn't already exist.
+    ///
+    /// This method is similar to [set()](#method.set) but it doesn't override existing data:
+    /// if a value or a list is already stored under this key nothing is changed and no dump is
+    /// triggered. This is consistent with [exists()](#method.exists), which also checks for both
+    /// values and lists.
+    ///
+    /// This method returns `Ok(true)` if the value was inserted, `Ok(false)` if the key already
+    /// exists, or an `Err(`[Error](error/struct.Error.html)`)` if serialization or the DB dump
+    /// (which is decided according to the dump policy) fails. In case of a failure the DB is left unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - a string key
+    /// * `value` - a value of any serializable type
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// // the key doesn't exist, so the value is set
+    /// assert!(db.set_if_absent("key1", &100).unwrap());
+    ///
+    /// // the key already exists, so the value isn't changed
+    /// assert!(!db.set_if_absent("key1", &200).unwrap());
+    /// ```
+    ///
+    pub fn set_if_absent<V>(&mut self, key: &str, value: &V) -> Result<bool>
+    where
+        V: Serialize,
+    {
+        if self.exists(key) {
+            return Ok(false);
+        }
+
+        let ser_data = match self.serializer.serialize_data(value) {
+            Ok(data) => data,
+            Err(err_str) => return Err(PickleDb::serialization_error(key, err_str)),
+        };
+
+        // the key may still hold an expired value
+        let counted = self.key_footprint(key);
+        self.check_size_limit(counted, key.len() + ser_data.len())?;
+        let orig_value = self.map.insert(String::from(key), ser_data);
+        let orig_expiry = self.expiry_map.remove(key);
+        self.recount_key(key, counted);
+        match self.dumpdb_key(key) {
+            Ok(_) => Ok(true),
+            Err(err) => {
+                let counted = self.key_footprint(key);
+                self.map.remove(key);
+                if let Some(orig_value) = orig_value {
+                    self.map.insert(String::from(key), orig_value);
+                }
+                if let Some(orig_expiry) = orig_expiry {
+                    self.expiry_map.insert(String::from(key), orig_expiry);
+                }
+                self.recount_key(key, counted);
+                Err(err)
+            }
+        }
+    }
+
+    /// Get a value of a key.
+    ///
+    /// The key is always a string but the value can be of any type. It's the user's
+    /// responsibility to know the value type and give it while calling this method.
+    /// If the key doesn't exist, if it has expired (see [set_with_ttl()](#method.set_with_ttl))
+    /// or if the type is wrong, `None` will be returned. Otherwise `Some(V)` will be returned.
+    /// Since the values are stored in a serialized way the returned object is
+    /// not a reference to the value stored in a DB but actually a new instance of it
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - a string key
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use serde::{Serialize, Deserialize};
+    /// # #[derive(Serialize, Deserialize)] struct Coor { x: i32, y: i32, }
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// // read a num
+    /// let num = db.get::<i32>("key1").unwrap();
+    ///
+    /// // read a floating point number
+    /// let float_num = db.get::<f32>("key2").unwrap();
+    ///
+    /// // read a String
+    /// let my_str = db.get::<String>("key3").unwrap();
+    ///
+    /// // read a Vec
+    /// let vec = db.get::<Vec<i32>>("key4").unwrap();
+    ///
+    /// // read a struct
+    /// let coor = db.get::<Coor>("key5").unwrap();
+    /// ```
+    ///
+    pub fn get<V>(&self, key: &str) -> Option<V>
+    where
+        V: DeserializeOwned,
+    {
+        if self.is_expired(key) {
+            return None;
+        }
+
+        match self.map.get(key) {
+            Some(val) => self.serializer.deserialize_data::<V>(val),
+            None => None,
+        }
+    }
+
+    /// Get a value of a key, deserializing it only the first time it's read.
+    ///
+    /// This method is similar to [get()](#method.get) but the deserialized value is kept in a cache, so
+    /// reading the same key with the same type again only clones the cached value instead of
+    /// deserializing it. This is useful for keys that are read much more often than they change.
+    ///
+    /// A key is removed from the cache whenever it changes, for example when it's set, removed or renamed,
+    /// so this method always returns the same value as [get()](#method.get) would. Please note that
+    /// cached values are kept in memory until their key changes, in addition to the serialized values
+    /// that the DB always keeps. Values that are read using [get()](#method.get) aren't cached.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - a string key
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// db.set("config", &vec![1, 2, 3]).unwrap();
+    ///
+    /// // the value is deserialized only once
+    /// for _ in 0..1000 {
+    ///     assert_eq!(db.get_cached::<Vec<i32>>("config").unwrap().len(), 3);
+    /// }
+    /// ```
+    ///
+    pub fn get_cached<V>(&self, key: &str) -> Option<V>
+    where
+        V: DeserializeOwned + Clone + Send + 'static,
+    {
+        if self.is_expired(key) {
+            return None;
+        }
+
+        let mut value_cache = match self.value_cache.lock() {
+            Ok(value_cache) => value_cache,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let cached_value = value_cache
+            .get(key)
+            .and_then(|values| values.get(&TypeId::of::<V>()))
+            .and_then(|value| value.downcast_ref::<V>());
+        if let Some(value) = cached_value {
+            return Some(value.clone());
+        }
+
+        let value = self.get::<V>(key)?;
+        value_cache
+            .entry(key.to_string())
+            .or_default()
+            .insert(TypeId::of::<V>(), Box::new(value.clone()));
+        Some(value)
+    }
+
+    /// Get a value of a key, or the default value of its type.
+    ///
+    /// This method is similar to [get()](#method.get) but instead of returning `None` if the key doesn't
+    /// exist, has expired or holds a value of a different type, it returns `V::default()`.
+    /// The DB isn't changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - a string key
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::collections::HashMap;
+    /// # let db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// // an empty map is returned if the key doesn't exist
+    /// let settings: HashMap<String, Vec<String>> = db.get_or_default("settings");
+    /// ```
+    ///
+    pub fn get_or_default<V>(&self, key: &str) -> V
+    where
+        V: DeserializeOwned + Default,
+    {
+        self.get(key).unwrap_or_default()
+    }
+
+    /// Get the values of several keys in one call.
+    ///
+    /// This method is similar to calling [get()](#method.get) for each of the keys. All the values must be
+    /// of the same type `V`. The returned vector is aligned with `keys`: each position holds `Some(V)` if
+    /// the matching key was found and its value is of type `V`, or `None` otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - the keys to get the values of
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// db.set("width", &100).unwrap();
+    /// db.set("height", &200).unwrap();
+    ///
+    /// let sizes = db.get_many::<i32>(&["width", "height", "depth"]);
+    /// assert_eq!(sizes, vec![Some(100), Some(200), None]);
+    /// ```
+    ///
+    pub fn get_many<V>(&self, keys: &[&str]) -> Vec<Option<V>>
+    where
+        V: DeserializeOwned,
+    {
+        keys.iter().map(|key| self.get(key)).collect()
+    }
+
+    /// Get a value of a key, distinguishing between a missing key and a value of a wrong type.
+    ///
+    /// This method is similar to [get()](#method.get), but instead of returning `None` in both cases
+    /// it returns `Ok(None)` if the key doesn't exist (or has expired) and an `Err(`[Error](error/struct.Error.html)`)`
+    /// of type [ErrorType::Serialization](error/enum.ErrorType.html#variant.Serialization) if the value
+    /// can't be deserialized to `V`. The error contains the message of the underlying serialization library,
+    /// which is useful for debugging type mismatches. Otherwise `Ok(Some(V))` is returned.
+    ///
+    /// Please note that some serialization methods (for example Bincode) don't store type information,
+    /// so reading a value as a wrong type doesn't always fail.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - a string key
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_json("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// db.set("key1", &String::from("hello")).unwrap();
+    ///
+    /// // the key doesn't exist
+    /// assert!(db.try_get::<i32>("key2").unwrap().is_none());
+    ///
+    /// // the key exists but its value isn't a number
+    /// match db.try_get::<i32>("key1") {
+    ///     Ok(_) => println!("this won't happen"),
+    ///     Err(err) => println!("failed to read key1: {}", err),
+    /// }
+    /// ```
+    ///
+    pub fn try_get<V>(&self, key: &str) -> Result<Option<V>>
+    where
+        V: DeserializeOwned,
+    {
+        if self.is_expired(key) {
+            return Ok(None);
+        }
+
+        match self.map.get(key) {
+            Some(val) => match self.serializer.deserialize_data_result::<V>(val) {
+                Ok(value) => Ok(Some(value)),
+                Err(err_str) => Err(PickleDb::deserialization_error(key, err_str)),
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Get the serialized bytes of a value.
+    ///
+    /// This method returns the value stored under a key exactly as it's stored in the DB, meaning
+    /// serialized according to the [SerializationMethod](enum.SerializationMethod.html) this DB uses.
+    /// This is useful for debugging or for passing values to [set_raw()](#method.set_raw) without
+    /// deserializing and serializing them again.
+    ///
+    /// If the key doesn't exist or has expired `None` is returned, otherwise `Some(&[u8])` is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - a string key
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_json("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// db.set("key1", &100).unwrap();
+    ///
+    /// // this DB uses JSON serialization, so the bytes are "100"
+    /// assert_eq!(db.get_raw("key1").unwrap(), b"100");
+    /// ```
+    ///
+    pub fn get_raw(&self, key: &str) -> Option<&[u8]> {
+        if self.is_expired(key) {
+            return None;
+        }
+
+        self.map.get(key).map(|val| val.as_slice())
+    }
+
+    /// Get the size in bytes of a value as it's stored in the DB.
+    ///
+    /// This is the length of the serialized value, so it depends on the serialization method of the DB.
+    /// It's useful for finding which keys take most of the space without extracting the values.
+    ///
+    /// If the key doesn't exist or has expired `None` is returned. Lists aren't considered, use
+    /// [list_bytes_len()](#method.list_bytes_len) for them.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - a string key
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_json("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// db.set("key1", &100).unwrap();
+    ///
+    /// // this DB uses JSON serialization, so the value is stored as "100"
+    /// assert_eq!(db.value_len("key1"), Some(3));
+    /// ```
+    ///
+    pub fn value_len(&self, key: &str) -> Option<usize> {
+        self.get_raw(key).map(|val| val.len())
+    }
+
+    /// Get the kind of a value stored in the DB.
+    ///
+    /// This method inspects the serialized value of `key` and returns its coarse kind (a number, a string,
+    /// an object, etc.) without knowing its type. This is useful for displaying or inspecting a DB that
+    /// contains values of different types.
+    ///
+    /// The kind can be determined only for serialization methods that store enough information about the
+    /// structure of the data. This means it's not supported for
+    /// [SerializationMethod::Bin](enum.SerializationMethod.html#variant.Bin), which stores the raw data only.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - the key of the value
+    ///
+    /// # Return value
+    ///
+    /// * `Some(ValueKind)` - if the key is found and the kind of its value can be determined
+    /// * `None` - if the key isn't found, if it's a list, or if the serialization method doesn't support it
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use pickledb::ValueKind;
+    ///
+    /// # let mut db = pickledb::PickleDb::new_json("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// db.set("key1", &100).unwrap();
+    /// db.set("key2", &vec![1, 2, 3]).unwrap();
+    ///
+    /// assert_eq!(db.value_kind("key1"), Some(ValueKind::Number));
+    /// assert_eq!(db.value_kind("key2"), Some(ValueKind::Array));
+    /// ```
+    ///
+    pub fn value_kind(&self, key: &str) -> Option<ValueKind> {
+        let val = self.get_raw(key)?;
+        self.serializer
+            .deserialize_data::<DeserializedValueKind>(val)
+            .map(|kind| kind.0)
+    }
+
+    /// Check that all the data stored in the DB can be parsed.
+    ///
+    /// This method goes over all the values, list items, set members and hash fields in the DB and checks
+    /// that each one of them is valid data of the serialization method of the DB, without knowing its type.
+    /// This is useful for verifying a DB that was loaded from a file that isn't trusted, or one whose data
+    /// was stored using [set_raw()](#method.set_raw) or [from_entries()](#method.from_entries).
+    ///
+    /// The method returns a vector of `(key, error)` pairs with an entry for each piece of data that can't
+    /// be parsed, in no particular order, so an empty vector means the whole DB is valid. The error of a list
+    /// item, a set member or a hash field also describes which one of them is invalid.
+    ///
+    /// Like [value_kind()](#method.value_kind), the data can be checked only for serialization methods that
+    /// store enough information about its structure. For [SerializationMethod::Bin](enum.SerializationMethod.html#variant.Bin)
+    /// and for custom serializers (see [PickleDbSerializer](trait.PickleDbSerializer.html)) nothing can be
+    /// checked and an empty vector is always returned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let db = pickledb::PickleDb::new_json("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// for (key, error) in db.validate() {
+    ///     println!("Invalid data in '{}': {}", key, error);
+    /// }
+    /// ```
+    ///
+    pub fn validate(&self) -> Vec<(String, String)> {
+        if let SerializationMethod::Bin | SerializationMethod::Custom = self.serializer.ser_method()
+        {
+            return Vec::new();
+        }
+
+        let check = |ser_data: &[u8]| {
+            self.serializer
+                .deserialize_data_result::<DeserializedValueKind>(ser_data)
+                .err()
+        };
+
+        let mut errors = Vec::new();
+        for (key, value) in self.map.iter() {
+            if self.is_expired(key) {
+                continue;
+            }
+            if let Some(err) = check(value) {
+                errors.push((key.to_string(), err));
+            }
+        }
+        for (name, list) in self.list_map.iter() {
+            for (pos, item) in list.iter().enumerate() {
+                if let Some(err) = check(item) {
+                    errors.push((name.to_string(), format!("Item {}: {}", pos, err)));
+                }
+            }
+        }
+        for (name, set) in self.set_map.iter() {
+            for member in set.iter() {
+                if let Some(err) = check(member) {
+                    errors.push((name.to_string(), format!("Member: {}", err)));
+                }
+            }
+        }
+        for (name, hash) in self.hash_map.iter() {
+            for (field, value) in hash.iter() {
+                if let Some(err) = check(value) {
+                    errors.push((name.to_string(), format!("Field '{}': {}", field, err)));
+                }
+            }
+        }
+        errors
+    }
+
+    /// Get a value from a key as a [serde_json::Value](https://docs.rs/serde_json/latest/serde_json/enum.Value.html).
+    ///
+    /// This method deserializes the value of `key` into a generic JSON value, regardless of the type
+    /// that was originally stored and of the serialization method of the DB. This is useful for inspecting
+    /// values or re-serializing them without knowing their type.
+    ///
+    /// Converting a value is possible only for serialization methods that store enough information about
+    /// the structure of the data, so `None` is always returned for
+    /// [SerializationMethod::Bin](enum.SerializationMethod.html#variant.Bin). `None` is also returned for
+    /// values that can't be represented in JSON, for example CBOR maps whose keys aren't strings.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - the key of the value
+    ///
+    /// # Return value
+    ///
+    /// * `Some(serde_json::Value)` - if the key is found and its value can be converted to JSON
+    /// * `None` - if the key isn't found, if it's a list, or if its value can't be converted
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_cbor("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// db.set("key1", &vec![1, 2, 3]).unwrap();
+    ///
+    /// assert_eq!(db.get_json("key1").unwrap(), serde_json::json!([1, 2, 3]));
+    /// ```
+    ///
+    #[cfg(feature = "json")]
+    pub fn get_json(&self, key: &str) -> Option<serde_json::Value> {
+        let val = self.get_raw(key)?;
+        self.serializer.deserialize_data(val)
+    }
+
+    /// Get a value of a key, or set it to a default value if it doesn't exist.
+    ///
+    /// If the key exists and its value can be deserialized to `V`, the value is returned and the
+    /// DB isn't changed. Otherwise `default` is called, the value it returns is stored under this key
+    /// (exactly like calling [set()](#method.set), including triggering a dump according to the
+    /// dump policy) and then returned.
+    ///
+    /// Please note that a key that exists but holds a value of a different type (meaning it can't be
+    /// deserialized to `V`) is treated like a missing key: its value is **overridden** with the default
+    /// value rather than returning an error. The same goes for a list with that name, which is replaced
+    /// by the new value.
+    ///
+    /// This method returns `Ok(V)` upon success or an `Err(`[Error](error/struct.Error.html)`)` if storing
+    /// the default value fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - a string key
+    /// * `default` - a closure that returns the default value to store if the key doesn't exist
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// // the key doesn't exist, so 100 is stored and returned
+    /// let num = db.get_or_insert_with("key1", || 100).unwrap();
+    ///
+    /// // the key already exists, so the stored value (100) is returned
+    /// let same_num = db.get_or_insert_with("key1", || 200).unwrap();
+    /// ```
+    ///
+    pub fn get_or_insert_with<V, F>(&mut self, key: &str, default: F) -> Result<V>
+    where
+        V: Serialize + DeserializeOwned,
+        F: FnOnce() -> V,
+    {
+        if let Some(value) = self.get::<V>(key) {
+            return Ok(value);
+        }
+
+        let value = default();
+        self.set(key, &value)?;
+        Ok(value)
+    }
+
+    /// Update the value of a key using a closure, in a single read-modify-write call.
+    ///
+    /// This method reads the current value of the key and passes it to `f` as `Some(V)`, then stores
+    /// the value `f` returns under this key (exactly like calling [set()](#method.set), including
+    /// triggering a single dump according to the dump policy).
+    ///
+    /// If the key doesn't exist, has expired (see [set_with_ttl()](#method.set_with_ttl)) or holds a value
+    /// that can't be deserialized to `V`, `f` is called with `None` and the value it returns **overrides**
+    /// whatever was stored under this key, similar to [get_or_insert_with()](#method.get_or_insert_with).
+    /// The same goes for a list with that name, which is replaced by the new value.
+    ///
+    /// This method returns `Ok(true)` if an existing value was passed to `f`, `Ok(false)` if `f` was called
+    /// with `None`, or an `Err(`[Error](error/struct.Error.html)`)` if serialization or the DB dump fails.
+    /// In case of a failure the DB is left unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - a string key
+    /// * `f` - a closure that takes the current value (if there is one) and returns the new value
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// // increment a counter, starting from 0 if it doesn't exist yet
+    /// db.update("counter", |count: Option<i32>| count.unwrap_or(0) + 1).unwrap();
+    /// ```
+    ///
+    pub fn update<V, F>(&mut self, key: &str, f: F) -> Result<bool>
+    where
+        V: Serialize + DeserializeOwned,
+        F: FnOnce(Option<V>) -> V,
+    {
+        let value = self.get::<V>(key);
+        let existed = value.is_some();
+        self.set(key, &f(value))?;
+        Ok(existed)
+    }
+
+    /// Set the value of a key only if its current value equals an expected value.
+    ///
+    /// This method serializes `expected` and compares it with the bytes currently stored under the key
+    /// (in the same way [lrem_value()](#method.lrem_value) compares items). If they're equal, `new` is stored
+    /// under the key exactly like calling [set()](#method.set), otherwise nothing is changed and no dump is
+    /// triggered. A key that doesn't exist, has expired (see [set_with_ttl()](#method.set_with_ttl)) or holds
+    /// a list never matches.
+    ///
+    /// This method returns `Ok(true)` if the value was swapped, `Ok(false)` if the current value doesn't equal
+    /// `expected`, or an `Err(`[Error](error/struct.Error.html)`)` if serialization or the DB dump fails.
+    /// In case of a failure the DB is left unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - a string key
+    /// * `expected` - the value the key is expected to hold
+    /// * `new` - the value to set if the key holds the expected value
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// db.set("version", &1).unwrap();
+    ///
+    /// // the value is 1, so it's swapped
+    /// assert!(db.compare_and_swap("version", &1, &2).unwrap());
+    ///
+    /// // the value isn't 1 anymore, so nothing changes
+    /// assert!(!db.compare_and_swap("version", &1, &3).unwrap());
+    /// assert_eq!(db.get::<i32>("version").unwrap(), 2);
+    /// ```
+    ///
+    pub fn compare_and_swap<V>(&mut self, key: &str, expected: &V, new: &V) -> Result<bool>
+    where
+        V: Serialize,
+    {
+        let ser_expected = match self.serializer.serialize_data(expected) {
+            Ok(data) => data,
+            Err(err_str) => return Err(PickleDb::serialization_error(key, err_str)),
+        };
+        let ser_new = match self.serializer.serialize_data(new) {
+            Ok(data) => data,
+            Err(err_str) => return Err(PickleDb::serialization_error(key, err_str)),
+        };
+
+        match self.map.get(key) {
+            Some(value) if *value == ser_expected && !self.is_expired(key) => (),
+            _ => return Ok(false),
+        }
+
+        self.set_raw(key, ser_new)?;
+        Ok(true)
+    }
+
+    /// Check if a key exists.
+    ///
+    /// This method returns `true` if the key exists and `false` otherwise. Keys that have expired
+    /// (see [set_with_ttl()](#method.set_with_ttl)) are treated as if they don't exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - the key to check
+    ///
+    pub fn exists(&self, key: &str) -> bool {
+        self.vexists(key)
+            || self.list_map.contains_key(key)
+            || self.set_map.contains_key(key)
+            || self.hash_map.contains_key(key)
+    }
+
+    /// Check if a key-value pair exists.
+    ///
+    /// This method returns `true` if a value is stored under this key and `false` otherwise.
+    /// The difference between this method and [exists()](#method.exists) is that this methods checks only
+    /// for key-value pairs and [exists()](#method.exists) checks for both values and lists.
+    /// To check only for lists use [lexists()](#method.lexists). Keys that have expired
+    /// (see [set_with_ttl()](#method.set_with_ttl)) are treated as if they don't exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - the key to check
+    ///
+    pub fn vexists(&self, key: &str) -> bool {
+        self.map.contains_key(key) && !self.is_expired(key)
+    }
+
+    /// Find a key whose value is equal to a given value.
+    ///
+    /// This method serializes the value once and looks for a key-value pair whose stored value is equal to it,
+    /// the same way [lrem_value()](#method.lrem_value) compares list items, so no stored value is deserialized.
+    /// Please note the same caveat about comparing serialized values that is described in
+    /// [lcontains()](#method.lcontains). Lists and keys that have expired aren't considered.
+    ///
+    /// The method returns `Some(&str)` with the first matching key that is found, or `None` if no value is equal
+    /// to the given value or if the value can't be serialized. Since the keys aren't kept in any order, if more
+    /// than one key matches it's not defined which one is returned, use [find_keys_by_value()](#method.find_keys_by_value)
+    /// to get all of them.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - a reference of the value to look for
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// db.set("user:1", &String::from("John")).unwrap();
+    /// db.set("user:2", &String::from("Jane")).unwrap();
+    ///
+    /// assert_eq!(db.find_key_by_value(&String::from("Jane")), Some("user:2"));
+    /// ```
+    ///
+    pub fn find_key_by_value<V>(&self, value: &V) -> Option<&str>
+    where
+        V: Serialize,
+    {
+        let serialized_value = self.serializer.serialize_data(value).ok()?;
+        self.map
+            .iter()
+            .find(|(key, val)| **val == serialized_value && !self.is_expired(key))
+            .map(|(key, _)| key.as_str())
+    }
+
+    /// Find all the keys whose values are equal to a given value.
+    ///
+    /// This method is similar to [find_key_by_value()](#method.find_key_by_value) but returns all the matching
+    /// keys, in no particular order. If no value is equal to the given value or if the value can't be serialized
+    /// an empty vector is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - a reference of the value to look for
+    ///
+    pub fn find_keys_by_value<V>(&self, value: &V) -> Vec<&str>
+    where
+        V: Serialize,
+    {
+        let serialized_value = match self.serializer.serialize_data(value) {
+            Ok(serialized_value) => serialized_value,
+            Err(_) => return Vec::new(),
+        };
+        self.map
+            .iter()
+            .filter(|(key, val)| **val == serialized_value && !self.is_expired(key))
+            .map(|(key, _)| key.as_str())
+            .collect()
+    }
+
+    fn is_expired(&self, key: &str) -> bool {
+        match self.expiry_map.get(key) {
+            Some(expiry) => *expiry <= SystemTime::now(),
+            None => false,
+        }
+    }
+
+    // Expired key-value pairs are kept in memory until purge_expired() or the next full dump removes
+    // them, so all the methods that return keys, values or counts skip them
+    fn expired_count(&self, now: SystemTime) -> usize {
+        self.expiry_map
+            .values()
+            .filter(|expiry| **expiry <= now)
+            .count()
+    }
+
+    fn expired_footprint(&self) -> usize {
+        let now = SystemTime::now();
+        self.expiry_map
+            .iter()
+            .filter(|(_, expiry)| **expiry <= now)
+            .map(|(key, _)| self.key_footprint(key))
+            .sum()
+    }
+
+    // remove all the expired key-value pairs from memory without dumping, returns how many were removed
+    fn remove_expired(&mut self) -> usize {
+        let now = SystemTime::now();
+        let expired_keys: Vec<String> = self
+            .expiry_map
+            .iter()
+            .filter(|(_, expiry)| **expiry <= now)
+            .map(|(key, _)| key.to_string())
+            .collect();
+
+        for key in expired_keys.iter() {
+            self.used_bytes -= self.key_footprint(key);
+            self.expiry_map.remove(key);
+            self.map.remove(key);
+            self.clear_cached(Some(key));
+        }
+        expired_keys.len()
+    }
+
+    /// Get a vector of all the keys in the DB.
+    ///
+    /// The keys returned in the vector are not references to the actual key string
+    /// objects but rather a clone of them. To avoid cloning use [all_keys()](#method.all_keys).
+    ///
+    pub fn get_all(&self) -> Vec<String> {
+        [
+            self.get_value_keys(),
+            self.list_map.keys().cloned().collect::<Vec<String>>(),
+            self.set_map.keys().cloned().collect::<Vec<String>>(),
+            self.hash_map.keys().cloned().collect::<Vec<String>>(),
+        ]
+        .concat()
+    }
+
+    /// Get a vector of the keys of all key-value pairs in the DB.
+    ///
+    /// Unlike [get_all()](#method.get_all) this method doesn't return list names.
+    /// The keys returned in the vector are not references to the actual key string
+    /// objects but rather a clone of them. To avoid cloning use [value_keys()](#method.value_keys).
+    ///
+    pub fn get_value_keys(&self) -> Vec<String> {
+        self.value_keys().map(String::from).collect()
+    }
+
+    /// Get a vector of the names of all lists in the DB.
+    ///
+    /// Unlike [get_all()](#method.get_all) this method doesn't return keys of key-value pairs.
+    /// The names returned in the vector are not references to the actual name string
+    /// objects but rather a clone of them. To avoid cloning use [list_keys()](#method.list_keys).
+    ///
+    pub fn get_list_keys(&self) -> Vec<String> {
+        self.list_map.keys().cloned().collect()
+    }
+
+    /// Get a vector of all the key-value pairs in the DB, with the values as raw bytes.
+    ///
+    /// Unlike [iter()](#method.iter) the returned vector holds a copy of the keys and values rather than
+    /// references to them, so it doesn't borrow the DB and can be sent to other threads. The values are
+    /// serialized with the serialization method of the DB, the same as [get_raw()](#method.get_raw) returns,
+    /// and can be stored in another DB that uses the same serialization method with [set_raw()](#method.set_raw).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// # let mut other_db = pickledb::PickleDb::new_bin("2.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// let entries = db.entries();
+    /// std::thread::spawn(move || {
+    ///     for (key, value) in entries {
+    ///         other_db.set_raw(&key, value).unwrap();
+    ///     }
+    /// });
+    /// ```
+    ///
+    pub fn entries(&self) -> Vec<(String, Vec<u8>)> {
+        self.map
+            .iter()
+            .filter(|(key, _)| !self.is_expired(key))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect()
+    }
+
+    /// Get a vector of all the lists in the DB, with their items as raw bytes.
+    ///
+    /// This method is similar to [entries()](#method.entries) but returns a copy of the names of the lists
+    /// and their items. The items are serialized with the serialization method of the DB.
+    ///
+    pub fn list_entries(&self) -> Vec<(String, Vec<Vec<u8>>)> {
+        self.list_map
+            .iter()
+            .map(|(name, list)| (name.clone(), list.clone()))
+            .collect()
+    }
+
+    /// Get all the key-value pairs in the DB as a map of values of type `V`.
+    ///
+    /// This method is the counterpart of [from_map()](#method.from_map): it deserializes all the values in the
+    /// DB into `V` and returns them in a standard `HashMap`, which is convenient when all or most of the values
+    /// are of the same type. Values that can't be deserialized into `V` are skipped, use
+    /// [try_to_map()](#method.try_to_map) to get an error instead. Lists and keys that have expired aren't
+    /// included in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_json("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// db.set("player1", &10).unwrap();
+    /// db.set("player2", &20).unwrap();
+    ///
+    /// let scores = db.to_map::<i32>();
+    /// assert_eq!(scores["player2"], 20);
+    /// ```
+    ///
+    pub fn to_map<V>(&self) -> HashMap<String, V>
+    where
+        V: DeserializeOwned,
+    {
+        self.map
+            .iter()
+            .filter(|(key, _)| !self.is_expired(key))
+            .filter_map(|(key, value)| {
+                self.serializer
+                    .deserialize_data::<V>(value)
+                    .map(|value| (key.to_string(), value))
+            })
+            .collect()
+    }
+
+    /// Get all the key-value pairs in the DB as a map of values of type `V`, or an error if one of the values
+    /// can't be deserialized into `V`.
+    ///
+    /// This method is similar to [to_map()](#method.to_map), but instead of skipping values that can't be
+    /// deserialized it returns an `Err(`[Error](error/struct.Error.html)`)` of type
+    /// [ErrorType::Serialization](error/enum.ErrorType.html#variant.Serialization) whose message contains the
+    /// key of the first such value that is found. Please note that some serialization methods (for example
+    /// Bincode) don't store type information, so reading a value as a wrong type doesn't always fail.
+    ///
+    pub fn try_to_map<V>(&self) -> Result<HashMap<String, V>>
+    where
+        V: DeserializeOwned,
+    {
+        let mut map = HashMap::with_capacity(self.map.len());
+        for (key, value) in self.map.iter() {
+            if self.is_expired(key) {
+                continue;
+            }
+            match self.serializer.deserialize_data_result::<V>(value) {
+                Ok(value) => map.insert(key.to_string(), value),
+                Err(err_str) => return Err(PickleDb::deserialization_error(key, err_str)),
+            };
+        }
+        Ok(map)
+    }
+
+    /// Return an iterator over the keys of all key-value pairs in the DB.
+    ///
+    /// This method is similar to [get_value_keys()](#method.get_value_keys) but returns references
+    /// to the keys instead of cloning them.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// for key in db.value_keys() {
+    ///     println!("{}", key);
+    /// }
+    /// ```
+    ///
+    pub fn value_keys(&self) -> impl Iterator<Item = &str> {
+        self.map
+            .keys()
+            .filter(move |key| !self.is_expired(key))
+            .map(|key| key.as_str())
+    }
+
+    /// Return an iterator over the names of all lists in the DB.
+    ///
+    /// This method is similar to [get_list_keys()](#method.get_list_keys) but returns references
+    /// to the names instead of cloning them.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// for name in db.list_keys() {
+    ///     println!("{}: {} items", name, db.llen(name));
+    /// }
+    /// ```
+    ///
+    pub fn list_keys(&self) -> impl Iterator<Item = &str> {
+        self.list_map.keys().map(|name| name.as_str())
+    }
+
+    /// Return an iterator over all the keys in the DB: the keys of all key-value pairs followed by
+    /// the names of all lists, sets and hashes.
+    ///
+    /// This method is similar to [get_all()](#method.get_all) but returns references to the keys
+    /// instead of cloning them. Unlike [iter()](#method.iter) the values aren't accessed at all.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// let user_keys = db.all_keys().filter(|key| key.starts_with("user:")).count();
+    /// ```
+    ///
+    pub fn all_keys(&self) -> impl Iterator<Item = &str> {
+        self.value_keys()
+            .chain(self.list_keys())
+            .chain(self.set_map.keys().map(|name| name.as_str()))
+            .chain(self.hash_map.keys().map(|name| name.as_str()))
+    }
+
+    /// Get the total number of keys in the DB.
+    ///
+    pub fn total_keys(&self) -> usize {
+        self.value_count()
+            + self.list_map.iter().len()
+            + self.set_map.iter().len()
+            + self.hash_map.iter().len()
+    }
+
+    /// Check if the DB is empty.
+    ///
+    /// This method returns `true` if the DB doesn't contain any key-value pairs, lists, sets or hashes, meaning
+    /// [total_keys()](#method.total_keys) is 0, and `false` otherwise.
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.value_count() == 0
+            && self.list_map.is_empty()
+            && self.set_map.is_empty()
+            && self.hash_map.is_empty()
+    }
+
+    /// Get the number of key-value pairs in the DB.
+    ///
+    /// Unlike [total_keys()](#method.total_keys) this method doesn't count lists.
+    ///
+    pub fn value_count(&self) -> usize {
+        self.map.len() - self.expired_count(SystemTime::now())
+    }
+
+    /// Get the number of lists in the DB.
+    ///
+    /// Unlike [total_keys()](#method.total_keys) this method doesn't count key-value pairs.
+    ///
+    pub fn list_count(&self) -> usize {
+        self.list_map.len()
+    }
+
+    /// Remove a key-value pair, a list, a set or a hash from the DB.
+    ///
+    /// This methods returns `Ok(true)` if the key was found in the DB or `Ok(false)` if it wasn't found.
+    /// It may also return `Err(`[Error](error/struct.Error.html)`)` if key was found but removal failed.
+    /// Removal error is not likely to happen but may occur mostly in cases where this action triggers a DB dump
+    /// (which is decided according to the dump policy)
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - the key or the name of the list, set or hash to remove
+    ///
+    pub fn rem(&mut self, key: &str) -> Result<bool> {
+        // a key is stored in only one of the maps, so only one of them is changed
+        let counted = self.key_footprint(key);
+        self.used_bytes -= counted;
+
+        let remove_map = match self.map.remove(key) {
+            None => None,
+            Some(val) => {
+                let expiry = self.expiry_map.remove(key);
+                match self.dumpdb_key(key) {
+                    Ok(_) => Some(val),
+                    Err(err) => {
+                        self.map.insert(String::from(key), val);
+                        if let Some(expiry) = expiry {
+                            self.expiry_map.insert(String::from(key), expiry);
+                        }
+                        self.used_bytes += counted;
+                        return Err(err);
+                    }
+                }
+            }
+        };
+
+        let remove_list = match self.list_map.remove(key) {
+            None => None,
+            Some(list) => match self.dumpdb_key(key) {
+                Ok(_) => Some(list),
+                Err(err) => {
+                    self.list_map.insert(String::from(key), list);
+                    self.used_bytes += counted;
+                    return Err(err);
+                }
+            },
+        };
+
+        let remove_set = match self.set_map.remove(key) {
+            None => None,
+            Some(set) => match self.dumpdb_key(key) {
+                Ok(_) => Some(set),
+                Err(err) => {
+                    self.set_map.insert(String::from(key), set);
+                    self.used_bytes += counted;
+                    return Err(err);
+                }
+            },
+        };
+
+        let remove_hash = match self.hash_map.remove(key) {
+            None => None,
+            Some(hash) => match self.dumpdb_key(key) {
+                Ok(_) => Some(hash),
+                Err(err) => {
+                    self.hash_map.insert(String::from(key), hash);
+                    self.used_bytes += counted;
+                    return Err(err);
+                }
+            },
+        };
+
+        Ok(remove_map.is_some()
+            || remove_list.is_some()
+            || remove_set.is_some()
+            || remove_hash.is_some())
+    }
+
+    /// Remove a key-value pair from the DB and return its value.
+    ///
+    /// This method is similar to [rem()](#method.rem) but it removes only key-value pairs, and returns the
+    /// removed value the same way [get()](#method.get) would, which is useful for logging the value or
+    /// moving it elsewhere. It's the value counterpart of [lpop()](#method.lpop).
+    ///
+    /// This method returns `Ok(Some(V))` if the key was found and removed, or `Ok(None)` if the key doesn't
+    /// exist, has expired or holds a list, a set or a hash. A value that can't be deserialized into `V` isn't
+    /// removed and `Ok(None)` is returned as well. If the dump that is triggered by the removal fails, the value
+    /// is put back and an `Err(`[Error](error/struct.Error.html)`)` is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - the key to remove
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// db.set("key1", &100).unwrap();
+    ///
+    /// // remove the key and get its value
+    /// assert_eq!(db.take::<i32>("key1").unwrap(), Some(100));
+    /// assert!(!db.exists("key1"));
+    /// ```
+    ///
+    pub fn take<V>(&mut self, key: &str) -> Result<Option<V>>
+    where
+        V: DeserializeOwned,
+    {
+        let value = match self.get::<V>(key) {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+
+        let counted = self.key_footprint(key);
+        let orig_value = self.map.remove(key).unwrap();
+        let expiry = self.expiry_map.remove(key);
+        self.used_bytes -= counted;
+        match self.dumpdb_key(key) {
+            Ok(_) => Ok(Some(value)),
+            Err(err) => {
+                self.map.insert(String::from(key), orig_value);
+                if let Some(expiry) = expiry {
+                    self.expiry_map.insert(String::from(key), expiry);
+                }
+                self.used_bytes += counted;
+                Err(err)
+            }
+        }
+    }
+
+    /// Remove several key-value pairs, lists, sets or hashes from the DB.
+    ///
+    /// This method is similar to calling [rem()](#method.rem) for each of the keys, but it triggers only a single
+    /// DB dump (which is decided according to the dump policy), which is much faster. Keys that aren't found
+    /// in the DB are ignored.
+    ///
+    /// This method returns the number of keys that were found and removed, or an `Err(`[Error](error/struct.Error.html)`)`
+    /// if the dump fails. In that case none of the keys are removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - the keys or the names of the lists, sets or hashes to remove
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// db.set("key1", &1).unwrap();
+    /// db.set("key2", &2).unwrap();
+    /// db.lcreate("list1").unwrap();
+    ///
+    /// // a single dump removes all of these keys
+    /// assert_eq!(db.rem_many(&["key1", "key2", "list1", "non_existent_key"]).unwrap(), 3);
+    /// ```
+    ///
+    pub fn rem_many(&mut self, keys: &[&str]) -> Result<usize> {
+        let mut originals = Vec::new();
+        for key in keys {
+            self.used_bytes -= self.key_footprint(key);
+            let orig_value = self.map.remove(*key);
+            let orig_list = self.list_map.remove(*key);
+            let orig_set = self.set_map.remove(*key);
+            let orig_hash = self.hash_map.remove(*key);
+            let orig_expiry = self.expiry_map.remove(*key);
+            if orig_value.is_some()
+                || orig_list.is_some()
+                || orig_set.is_some()
+                || orig_hash.is_some()
+            {
+                originals.push((
+                    *key,
+                    orig_value,
+                    orig_list,
+                    orig_set,
+                    orig_hash,
+                    orig_expiry,
+                ));
+            }
+        }
+
+        if originals.is_empty() {
+            return Ok(0);
+        }
+
+        match self.dumpdb() {
+            Ok(_) => Ok(originals.len()),
+            Err(err) => {
+                for (key, orig_value, orig_list, orig_set, orig_hash, orig_expiry) in originals {
+                    let counted = self.key_footprint(key);
+                    if let Some(orig_value) = orig_value {
+                        self.map.insert(String::from(key), orig_value);
+                    }
+                    if let Some(orig_list) = orig_list {
+                        self.list_map.insert(String::from(key), orig_list);
+                    }
+                    if let Some(orig_set) = orig_set {
+                        self.set_map.insert(String::from(key), orig_set);
+                    }
+                    if let Some(orig_hash) = orig_hash {
+                        self.hash_map.insert(String::from(key), orig_hash);
+                    }
+                    if let Some(orig_expiry) = orig_expiry {
+                        self.expiry_map.insert(String::from(key), orig_expiry);
+                    }
+                    self.recount_key(key, counted);
+                }
+                Err(err)
+            }
+        }
+    }
+
+    /// Keep only the key-value pairs that match a predicate.
+    ///
+    /// This method calls `f` with each key and its value, and removes the key-value pairs for which `f`
+    /// returns `false`. This is useful for evicting entries from a cache, for example. All removals
+    /// trigger only a single DB dump (which is decided according to the dump policy).
+    ///
+    /// Only key-value pairs whose value can be deserialized to type `V` are passed to `f`. Values of
+    /// other types, as well as lists and expired keys, are left untouched and are never removed.
+    /// Please note that since Bincode isn't self-describing, values of other types may be deserialized
+    /// to `V` successfully when using [SerializationMethod::Bin](enum.SerializationMethod.html#variant.Bin).
+    ///
+    /// This method returns the number of removed key-value pairs, or an `Err(`[Error](error/struct.Error.html)`)`
+    /// if the dump fails. In that case none of the key-value pairs are removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - a predicate that gets a key and its value and returns whether to keep them
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_json("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// db.set("player1", &10).unwrap();
+    /// db.set("player2", &50).unwrap();
+    ///
+    /// // remove all scores below 20
+    /// assert_eq!(db.retain(|_key, score: &i32| *score >= 20).unwrap(), 1);
+    /// ```
+    ///
+    pub fn retain<V, F>(&mut self, mut f: F) -> Result<usize>
+    where
+        V: DeserializeOwned,
+        F: FnMut(&str, &V) -> bool,
+    {
+        let mut keys_to_remove = Vec::new();
+        for (key, val) in self.map.iter() {
+            if self.is_expired(key) {
+                continue;
+            }
+
+            if let Some(value) = self.serializer.deserialize_data::<V>(val) {
+                if !f(key, &value) {
+                    keys_to_remove.push(key.to_string());
+                }
+            }
+        }
+
+        if keys_to_remove.is_empty() {
+            return Ok(0);
+        }
+
+        let mut originals = Vec::with_capacity(keys_to_remove.len());
+        for key in keys_to_remove {
+            self.used_bytes -= self.key_footprint(&key);
+            let orig_value = self.map.remove(&key);
+            let orig_expiry = self.expiry_map.remove(&key);
+            originals.push((key, orig_value, orig_expiry));
+        }
+
+        match self.dumpdb() {
+            Ok(_) => Ok(originals.len()),
+            Err(err) => {
+                for (key, orig_value, orig_expiry) in originals {
+                    if let Some(orig_expiry) = orig_expiry {
+                        self.expiry_map.insert(key.clone(), orig_expiry);
+                    }
+                    if let Some(orig_value) = orig_value {
+                        self.used_bytes += key.len() + orig_value.len();
+                        self.map.insert(key, orig_value);
+                    }
+                }
+                Err(err)
+            }
+        }
+    }
+
+    /// Remove all key-value pairs, lists, sets and hashes from the DB.
+    ///
+    /// This method empties the DB and triggers a single DB dump (which is decided according to
+    /// the dump policy), which is much faster than removing the keys one by one.
+    ///
+    /// This method returns `Ok` if clearing the DB is successful or an `Err(`[Error](error/struct.Error.html)`)`
+    /// if the dump fails, in which case all the data is restored so the in-memory DB remains unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// db.set("key1", &100).unwrap();
+    /// db.lcreate("list1").unwrap();
+    ///
+    /// // now the DB is empty
+    /// db.clear().unwrap();
+    /// ```
+    ///
+    pub fn clear(&mut self) -> Result<()> {
+        let orig_map = std::mem::take(&mut self.map);
+        let orig_list_map = std::mem::take(&mut self.list_map);
+        let orig_expiry_map = std::mem::take(&mut self.expiry_map);
+        let orig_set_map = std::mem::take(&mut self.set_map);
+        let orig_hash_map = std::mem::take(&mut self.hash_map);
+        let orig_used_bytes = std::mem::take(&mut self.used_bytes);
+        match self.dumpdb() {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                self.map = orig_map;
+                self.list_map = orig_list_map;
+                self.expiry_map = orig_expiry_map;
+                self.set_map = orig_set_map;
+                self.hash_map = orig_hash_map;
+                self.used_bytes = orig_used_bytes;
+                Err(err)
+            }
+        }
+    }
+
+    /// Replace all the key-value pairs in the DB with new ones.
+    ///
+    /// This method removes all the existing key-value pairs from the DB, stores the entries of `entries` instead
+    /// and triggers a single DB dump (which is decided according to the dump policy). This is useful for restoring
+    /// a snapshot or loading default values, and is much faster than calling [clear()](#method.clear) and then
+    /// [set()](#method.set) for each entry.
+    ///
+    /// Lists, sets and hashes are removed as well if `clear_lists` is `true`. Otherwise they're kept, except
+    /// those whose name is one of the new keys, which are overridden by the new values like in [set()](#method.set).
+    ///
+    /// This method returns `Ok` if all the entries are stored successfully or an `Err(`[Error](error/struct.Error.html)`)`
+    /// if serializing one of the values or the DB dump fails, in which case the DB is left unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `entries` - the new key-value pairs
+    /// * `clear_lists` - whether to also remove all the lists, sets and hashes from the DB
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::collections::HashMap;
+    ///
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// let mut defaults = HashMap::new();
+    /// defaults.insert(String::from("volume"), 50);
+    /// defaults.insert(String::from("brightness"), 100);
+    ///
+    /// // now the DB holds only the default values, and the lists in it are kept
+    /// db.replace_all(defaults, false).unwrap();
+    /// ```
+    ///
+    pub fn replace_all<V>(&mut self, entries: HashMap<String, V>, clear_lists: bool) -> Result<()>
+    where
+        V: Serialize,
+    {
+        let mut new_map = HashMap::with_capacity(entries.len());
+        for (key, value) in entries {
+            match self.serializer.serialize_data(&value) {
+                Ok(data) => new_map.insert(key, data),
+                Err(err_str) => return Err(PickleDb::serialization_error(&key, err_str)),
+            };
+        }
+
+        if self.max_bytes.is_some() {
+            let removed = self
+                .all_keys()
+                .filter(|key| {
+                    clear_lists || self.map.contains_key(*key) || new_map.contains_key(*key)
+                })
+                .map(|key| self.key_footprint(key))
+                .sum();
+            let added = new_map
+                .iter()
+                .map(|(key, value)| key.len() + value.len())
+                .sum();
+            self.check_size_limit(removed, added)?;
+        }
+
+        let orig_map = std::mem::replace(&mut self.map, new_map);
+        let orig_expiry_map = std::mem::take(&mut self.expiry_map);
+        let mut orig_list_map = HashMap::new();
+        let mut orig_set_map = HashMap::new();
+        let mut orig_hash_map = HashMap::new();
+        if clear_lists {
+            orig_list_map = std::mem::take(&mut self.list_map);
+            orig_set_map = std::mem::take(&mut self.set_map);
+            orig_hash_map = std::mem::take(&mut self.hash_map);
+        } else {
+            for key in self.map.keys() {
+                if let Some((key, list)) = self.list_map.remove_entry(key) {
+                    orig_list_map.insert(key, list);
+                } else if let Some((key, set)) = self.set_map.remove_entry(key) {
+                    orig_set_map.insert(key, set);
+                } else if let Some((key, hash)) = self.hash_map.remove_entry(key) {
+                    orig_hash_map.insert(key, hash);
+                }
+            }
+        }
+        let used_bytes = self.count_used_bytes();
+        let orig_used_bytes = std::mem::replace(&mut self.used_bytes, used_bytes);
+
+        match self.dumpdb() {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                self.map = orig_map;
+                self.expiry_map = orig_expiry_map;
+                self.list_map.extend(orig_list_map);
+                self.set_map.extend(orig_set_map);
+                self.hash_map.extend(orig_hash_map);
+                self.used_bytes = orig_used_bytes;
+                Err(err)
+            }
+        }
+    }
+
+    /// Rename a key-value pair, a list, a set or a hash.
+    ///
+    /// This method moves the value, list, set or hash stored under `old` so it's stored under `new`.
+    /// The stored data is moved as is, meaning it isn't deserialized and serialized again.
+    /// If a value, a list, a set or a hash already exists under `new` it will be overridden.
+    ///
+    /// This methods returns `Ok(true)` if `old` was found in the DB and renamed or `Ok(false)` if it
+    /// wasn't found. It may also return `Err(`[Error](error/struct.Error.html)`)` if renaming failed,
+    /// in which case the DB is left unchanged. Failures are not likely to happen but may occur mostly
+    /// in cases where this action triggers a DB dump (which is decided according to the dump policy)
+    ///
+    /// # Arguments
+    ///
+    /// * `old` - the key or list name to rename
+    /// * `new` - the new key or list name
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// db.set("temp_key", &100).unwrap();
+    ///
+    /// // now the value is stored under "final_key" and "temp_key" no longer exists
+    /// db.rename("temp_key", "final_key").unwrap();
+    /// ```
+    ///
+    pub fn rename(&mut self, old: &str, new: &str) -> Result<bool> {
+        if !self.exists(old) {
+            return Ok(false);
+        }
+        if old == new {
+            return Ok(true);
+        }
+
+        let old_counted = self.key_footprint(old);
+        let new_counted = self.key_footprint(new);
+        self.check_size_limit(
+            old_counted + new_counted,
+            old_counted - old.len() + new.len(),
+        )?;
+
+        let orig_value = self.map.remove(new);
+        let orig_list = self.list_map.remove(new);
+        let orig_set = self.set_map.remove(new);
+        let orig_hash = self.hash_map.remove(new);
+        let orig_expiry = self.expiry_map.remove(new);
+
+        if let Some(value) = self.map.remove(old) {
+            self.map.insert(String::from(new), value);
+        }
+        if let Some(list) = self.list_map.remove(old) {
+            self.list_map.insert(String::from(new), list);
+        }
+        if let Some(set) = self.set_map.remove(old) {
+            self.set_map.insert(String::from(new), set);
+        }
+        if let Some(hash) = self.hash_map.remove(old) {
+            self.hash_map.insert(String::from(new), hash);
+        }
+        if let Some(expiry) = self.expiry_map.remove(old) {
+            self.expiry_map.insert(String::from(new), expiry);
+        }
+        self.recount_key(old, old_counted);
+        self.recount_key(new, new_counted);
+
+        match self.dumpdb() {
+            Ok(_) => Ok(true),
+            Err(err) => {
+                let old_counted = self.key_footprint(old);
+                let new_counted = self.key_footprint(new);
+                if let Some(value) = self.map.remove(new) {
+                    self.map.insert(String::from(old), value);
+                }
+                if let Some(list) = self.list_map.remove(new) {
+                    self.list_map.insert(String::from(old), list);
+                }
+                if let Some(set) = self.set_map.remove(new) {
+                    self.set_map.insert(String::from(old), set);
+                }
+                if let Some(hash) = self.hash_map.remove(new) {
+                    self.hash_map.insert(String::from(old), hash);
+                }
+                if let Some(expiry) = self.expiry_map.remove(new) {
+                    self.expiry_map.insert(String::from(old), expiry);
+                }
+                if let Some(value) = orig_value {
+                    self.map.insert(String::from(new), value);
+                }
+                if let Some(list) = orig_list {
+                    self.list_map.insert(String::from(new), list);
+                }
+                if let Some(set) = orig_set {
+                    self.set_map.insert(String::from(new), set);
+                }
+                if let Some(hash) = orig_hash {
+                    self.hash_map.insert(String::from(new), hash);
+                }
+                if let Some(expiry) = orig_expiry {
+                    self.expiry_map.insert(String::from(new), expiry);
+                }
+                self.recount_key(old, old_counted);
+                self.recount_key(new, new_counted);
+                Err(err)
+            }
+        }
+    }
+
+    /// Copy a key-value pair, a list, a set or a hash to a new key.
+    ///
+    /// This method is similar to [rename()](#method.rename) but the value, list, set or hash stored under
+    /// `src` is kept, and a copy of it is stored under `dst`. The stored data is copied as is, meaning it isn't
+    /// deserialized and serialized again, so it works for values of any type. If the value has an expiry
+    /// time (see [set_with_ttl()](#method.set_with_ttl)) the copy expires at the same time. If a value, a list,
+    /// a set or a hash already exists under `dst` it will be overridden.
+    ///
+    /// This methods returns `Ok(true)` if `src` was found in the DB and copied or `Ok(false)` if it
+    /// wasn't found. It may also return `Err(`[Error](error/struct.Error.html)`)` if copying failed,
+    /// in which case the DB is left unchanged. Failures are not likely to happen but may occur mostly
+    /// in cases where this action triggers a DB dump (which is decided according to the dump policy)
+    ///
+    /// # Arguments
+    ///
+    /// * `src` - the key or list name to copy
+    /// * `dst` - the key or list name to store the copy under
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// db.set("config", &vec![1, 2, 3]).unwrap();
+    ///
+    /// // now both "config" and "config_backup" hold [1, 2, 3]
+    /// db.copy("config", "config_backup").unwrap();
+    /// ```
+    ///
+    pub fn copy(&mut self, src: &str, dst: &str) -> Result<bool> {
+        if !self.exists(src) {
+            return Ok(false);
+        }
+        if src == dst {
+            return Ok(true);
+        }
+
+        let counted = self.key_footprint(dst);
+        self.check_size_limit(counted, self.key_footprint(src) - src.len() + dst.len())?;
+
+        let orig_value = self.map.remove(dst);
+        let orig_list = self.list_map.remove(dst);
+        let orig_set = self.set_map.remove(dst);
+        let orig_hash = self.hash_map.remove(dst);
+        let orig_expiry = self.expiry_map.remove(dst);
+
+        if let Some(value) = self.map.get(src) {
+            self.map.insert(String::from(dst), value.clone());
+        }
+        if let Some(list) = self.list_map.get(src) {
+            self.list_map.insert(String::from(dst), list.clone());
+        }
+        if let Some(set) = self.set_map.get(src) {
+            self.set_map.insert(String::from(dst), set.clone());
+        }
+        if let Some(hash) = self.hash_map.get(src) {
+            self.hash_map.insert(String::from(dst), hash.clone());
+        }
+        if let Some(expiry) = self.expiry_map.get(src) {
+            self.expiry_map.insert(String::from(dst), *expiry);
+        }
+        self.recount_key(dst, counted);
+
+        match self.dumpdb_key(dst) {
+            Ok(_) => Ok(true),
+            Err(err) => {
+                let counted = self.key_footprint(dst);
+                self.map.remove(dst);
+                self.list_map.remove(dst);
+                self.set_map.remove(dst);
+                self.hash_map.remove(dst);
+                self.expiry_map.remove(dst);
+                if let Some(value) = orig_value {
+                    self.map.insert(String::from(dst), value);
+                }
+                if let Some(list) = orig_list {
+                    self.list_map.insert(String::from(dst), list);
+                }
+                if let Some(set) = orig_set {
+                    self.set_map.insert(String::from(dst), set);
+                }
+                if let Some(hash) = orig_hash {
+                    self.hash_map.insert(String::from(dst), hash);
+                }
+                if let Some(expiry) = orig_expiry {
+                    self.expiry_map.insert(String::from(dst), expiry);
+                }
+                self.recount_key(dst, counted);
+                Err(err)
+            }
+        }
+    }
+
+    /// Swap the data stored under two keys.
+    ///
+    /// This method exchanges the values, lists, sets or hashes stored under `key_a` and `key_b`, including their
+    /// expiry times (see [set_with_ttl()](#method.set_with_ttl)). Like [rename()](#method.rename), the stored
+    /// data is moved as is, meaning it isn't deserialized and serialized again. The keys may hold different
+    /// kinds of data, for example a value may be swapped with a list.
+    ///
+    /// This methods returns `Ok(true)` if both keys were found in the DB and swapped or `Ok(false)` if
+    /// any of them wasn't found, in which case nothing is changed. It may also return
+    /// `Err(`[Error](error/struct.Error.html)`)` if swapping failed, in which case the DB is left unchanged.
+    /// Failures are not likely to happen but may occur mostly in cases where this action triggers a DB dump
+    /// (which is decided according to the dump policy)
+    ///
+    /// # Arguments
+    ///
+    /// * `key_a` - the first key or list name
+    /// * `key_b` - the second key or list name
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// db.set("active", &String::from("config A")).unwrap();
+    /// db.set("staged", &String::from("config B")).unwrap();
+    ///
+    /// // now "active" holds "config B" and "staged" holds "config A"
+    /// db.swap("active", "staged").unwrap();
+    /// ```
+    ///
+    pub fn swap(&mut self, key_a: &str, key_b: &str) -> Result<bool> {
+        if !self.exists(key_a) || !self.exists(key_b) {
+            return Ok(false);
+        }
+        if key_a == key_b {
+            return Ok(true);
+        }
+
+        self.swap_entries(key_a, key_b);
+        match self.dumpdb() {
+            Ok(_) => Ok(true),
+            Err(err) => {
+                self.swap_entries(key_a, key_b);
+                Err(err)
+            }
+        }
+    }
+
+    fn swap_entries(&mut self, key_a: &str, key_b: &str) {
+        let counted_a = self.key_footprint(key_a);
+        let counted_b = self.key_footprint(key_b);
+
+        let value_a = self.map.remove(key_a);
+        let list_a = self.list_map.remove(key_a);
+        let set_a = self.set_map.remove(key_a);
+        let hash_a = self.hash_map.remove(key_a);
+        let expiry_a = self.expiry_map.remove(key_a);
+
+        if let Some(value) = self.map.remove(key_b) {
+            self.map.insert(String::from(key_a), value);
+        }
+        if let Some(list) = self.list_map.remove(key_b) {
+            self.list_map.insert(String::from(key_a), list);
+        }
+        if let Some(set) = self.set_map.remove(key_b) {
+            self.set_map.insert(String::from(key_a), set);
+        }
+        if let Some(hash) = self.hash_map.remove(key_b) {
+            self.hash_map.insert(String::from(key_a), hash);
+        }
+        if let Some(expiry) = self.expiry_map.remove(key_b) {
+            self.expiry_map.insert(String::from(key_a), expiry);
+        }
+
+        if let Some(value) = value_a {
+            self.map.insert(String::from(key_b), value);
+        }
+        if let Some(list) = list_a {
+            self.list_map.insert(String::from(key_b), list);
+        }
+        if let Some(set) = set_a {
+            self.set_map.insert(String::from(key_b), set);
+        }
+        if let Some(hash) = hash_a {
+            self.hash_map.insert(String::from(key_b), hash);
+        }
+        if let Some(expiry) = expiry_a {
+            self.expiry_map.insert(String::from(key_b), expiry);
+        }
+
+        self.recount_key(key_a, counted_a);
+        self.recount_key(key_b, counted_b);
+    }
+
+    /// Remove all the key-value pairs that have expired.
+    ///
+    /// Key-value pairs set with [set_with_ttl()](#method.set_with_ttl) aren't removed from the DB as soon as
+    /// they expire. They're treated as if they don't exist by all the methods that read keys, values and counts,
+    /// and they're removed the next time the entire DB is dumped to the file. This method proactively removes
+    /// all of them and triggers a DB dump (which is decided according to the dump policy) if any key was removed.
+    ///
+    /// This method returns the number of key-value pairs that were removed, or an
+    /// `Err(`[Error](error/struct.Error.html)`)` if the dump fails. Since these keys can't be accessed anyway
+    /// they're removed from memory even if the dump fails, and the DB stays dirty (see
+    /// [is_dirty()](#method.is_dirty)) until the next successful dump.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// let removed = db.purge_expired().unwrap();
+    /// println!("{} keys have expired", removed);
+    /// ```
+    ///
+    pub fn purge_expired(&mut self) -> Result<usize> {
+        let removed = self.remove_expired();
+        if removed > 0 {
+            self.dumpdb()?;
+        }
+        Ok(removed)
+    }
+
+    /// Merge the contents of another DB into this DB.
+    ///
+    /// This method copies all the key-value pairs, lists, sets and hashes of `other` into this DB. Keys that exist
+    /// in both DBs are resolved according to `policy`: either the data of `other` overrides the current data or
+    /// the current data is kept. A value in one DB and a list, a set or a hash with the same name in the other DB
+    /// are also considered a conflict. Keys of `other` that have expired are ignored, and TTLs of the copied keys are kept.
+    ///
+    /// The data is copied as is, meaning it isn't deserialized and serialized again. That's why both DBs
+    /// must use the same serialization method, otherwise an `Err(`[Error](error/struct.Error.html)`)` of type
+    /// [ErrorType::Serialization](error/enum.ErrorType.html#variant.Serialization) is returned and nothing is merged.
+    ///
+    /// A single DB dump (which is decided according to the dump policy) is triggered after all data is merged.
+    /// This method returns `Ok` if the merge is successful or an `Err(`[Error](error/struct.Error.html)`)` if it fails,
+    /// in which case the DB is left unchanged. If the merged data would make the DB larger than its size limit (see
+    /// [PickleDbBuilder::max_bytes()](struct.PickleDbBuilder.html#method.max_bytes)), an error of type
+    /// [ErrorType::SizeLimitExceeded](error/enum.ErrorType.html#variant.SizeLimitExceeded) is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - the DB to copy the data from
+    /// * `policy` - an enum value that determines how keys that exist in both DBs are handled
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use pickledb::{PickleDb, PickleDbDumpPolicy, PickleDbMergePolicy};
+    ///
+    /// let mut db = PickleDb::load_json("example.db", PickleDbDumpPolicy::AutoDump).unwrap();
+    /// let other_db = PickleDb::load_json("other_example.db", PickleDbDumpPolicy::NeverDump).unwrap();
+    ///
+    /// // copy all the data from other_db, keeping the current values of keys that exist in both DBs
+    /// db.merge(&other_db, PickleDbMergePolicy::SkipExisting).unwrap();
+    /// ```
+    ///
+    pub fn merge(&mut self, other: &PickleDb, policy: PickleDbMergePolicy) -> Result<()> {
+        if self.serializer.ser_method() != other.serializer.ser_method() {
+            return Err(Error::new(ErrorCode::Serialization(format!(
+                "Cannot merge a DB serialized with {} into a DB serialized with {}",
+                other.serializer.ser_method(),
+                self.serializer.ser_method()
+            ))));
+        }
+
+        let skip_existing = matches!(policy, PickleDbMergePolicy::SkipExisting);
+        let orig_map = self.map.clone();
+        let orig_list_map = self.list_map.clone();
+        let orig_expiry_map = self.expiry_map.clone();
+        let orig_set_map = self.set_map.clone();
+        let orig_hash_map = self.hash_map.clone();
+
+        for (key, value) in other.map.iter() {
+            if other.is_expired(key) || (skip_existing && self.exists(key)) {
+                continue;
+            }
+
+            self.list_map.remove(key);
+            self.set_map.remove(key);
+            self.hash_map.remove(key);
+            self.map.insert(key.to_string(), value.to_vec());
+            match other.expiry_map.get(key) {
+                Some(expiry) => self.expiry_map.insert(key.to_string(), *expiry),
+                None => self.expiry_map.remove(key),
+            };
+        }
+
+        for (name, list) in other.list_map.iter() {
+            if skip_existing && self.exists(name) {
+                continue;
+            }
+
+            self.map.remove(name);
+            self.expiry_map.remove(name);
+            self.set_map.remove(name);
+            self.hash_map.remove(name);
+            self.list_map.insert(name.to_string(), list.to_vec());
+        }
+
+        for (name, set) in other.set_map.iter() {
+            if skip_existing && self.exists(name) {
+                continue;
+            }
+
+            self.map.remove(name);
+            self.expiry_map.remove(name);
+            self.list_map.remove(name);
+            self.hash_map.remove(name);
+            self.set_map.insert(name.to_string(), set.clone());
+        }
+
+        for (name, hash) in other.hash_map.iter() {
+            if skip_existing && self.exists(name) {
+                continue;
+            }
+
+            self.map.remove(name);
+            self.expiry_map.remove(name);
+            self.list_map.remove(name);
+            self.set_map.remove(name);
+            self.hash_map.insert(name.to_string(), hash.clone());
+        }
+        let used_bytes = self.count_used_bytes();
+        let orig_used_bytes = std::mem::replace(&mut self.used_bytes, used_bytes);
+
+        let result = match self.max_bytes {
+            Some(max_bytes) if self.memory_footprint() > max_bytes => {
+                Err(Error::new(ErrorCode::SizeLimitExceeded(max_bytes)))
+            }
+            _ => self.dumpdb(),
+        };
+        match result {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                self.map = orig_map;
+                self.list_map = orig_list_map;
+                self.expiry_map = orig_expiry_map;
+                self.set_map = orig_set_map;
+                self.hash_map = orig_hash_map;
+                self.used_bytes = orig_used_bytes;
+                Err(err)
+            }
+        }
+    }
+
+    /// Convert the DB to a different serialization method and save it to a new file.
+    ///
+    /// This method creates a new DB in `new_path` that uses `new_method`, and copies all the values,
+    /// lists, sets and hashes to it. Each value, list item, set member and hash field value is deserialized
+    /// with the current serialization method and serialized again with the new one. The new DB is written to the file
+    /// right away (regardless of the dump policy) and it uses the same dump policy as this DB. This DB
+    /// itself isn't affected.
+    ///
+    /// Values are converted without knowing their types, so this works only with serialization methods
+    /// that store enough information about the structure of the data. This means converting from or to
+    /// [SerializationMethod::Bin](enum.SerializationMethod.html#variant.Bin) isn't supported. Every value is
+    /// also verified to be read back exactly the same after the conversion, which isn't always the case,
+    /// for example because TOML doesn't support null values, or because JSON doesn't support byte arrays.
+    ///
+    /// This method returns the new DB upon success or an `Err(`[Error](error/struct.Error.html)`)` if
+    /// converting one of the values or writing the file fails. The error message specifies which key
+    /// couldn't be converted.
+    ///
+    /// # Arguments
+    ///
+    /// * `new_path` - a path where the new DB will be stored. If it already exists it will be overridden
+    /// * `new_method` - the serialization method of the new DB
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use pickledb::{PickleDb, PickleDbDumpPolicy, SerializationMethod};
+    ///
+    /// let json_db = PickleDb::load_json("example.db", PickleDbDumpPolicy::NeverDump).unwrap();
+    ///
+    /// // switch from JSON to CBOR
+    /// let cbor_db = json_db.convert_to("example.cbor.db", SerializationMethod::Cbor).unwrap();
+    /// ```
+    ///
+    pub fn convert_to<P: AsRef<Path>>(
+        &self,
+        new_path: P,
+        new_method: SerializationMethod,
+    ) -> Result<PickleDb> {
+        // the new DB isn't dumped (also when it's dropped) until all values are converted
+        let mut new_db = PickleDb::new(new_path, PickleDbDumpPolicy::NeverDump, new_method);
+        new_db.temp_dir = self.temp_dir.clone();
+        #[cfg(feature = "compression")]
+        {
+            new_db.compression = self.compression;
+        }
+        #[cfg(feature = "crypt")]
+        {
+            new_db.encryption_key = self.encryption_key;
+        }
+
+        for (key, value) in self.map.iter() {
+            if self.is_expired(key) {
+                continue;
+            }
+
+            match self.convert_value(value, &new_db.serializer) {
+                Ok(new_value) => new_db.map.insert(key.to_string(), new_value),
+                Err(err_str) => {
+                    return Err(Error::new(ErrorCode::Serialization(format!(
+                        "Cannot convert the value of key '{}': {}",
+                        key, err_str
+                    )))
+                    .with_key(key))
+                }
+            };
+            if let Some(expiry) = self.expiry_map.get(key) {
+                new_db.expiry_map.insert(key.to_string(), *expiry);
+            }
+        }
+
+        for (name, list) in self.list_map.iter() {
+            let mut new_list = Vec::with_capacity(list.len());
+            for (index, item) in list.iter().enumerate() {
+                match self.convert_value(item, &new_db.serializer) {
+                    Ok(new_item) => new_list.push(new_item),
+                    Err(err_str) => {
+                        return Err(Error::new(ErrorCode::Serialization(format!(
+                            "Cannot convert item {} of list '{}': {}",
+                            index, name, err_str
+                        )))
+                        .with_key(name))
+                    }
+                }
+            }
+            new_db.list_map.insert(name.to_string(), new_list);
+        }
+
+        for (name, set) in self.set_map.iter() {
+            let mut new_set = HashSet::with_capacity(set.len());
+            for member in set.iter() {
+                match self.convert_value(member, &new_db.serializer) {
+                    Ok(new_member) => new_set.insert(new_member),
+                    Err(err_str) => {
+                        return Err(Error::new(ErrorCode::Serialization(format!(
+                            "Cannot convert a member of set '{}': {}",
+                            name, err_str
+                        )))
+                        .with_key(name))
+                    }
+                };
+            }
+            new_db.set_map.insert(name.to_string(), new_set);
+        }
+
+        for (name, hash) in self.hash_map.iter() {
+            let mut new_hash = HashMap::with_capacity(hash.len());
+            for (field, value) in hash.iter() {
+                match self.convert_value(value, &new_db.serializer) {
+                    Ok(new_value) => new_hash.insert(field.to_string(), new_value),
+                    Err(err_str) => {
+                        return Err(Error::new(ErrorCode::Serialization(format!(
+                            "Cannot convert field '{}' of hash '{}': {}",
+                            field, name, err_str
+                        )))
+                        .with_key(name))
+                    }
+                };
+            }
+            new_db.hash_map.insert(name.to_string(), new_hash);
+        }
+        new_db.used_bytes = new_db.count_used_bytes();
+
+        new_db.write_to_file(&new_db.db_file_path)?;
+        new_db.dirty = false;
+        new_db.dump_policy = self.dump_policy;
+        Ok(new_db)
+    }
+
+    fn convert_value(
+        &self,
+        value: &[u8],
+        new_serializer: &Serializer,
+    ) -> std::result::Result<Vec<u8>, String> {
+        let generic_value = self
+            .serializer
+            .deserialize_data_result::<GenericValue>(value)?;
+        let new_value = new_serializer.serialize_data(&generic_value)?;
+
+        // make sure the converted value is read back exactly the same
+        match new_serializer.deserialize_data_result::<GenericValue>(&new_value) {
+            Ok(read_value) if read_value == generic_value => Ok(new_value),
+            _ => Err(format!(
+                "the value can't be stored the same way using {} serialization",
+                new_serializer.ser_method()
+            )),
+        }
+    }
+
+    /// Get a view of the DB that only accesses keys starting with a certain prefix.
+    ///
+    /// This method returns a [PrefixedDb](struct.PrefixedDb.html) object which adds `prefix` to every
+    /// key given to its methods, and only returns keys starting with `prefix` (with the prefix removed)
+    /// when iterating over it. This enables different parts of an application to share a single DB
+    /// without their keys colliding.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - the prefix to add to the keys
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// // this value is stored under "users:john"
+    /// db.with_prefix("users:").set("john", &30).unwrap();
+    ///
+    /// assert_eq!(db.get::<i32>("users:john").unwrap(), 30);
+    /// ```
+    ///
+    pub fn with_prefix(&mut self, prefix: &str) -> PrefixedDb<'_> {
+        PrefixedDb {
+            db: self,
+            prefix: String::from(prefix),
+        }
+    }
+
+    /// Create a new list.
+    ///
+    /// This method just creates a new list, it doesn't add any elements to it.
+    /// If another list, value, set or hash is already set under this key, they will be overridden,
+    /// meaning the new list will override the old list, value, set or hash.
+    ///
+    /// Upon success, the method returns an object of type
+    /// [PickleDbListExtender](struct.PickleDbListExtender.html) that enables to add
+    /// items to the newly created list. Alternatively you can use [ladd()](#method.ladd)
+    /// or [lextend()](#method.lextend) to add items to the list.
+    ///
+    /// In case of a failure an
+    /// `Err(`[Error](error/struct.Error.html)`)` is returned. Failures
+    /// are not likely to happen but may occur mostly in cases where this action triggers a DB dump
+    /// (which is decided according to the dump policy)
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the key of the list that will be created
+    ///
+    pub fn lcreate(&mut self, name: &str) -> Result<PickleDbListExtender<'_>> {
+        let counted = self.key_footprint(name);
+        self.check_size_limit(counted, name.len())?;
+
+        let new_list: Vec<Vec<u8>> = Vec::new();
+        if self.map.contains_key(name) {
+            self.map.remove(name);
+            self.expiry_map.remove(name);
+        }
+        self.set_map.remove(name);
+        self.hash_map.remove(name);
+        self.list_map.insert(String::from(name), new_list);
+        self.recount_key(name, counted);
+        self.dumpdb_key(name)?;
+        Ok(PickleDbListExtender {
+            db: self,
+            list_name: String::from(name),
+        })
+    }
+
+    /// Check if a list exists.
+    ///
+    /// This method returns `true` if the list name exists and `false` otherwise.
+    /// The difference between this method and [exists()](#method.exists) is that this methods checks only
+    /// for lists with that name (key) and [exists()](#method.exists) checks for both values and lists.
+    /// To check only for key-value pairs use [vexists()](#method.vexists).
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the list key to check
+    ///
+    pub fn lexists(&self, name: &str) -> bool {
+        self.list_map.contains_key(name)
+    }
+
+    /// Add a single item to an existing list.
+    ///
+    /// As mentioned before, the lists are heterogeneous, meaning a single list can contain
+    /// items of different types. That means that the item can be of any type that is serializable.
+    /// That includes all primitive types, vectors, tuples and every struct that has the
+    /// `#[derive(Serialize, Deserialize)` attribute.
+    ///
+    /// If the item was added successfully the method returns
+    /// `Some(`[PickleDbListExtender](struct.PickleDbListExtender.html)`)` which enables to add more
+    /// items to the list. Alternatively the method returns `None` if the list isn't found in the DB
+    /// or if a failure happened while extending the list. Failures are not likely to happen but may
+    /// occur mostly in cases where this action triggers a DB dump (which is decided according to the dump policy)
+    /// or when the DB reached its size limit. Use [try_ladd()](#method.try_ladd) and
+    /// [try_lextend()](#method.try_lextend) to get the reason of the failure
+    ///
+    /// # Panics
+    ///
+    /// Panics if the item can't be serialized. The panic message includes the list name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the list key
+    /// * `value` - a reference of the item to add to the list
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// // create a new list
+    /// db.lcreate("list1");
+    ///
+    /// // add items of different types to the list
+    /// db.ladd("list1", &100).unwrap()
+    ///   .ladd(&String::from("my string"))
+    ///   .ladd(&vec!["aa", "bb", "cc"]);
+    /// ```
+    ///
+    pub fn ladd<V>(&mut self, name: &str, value: &V) -> Option<PickleDbListExtender<'_>>
+    where
+        V: Serialize,
+    {
+        self.lextend(name, &[value])
+    }
+
+    /// Add a single tagged item to an existing list.
+    ///
+    /// This method is similar to [ladd()](#method.ladd) but the item is stored as a
+    /// [PickleValue](enum.PickleValue.html), which keeps its variant. This way items of mixed types can be
+    /// read back using [lget_value()](#method.lget_value) and matched on, without knowing their types in advance.
+    ///
+    /// If the item was added successfully the method returns
+    /// `Some(`[PickleDbListExtender](struct.PickleDbListExtender.html)`)` which enables to add more
+    /// items to the list. Alternatively the method returns `None` if the list isn't found in the DB
+    /// or if a failure happened while extending the list.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the list key
+    /// * `value` - a reference of the item to add to the list
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use pickledb::PickleValue;
+    ///
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// db.lcreate("list1");
+    ///
+    /// // add tagged items of different types to the list
+    /// db.ladd_value("list1", &PickleValue::from(100)).unwrap()
+    ///   .ladd_value(&PickleValue::from("my string"));
+    /// ```
+    ///
+    pub fn ladd_value(
+        &mut self,
+        name: &str,
+        value: &PickleValue,
+    ) -> Option<PickleDbListExtender<'_>> {
+        self.ladd(name, value)
+    }
+
+    /// Add a single item to an existing list, and return an error if it can't be added.
+    ///
+    /// This method is similar to [ladd()](#method.ladd) but returns a `Result` instead of an `Option`,
+    /// so a list that doesn't exist can be told apart from a DB that reached its size limit.
+    /// Please see [try_lextend()](#method.try_lextend) for the errors that may be returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the list key
+    /// * `value` - a reference of the item to add to the list
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// db.lcreate("list1").unwrap();
+    ///
+    /// if let Err(err) = db.try_ladd("list1", &100) {
+    ///     println!("Failed to add the item: {}", err);
+    /// }
+    /// ```
+    ///
+    pub fn try_ladd<V>(&mut self, name: &str, value: &V) -> Result<PickleDbListExtender<'_>>
+    where
+        V: Serialize,
+    {
+        self.try_lextend(name, &[value])
+    }
+
+    /// Add a single item to an existing list only if the list doesn't already contain it.
+    ///
+    /// This method serializes the item once and compares it with the items stored in the list, the same way
+    /// [lcontains()](#method.lcontains) does. If none of them is equal to it, the item is added to the end of the
+    /// list like in [ladd()](#method.ladd) and a DB dump (which is decided according to the dump policy) is
+    /// triggered. Otherwise nothing is changed. This gives lists lightweight set semantics, with the same caveat
+    /// about types whose serialization isn't deterministic that's mentioned in [lcontains()](#method.lcontains).
+    ///
+    /// This method returns `Ok(true)` if the item was added, `Ok(false)` if the list already contains it, or an
+    /// `Err(`[Error](error/struct.Error.html)`)` if the list doesn't exist (of type
+    /// [ErrorType::ListNotFound](error/enum.ErrorType.html#variant.ListNotFound)), if serialization fails or if
+    /// the dump fails, in which case the list is left unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the list key
+    /// * `value` - a reference of the item to add to the list
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// db.lcreate("tags").unwrap().lextend(&vec!["red", "green"]);
+    ///
+    /// // the list already contains this item, so it isn't added
+    /// assert!(!db.ladd_unique("tags", &"red").unwrap());
+    ///
+    /// // now the list looks like this: ["red", "green", "blue"]
+    /// assert!(db.ladd_unique("tags", &"blue").unwrap());
+    /// ```
+    ///
+    pub fn ladd_unique<V>(&mut self, name: &str, value: &V) -> Result<bool>
+    where
+        V: Serialize,
+    {
+        let serialized_value = match self.serializer.serialize_data(value) {
+            Ok(val) => val,
+            Err(err_str) => return Err(PickleDb::serialization_error(name, err_str)),
+        };
+
+        match self.list_map.get(name) {
+            Some(list) if list.contains(&serialized_value) => return Ok(false),
+            Some(_) => (),
+            None => return Err(Error::new(ErrorCode::ListNotFound(String::from(name)))),
+        }
+
+        let added_size = serialized_value.len();
+        self.check_size_limit(0, added_size)?;
+        self.list_map.get_mut(name).unwrap().push(serialized_value);
+        self.used_bytes += added_size;
+        match self.dumpdb_key(name) {
+            Ok(_) => Ok(true),
+            Err(err) => {
+                self.list_map.get_mut(name).unwrap().pop();
+                self.used_bytes -= added_size;
+                Err(err)
+            }
+        }
+    }
+
+    /// Add multiple items to an existing list.
+    ///
+    /// As mentioned before, the lists are heterogeneous, meaning a single list can contain
+    /// items of different types. That means that the item can be of any type that is serializable.
+    /// That includes all primitive types, vectors, tuples and every struct that has the
+    /// `#[derive(Serialize, Deserialize)` attribute.
+    /// This method adds multiple items to the list, but since they're in a vector that means all
+    /// of them are of the same type. Of course it doesn't mean that the list cannot contain items
+    /// of other types as well, as you can see in the example below.
+    ///
+    /// If all items were added successfully the method returns
+    /// `Some(`[PickleDbListExtender](struct.PickleDbListExtender.html)`)` which enables to add more
+    /// items to the list. Alternatively the method returns `None` if the list isn't found in the DB
+    /// or if a failure happened while extending the list. Failures are not likely to happen but may
+    /// occur mostly in cases where this action triggers a DB dump (which is decided according to the dump policy)
+    /// or when the DB reached its size limit. Use [try_ladd()](#method.try_ladd) and
+    /// [try_lextend()](#method.try_lextend) to get the reason of the failure
+    ///
+    /// # Panics
+    ///
+    /// Panics if one of the items can't be serialized. The panic message includes the list name and the
+    /// position of the item in `seq`.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the list key
+    /// * `seq` - an iterator containing references to the new items to add to the list
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// // create a new list
+    /// db.lcreate("list1");
+    ///
+    /// // add a bunch of numbers to the list
+    /// db.lextend("list1", &vec![100, 200, 300]).unwrap()
+    ///
+    /// // add a String item to the list
+    ///   .ladd(&String::from("my string"))
+    ///
+    /// // add a vector item to the list
+    ///   .ladd(&vec!["aa", "bb", "cc"]);
+    ///
+    /// // now the list contains 5 items and looks like this: [100, 200, 300, "my string", ["aa, "bb", "cc"]]
+    /// ```
+    ///
+    pub fn lextend<'a, V, I>(&mut self, name: &str, seq: I) -> Option<PickleDbListExtender<'_>>
+    where
+        V: 'a + Serialize,
+        I: IntoIterator<Item = &'a V>,
+    {
+        if !self.list_map.contains_key(name) {
+            return None;
+        }
+
+        let serialized: Vec<Vec<u8>> = seq
+            .into_iter()
+            .enumerate()
+            .map(|(pos, x)| {
+                self.serializer.serialize_data(x).unwrap_or_else(|err_str| {
+                    panic!(
+                        "Cannot serialize item {} added to list '{}': {}",
+                        pos, name, err_str
+                    )
+                })
+            })
+            .collect();
+        self.lextend_serialized(name, serialized).ok()?;
+        Some(PickleDbListExtender {
+            db: self,
+            list_name: String::from(name),
+        })
+    }
+
+    /// Add multiple items to an existing list, and return an error if they can't be added.
+    ///
+    /// This method is similar to [lextend()](#method.lextend) but returns a `Result` instead of an `Option`,
+    /// so the reason of a failure can be told: an error of type
+    /// [ErrorType::ListNotFound](error/enum.ErrorType.html#variant.ListNotFound) if the list doesn't exist,
+    /// [ErrorType::SizeLimitExceeded](error/enum.ErrorType.html#variant.SizeLimitExceeded) if the items would
+    /// make the DB larger than its size limit (see [PickleDbBuilder::max_bytes()](struct.PickleDbBuilder.html#method.max_bytes)),
+    /// [ErrorType::Serialization](error/enum.ErrorType.html#variant.Serialization) if one of the items can't be
+    /// serialized, or an I/O error if the DB dump (which is decided according to the dump policy) fails.
+    /// In case of a failure none of the items are added.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the list key
+    /// * `seq` - an iterator containing references to the new items to add to the list
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use pickledb::error::ErrorType;
+    ///
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// match db.try_lextend("list1", &vec![100, 200, 300]) {
+    ///     Ok(_) => println!("items added"),
+    ///     Err(err) => match err.get_type() {
+    ///         ErrorType::ListNotFound => println!("list1 doesn't exist"),
+    ///         ErrorType::SizeLimitExceeded => println!("the DB is full"),
+    ///         _ => println!("failed to add items: {}", err),
+    ///     },
+    /// }
+    /// ```
+    ///
+    pub fn try_lextend<'a, V, I>(&mut self, name: &str, seq: I) -> Result<PickleDbListExtender<'_>>
+    where
+        V: 'a + Serialize,
+        I: IntoIterator<Item = &'a V>,
+    {
+        let mut serialized = Vec::new();
+        for value in seq {
+            match self.serializer.serialize_data(value) {
+                Ok(data) => serialized.push(data),
+                Err(err_str) => return Err(PickleDb::serialization_error(name, err_str)),
+            }
+        }
+        self.lextend_serialized(name, serialized)?;
+        Ok(PickleDbListExtender {
+            db: self,
+            list_name: String::from(name),
+        })
+    }
+
+    // add serialized items to the end of an existing list, the list is left unchanged if this fails
+    fn lextend_serialized(&mut self, name: &str, serialized: Vec<Vec<u8>>) -> Result<()> {
+        if !self.list_map.contains_key(name) {
+            return Err(Error::new(ErrorCode::ListNotFound(String::from(name))));
+        }
+
+        let added_size: usize = serialized.iter().map(Vec::len).sum();
+        self.check_size_limit(0, added_size)?;
+
+        let list = self.list_map.get_mut(name).unwrap();
+        let original_len = list.len();
+        list.extend(serialized);
+        self.used_bytes += added_size;
+        match self.dumpdb_key(name) {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                let same_list = self.list_map.get_mut(name).unwrap();
+                same_list.truncate(original_len);
+                self.used_bytes -= added_size;
+                Err(err)
+            }
+        }
+    }
+
+    /// Add a single item to the front of an existing list.
+    ///
+    /// This method is similar to [ladd()](#method.ladd) but the item is added before all the
+    /// other items in the list, meaning that after this call it will be in position 0.
+    ///
+    /// If the item was added successfully the method returns
+    /// `Some(`[PickleDbListExtender](struct.PickleDbListExtender.html)`)` which enables to add more
+    /// items to the list. Alternatively the method returns `None` if the list isn't found in the DB
+    /// or if a failure happened while extending the list. Failures are not likely to happen but may
+    /// occur mostly in cases where this action triggers a DB dump (which is decided according to the dump policy)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the item can't be serialized. The panic message includes the list name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the list key
+    /// * `value` - a reference of the item to add to the list
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// // create a new list
+    /// db.lcreate("list1").unwrap().lextend(&vec![2, 3]);
+    ///
+    /// // add an item to the front of the list
+    /// db.lpush_front("list1", &1);
+    ///
+    /// // now the list looks like this: [1, 2, 3]
+    /// ```
+    ///
+    pub fn lpush_front<V>(&mut self, name: &str, value: &V) -> Option<PickleDbListExtender<'_>>
+    where
+        V: Serialize,
+    {
+        self.lextend_front(name, &[value])
+    }
+
+    /// Add multiple items to the front of an existing list.
+    ///
+    /// This method is similar to [lextend()](#method.lextend) but the items are added before all the
+    /// other items in the list. The order of the added items is preserved, meaning that after this call
+    /// the first item of `seq` will be in position 0, the second item in position 1 and so on.
+    ///
+    /// If all items were added successfully the method returns
+    /// `Some(`[PickleDbListExtender](struct.PickleDbListExtender.html)`)` which enables to add more
+    /// items to the list. Alternatively the method returns `None` if the list isn't found in the DB
+    /// or if a failure happened while extending the list. Failures are not likely to happen but may
+    /// occur mostly in cases where this action triggers a DB dump (which is decided according to the dump policy)
+    ///
+    /// # Panics
+    ///
+    /// Panics if one of the items can't be serialized. The panic message includes the list name and the
+    /// position of the item in `seq`.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the list key
+    /// * `seq` - an iterator containing references to the new items to add to the list
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// // create a new list
+    /// db.lcreate("list1").unwrap().lextend(&vec![3, 4]);
+    ///
+    /// // add a bunch of numbers to the front of the list
+    /// db.lextend_front("list1", &vec![1, 2]);
+    ///
+    /// // now the list looks like this: [1, 2, 3, 4]
+    /// ```
+    ///
+    pub fn lextend_front<'a, V, I>(
+        &mut self,
+        name: &str,
+        seq: I,
+    ) -> Option<PickleDbListExtender<'_>>
+    where
+        V: 'a + Serialize,
+        I: IntoIterator<Item = &'a V>,
+    {
+        let room = self.size_limit_room();
+        let serializer = &self.serializer;
+        match self.list_map.get_mut(name) {
+            Some(list) => {
+                let serialized: Vec<Vec<u8>> = seq
+                    .into_iter()
+                    .enumerate()
+                    .map(|(pos, x)| {
+                        serializer.serialize_data(x).unwrap_or_else(|err_str| {
+                            panic!(
+                                "Cannot serialize item {} added to list '{}': {}",
+                                pos, name, err_str
+                            )
+                        })
+                    })
+                    .collect();
+                let added_size: usize = serialized.iter().map(Vec::len).sum();
+                if matches!(room, Some(room) if added_size > room) {
+                    return None;
+                }
+                let added_len = serialized.len();
+                list.splice(0..0, serialized);
+                self.used_bytes += added_size;
+                match self.dumpdb_key(name) {
+                    Ok(_) => (),
+                    Err(_) => {
+                        let same_list = self.list_map.get_mut(name).unwrap();
+                        same_list.drain(0..added_len);
+                        self.used_bytes -= added_size;
+                        return None;
+                    }
+                }
+                Some(PickleDbListExtender {
+                    db: self,
+                    list_name: String::from(name),
+                })
+            }
+
+            None => None,
+        }
+    }
+
+    /// Insert a single item to an existing list in a certain position.
+    ///
+    /// The item is inserted before the item currently in position `pos`, meaning that after
+    /// the insertion the new item will be in position `pos`. If `pos` equals the length of the
+    /// list the item is added to the end of the list, exactly like [ladd()](#method.ladd).
+    /// As mentioned before, the lists are heterogeneous, meaning a single list can contain
+    /// items of different types.
+    ///
+    /// If the item was inserted successfully the method returns
+    /// `Some(`[PickleDbListExtender](struct.PickleDbListExtender.html)`)` which enables to add more
+    /// items to the list. Alternatively the method returns `None` if the list isn't found in the DB,
+    /// if `pos` is greater than the length of the list or if a failure happened while inserting the item.
+    /// Failures are not likely to happen but may occur mostly in cases where this action triggers a DB dump
+    /// (which is decided according to the dump policy)
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the list key
+    /// * `pos` - the position to insert the item in
+    /// * `value` - a reference of the item to insert to the list
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// // create a new list
+    /// db.lcreate("list1").unwrap().lextend(&vec![1, 4]);
+    ///
+    /// // insert items in the middle of the list
+    /// db.linsert("list1", 1, &2).unwrap()
+    ///   .linsert(2, &3);
+    ///
+    /// // now the list looks like this: [1, 2, 3, 4]
+    /// ```
+    ///
+    pub fn linsert<V>(
+        &mut self,
+        name: &str,
+        pos: usize,
+        value: &V,
+    ) -> Option<PickleDbListExtender<'_>>
+    where
+        V: Serialize,
+    {
+        let room = self.size_limit_room();
+        match self.list_map.get_mut(name) {
+            Some(list) => {
+                if pos > list.len() {
+                    return None;
+                }
+
+                let ser_data = self.serializer.serialize_data(value).ok()?;
+                let added_size = ser_data.len();
+                if matches!(room, Some(room) if added_size > room) {
+                    return None;
+                }
+                list.insert(pos, ser_data);
+                self.used_bytes += added_size;
+                match self.dumpdb_key(name) {
+                    Ok(_) => (),
+                    Err(_) => {
+                        let same_list = self.list_map.get_mut(name).unwrap();
+                        same_list.remove(pos);
+                        self.used_bytes -= added_size;
+                        return None;
+                    }
+                }
+                Some(PickleDbListExtender {
+                    db: self,
+                    list_name: String::from(name),
+                })
+            }
+
+            None => None,
+        }
+    }
+
+    /// Get an item of of a certain list in a certain position.
+    ///
+    /// This method takes a list name and a position inside the list
+    /// and retrieves the item in this position. It's the user's responsibility
+    /// to know what is the correct type of the item and give it while calling this method.
+    /// Since the item in the lists are stored in a serialized way the returned object
+    /// is not a reference to the item stored in a DB but actually a new instance of it.
+    /// If the list is not found in the DB or the given position is out of bounds
+    /// of the list `None` will be returned. Otherwise `Some(V)` will be returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the list key
+    /// * `pos` - the position of the item inside the list. Expected value is >= 0
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// // create a list
+    /// db.lcreate("list1");
+    ///
+    /// // add a number to list1
+    /// db.ladd("list1", &100);
+    ///
+    /// // add a string to list1
+    /// db.ladd("list1", &String::from("my string"));
+    ///
+    /// // read the first item in the list - int
+    /// let x = db.lget::<i32>("list1", 0).unwrap();
+    ///
+    /// // read the second item in the list - string
+    /// let s = db.lget::<String>("list1", 1).unwrap();
+    /// ```
+    pub fn lget<V>(&self, name: &str, pos: usize) -> Option<V>
+    where
+        V: DeserializeOwned,
+    {
+        match self.list_map.get(name) {
+            Some(list) => match list.get(pos) {
+                Some(val) => self.serializer.deserialize_data::<V>(val),
+                None => None,
+            },
+            None => None,
+        }
+    }
+
+    /// Get a tagged item of a certain list in a certain position.
+    ///
+    /// This method is similar to [lget()](#method.lget) but reads an item that was stored as a
+    /// [PickleValue](enum.PickleValue.html), for example using [ladd_value()](#method.ladd_value),
+    /// so the type of the item doesn't need to be known in advance.
+    /// If the list is not found in the DB, the given position is out of bounds of the list or the item
+    /// wasn't stored as a `PickleValue`, `None` will be returned. Otherwise `Some(PickleValue)` will be returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the list key
+    /// * `pos` - the position of the item inside the list. Expected value is >= 0
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use pickledb::PickleValue;
+    ///
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// db.lcreate("list1").unwrap().ladd_value(&PickleValue::from(1.5));
+    ///
+    /// match db.lget_value("list1", 0) {
+    ///     Some(PickleValue::Int(num)) => println!("An integer: {}", num),
+    ///     Some(PickleValue::Float(num)) => println!("A floating point number: {}", num),
+    ///     _ => println!("Something else"),
+    /// }
+    /// ```
+    pub fn lget_value(&self, name: &str, pos: usize) -> Option<PickleValue> {
+        self.lget::<PickleValue>(name, pos)
+    }
+
+    /// Get a range of items of a certain list.
+    ///
+    /// This method takes a list name and a range of positions inside the list and retrieves
+    /// the items in positions `start` (inclusive) to `end` (exclusive), similar to Redis' `LRANGE`.
+    /// It's the user's responsibility to know what is the correct type of the items and give it while
+    /// calling this method.
+    ///
+    /// If `end` is out of bounds of the list it's clamped to the list length, and if `start` is not
+    /// lower than `end` an empty `Vec` is returned. If the list is not found in the DB or one of the items
+    /// in the range can't be deserialized into `V`, `None` is returned. Otherwise `Some(Vec<V>)`
+    /// will be returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the list key
+    /// * `start` - the position of the first item to retrieve
+    /// * `end` - the position after the last item to retrieve
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// // create a list
+    /// db.lcreate("list1").unwrap().lextend(&(0..100).collect::<Vec<i32>>());
+    ///
+    /// // read items 20..40 of the list
+    /// let page = db.lget_range::<i32>("list1", 20, 40).unwrap();
+    /// assert_eq!(page.len(), 20);
+    /// ```
+    pub fn lget_range<V>(&self, name: &str, start: usize, end: usize) -> Option<Vec<V>>
+    where
+        V: DeserializeOwned,
+    {
+        let list = self.list_map.get(name)?;
+        let end = end.min(list.len());
+        if start >= end {
+            return Some(Vec::new());
+        }
+
+        list[start..end]
+            .iter()
+            .map(|item| self.serializer.deserialize_data::<V>(item))
+            .collect()
+    }
+
+    /// Replace an item of a certain list in a certain position.
+    ///
+    /// This method takes a list name, a position inside the list and a value, and replaces
+    /// the item in this position with the new value. As mentioned before, the lists are heterogeneous,
+    /// so the new value doesn't have to be of the same type as the item it replaces.
+    ///
+    /// If the list is not found in the DB or the given position is out of bounds of the list
+    /// nothing is changed and `Ok(false)` is returned. If the item was replaced `Ok(true)` is returned.
+    /// In case of a failure an `Err(`[Error](error/struct.Error.html)`)` is returned and the original item
+    /// is restored. Failures are not likely to happen but may occur mostly in cases where this action
+    /// triggers a DB dump (which is decided according to the dump policy)
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the list key
+    /// * `pos` - the position of the item to replace
+    /// * `value` - a reference of the new item
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// // create a list
+    /// db.lcreate("list1").unwrap().lextend(&vec![1,2,3]);
+    ///
+    /// // replace the item in position 1
+    /// db.lset("list1", 1, &String::from("two")).unwrap();
+    ///
+    /// // the list now looks like this: [1, "two", 3]
+    /// ```
+    ///
+    pub fn lset<V>(&mut self, name: &str, pos: usize, value: &V) -> Result<bool>
+    where
+        V: Serialize,
+    {
+        let removed_size = match self.list_map.get(name) {
+            Some(list) if pos < list.len() => list[pos].len(),
+            _ => return Ok(false),
+        };
+
+        let ser_data = match self.serializer.serialize_data(value) {
+            Ok(data) => data,
+            Err(err_str) => return Err(PickleDb::serialization_error(name, err_str)),
+        };
+        let added_size = ser_data.len();
+        self.check_size_limit(removed_size, added_size)?;
+
+        let list = self.list_map.get_mut(name).unwrap();
+        let orig_value = std::mem::replace(&mut list[pos], ser_data);
+        self.used_bytes = self.used_bytes - removed_size + added_size;
+        match self.dumpdb_key(name) {
+            Ok(_) => Ok(true),
+            Err(err) => {
+                let same_list = self.list_map.get_mut(name).unwrap();
+                same_list[pos] = orig_value;
+                self.used_bytes = self.used_bytes - added_size + removed_size;
+                Err(err)
+            }
+        }
+    }
+
+    /// Check if a list contains a certain value.
+    ///
+    /// This method serializes the value and compares it with the serialized items stored in the list,
+    /// the same way [lrem_value()](#method.lrem_value) does. It returns `true` if the value is found and
+    /// `false` if it isn't found, if the list doesn't exist or if the value can't be serialized.
+    ///
+    /// Please note that since the comparison is done on the serialized bytes, it's reliable only when
+    /// serializing the same value always produces the same bytes. This is the case for primitive types,
+    /// strings, vectors, tuples and structs in all serialization methods, but not for types whose
+    /// serialized order isn't deterministic, such as a `HashMap` (which may be serialized with its entries
+    /// in a different order each time).
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the list key
+    /// * `value` - a reference of the value to look for
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// db.lcreate("list1").unwrap().lextend(&vec![1, 2, 3]);
+    ///
+    /// // add the value only if it's not already in the list
+    /// if !db.lcontains("list1", &4) {
+    ///     db.ladd("list1", &4);
+    /// }
+    /// ```
+    ///
+    pub fn lcontains<V>(&self, name: &str, value: &V) -> bool
+    where
+        V: Serialize,
+    {
+        match self.list_map.get(name) {
+            Some(list) => match self.serializer.serialize_data(value) {
+                Ok(serialized_value) => list.contains(&serialized_value),
+                Err(_) => false,
+            },
+            None => false,
+        }
+    }
+
+    /// Get the position of a value in a list.
+    ///
+    /// This method serializes the value and looks for the first item in the list that is equal to it,
+    /// the same way [lrem_value()](#method.lrem_value) does but without removing it. The returned position
+    /// can be used later in methods such as [lset()](#method.lset) or [lpop()](#method.lpop).
+    ///
+    /// The method returns `Some(usize)` with the position of the first matching item, or `None` if the value
+    /// isn't found, if the list doesn't exist or if the value can't be serialized. Please note the same
+    /// caveat about comparing serialized values that is described in [lcontains()](#method.lcontains).
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the list key
+    /// * `value` - a reference of the value to look for
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// db.lcreate("list1").unwrap().lextend(&vec![1, 2, 3]);
+    ///
+    /// // find the position of 2 and replace it
+    /// if let Some(pos) = db.lindex_of("list1", &2) {
+    ///     db.lset("list1", pos, &20).unwrap();
+    /// }
+    /// ```
+    ///
+    pub fn lindex_of<V>(&self, name: &str, value: &V) -> Option<usize>
+    where
+        V: Serialize,
+    {
+        let list = self.list_map.get(name)?;
+        let serialized_value = self.serializer.serialize_data(value).ok()?;
+        list.iter().position(|x| *x == serialized_value)
+    }
+
+    /// Get the length of a list.
+    ///
+    /// If the list is empty or if it doesn't exist the value of 0 is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the list key
+    ///
+    pub fn llen(&self, name: &str) -> usize {
+        match self.list_map.get(name) {
+            Some(list) => list.len(),
+            None => 0,
+        }
+    }
+
+    /// Get the total size in bytes of the items of a list as they're stored in the DB.
+    ///
+    /// This is the sum of the lengths of the serialized items, so it depends on the serialization method
+    /// of the DB. If the list doesn't exist `None` is returned, and if it's empty the value of 0 is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the list key
+    ///
+    pub fn list_bytes_len(&self, name: &str) -> Option<usize> {
+        self.list_map
+            .get(name)
+            .map(|list| list.iter().map(|item| item.len()).sum())
+    }
+
+    /// Check if a list is empty.
+    ///
+    /// This method returns `true` if the list doesn't contain any items or if it doesn't exist,
+    /// and `false` otherwise. This is the same as checking if [llen()](#method.llen) is 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the list key
+    ///
+    pub fn lis_empty(&self, name: &str) -> bool {
+        self.llen(name) == 0
+    }
+
+    /// Remove a list.
+    ///
+    /// This method is somewhat similar to [rem()](#method.rem) but with 2 small differences:
+    /// * This method only removes lists and not key-value pairs
+    /// * The return value of this method is the number of items that were in
+    ///   the list that was removed. If the list doesn't exist a value of zero (0) is
+    ///   returned. In case of a failure an `Err(`[Error](error/struct.Error.html)`)` is returned.
+    ///   Failures are not likely to happen but may occur mostly in cases where this action triggers a
+    ///   DB dump (which is decided according to the dump policy)
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the list key to remove
+    ///
+    pub fn lrem_list(&mut self, name: &str) -> Result<usize> {
+        let res = self.llen(name);
+        let counted = self.key_footprint(name);
+        match self.list_map.remove(name) {
+            Some(list) => {
+                self.used_bytes -= counted;
+                match self.dumpdb_key(name) {
+                    Ok(_) => Ok(res),
+                    Err(err) => {
+                        self.list_map.insert(String::from(name), list);
+                        self.used_bytes += counted;
+                        Err(err)
+                    }
+                }
+            }
+            None => Ok(res),
+        }
+    }
+
+    /// Remove all items from a list.
+    ///
+    /// Unlike [lrem_list()](#method.lrem_list) this method doesn't remove the list itself, only
+    /// its items, so the list still exists after calling it.
+    ///
+    /// The return value of this method is the number of items that were in the list before it was cleared.
+    /// If the list doesn't exist a value of zero (0) is returned. In case of a failure an
+    /// `Err(`[Error](error/struct.Error.html)`)` is returned and the items are restored.
+    /// Failures are not likely to happen but may occur mostly in cases where this action triggers a
+    /// DB dump (which is decided according to the dump policy)
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the list key to clear
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// db.lcreate("list1").unwrap().lextend(&vec![1, 2, 3]);
+    ///
+    /// // the list is now empty but it still exists
+    /// assert_eq!(db.lclear("list1").unwrap(), 3);
+    /// assert!(db.lexists("list1"));
+    /// ```
+    ///
+    pub fn lclear(&mut self, name: &str) -> Result<usize> {
+        match self.list_map.get_mut(name) {
+            Some(list) if !list.is_empty() => {
+                let orig_list = std::mem::take(list);
+                let removed_size: usize = orig_list.iter().map(Vec::len).sum();
+                self.used_bytes -= removed_size;
+                match self.dumpdb_key(name) {
+                    Ok(_) => Ok(orig_list.len()),
+                    Err(err) => {
+                        self.list_map.insert(String::from(name), orig_list);
+                        self.used_bytes += removed_size;
+                        Err(err)
+                    }
+                }
+            }
+
+            _ => Ok(0),
+        }
+    }
+
+    /// Pop an item out of a list.
+    ///
+    /// This method takes a list name and a position inside the list, removes the
+    /// item in this position and returns it to the user. It's the user's responsibility
+    /// to know what is the correct type of the item and give it while calling this method.
+    /// Since the item in the lists are stored in a serialized way the returned object
+    /// is not a reference to the item stored in a DB but actually a new instance of it.
+    ///
+    /// If the list is not found in the DB or the given position is out of bounds no item
+    /// will be removed and `None` will be returned. `None` may also be returned
+    /// if removing the item fails, which may happen mostly in cases where this action
+    /// triggers a DB dump (which is decided according to the dump policy).
+    /// Otherwise the item will be removed and `Some(V)` will be returned.
+    ///
+    /// This method is very similar to [lrem_value()](#method.lrem_value), the only difference is that this
+    /// methods returns the value and [lrem_value()](#method.lrem_value) returns only an indication whether
+    /// the item was removed or not.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the list key
+    /// * `pos` - the position of the item to remove
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// // create a list
+    /// db.lcreate("list1");
+    ///
+    /// // add 4 items to the list
+    /// db.lextend("list1", &vec![1,2,3,4]);
+    ///
+    /// // remove item in position 2
+    /// let item2 = db.lpop::<i32>("list1", 2);
+    ///
+    /// // item2 contains 3 and the list now looks like this: [1, 2, 4]
+    ///
+    /// // remove item in position 1
+    /// let item1 = db.lpop::<i32>("list1", 1);
+    ///
+    /// // item1 contains 2 and the list now looks like this: [1, 4]
+    /// ```
+    ///
+    pub fn lpop<V>(&mut self, name: &str, pos: usize) -> Option<V>
+    where
+        V: DeserializeOwned,
+    {
+        match self.list_map.get_mut(name) {
+            Some(list) => {
+                if pos < list.len() {
+                    let res = list.remove(pos);
+                    self.used_bytes -= res.len();
+                    match self.dumpdb_key(name) {
+                        Ok(_) => self.serializer.deserialize_data::<V>(&res),
+                        Err(_) => {
+                            self.used_bytes += res.len();
+                            let same_list = self.list_map.get_mut(name).unwrap();
+                            same_list.insert(pos, res);
+                            None
+                        }
+                    }
+                } else {
+                    None
+                }
+            }
+
+            None => None,
+        }
+    }
+
+    /// Pop the first item out of a list.
+    ///
+    /// This method is the same as calling [lpop()](#method.lpop) with position 0, which is useful
+    /// for using a list as a queue.
+    ///
+    /// If the list is not found in the DB or it's empty `None` will be returned. `None` may also be
+    /// returned if removing the item fails, which may happen mostly in cases where this action
+    /// triggers a DB dump (which is decided according to the dump policy).
+    /// Otherwise the item will be removed and `Some(V)` will be returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the list key
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// db.lcreate("list1").unwrap().lextend(&vec![1, 2, 3]);
+    ///
+    /// // first contains 1 and the list now looks like this: [2, 3]
+    /// let first = db.lpop_front::<i32>("list1").unwrap();
+    /// ```
+    ///
+    pub fn lpop_front<V>(&mut self, name: &str) -> Option<V>
+    where
+        V: DeserializeOwned,
+    {
+        self.lpop(name, 0)
+    }
+
+    /// Pop several items out of the beginning of a list.
+    ///
+    /// This method removes up to `n` items from the beginning of the list and returns them in their
+    /// order in the list, which is useful for processing a list that is used as a queue in batches.
+    /// Unlike calling [lpop_front()](#method.lpop_front) `n` times, the DB is dumped only once.
+    ///
+    /// If the list has fewer than `n` items all of them are removed and returned, and if the list is not
+    /// found in the DB an empty vector is returned. An empty vector is also returned if removing the items
+    /// fails, which may happen mostly in cases where this action triggers a DB dump (which is decided
+    /// according to the dump policy), in which case the list is left unchanged. Items that can't be
+    /// deserialized into `V` are removed but aren't returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the list key
+    /// * `n` - the maximal number of items to remove
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// db.lcreate("list1").unwrap().lextend(&vec![1, 2, 3, 4, 5]);
+    ///
+    /// // batch contains [1, 2, 3] and the list now looks like this: [4, 5]
+    /// let batch = db.lpop_front_n::<i32>("list1", 3);
+    /// ```
+    ///
+    pub fn lpop_front_n<V>(&mut self, name: &str, n: usize) -> Vec<V>
+    where
+        V: DeserializeOwned,
+    {
+        let items = match self.list_map.get_mut(name) {
+            Some(list) => {
+                let count = n.min(list.len());
+                if count == 0 {
+                    return Vec::new();
+                }
+                list.drain(..count).collect::<Vec<_>>()
+            }
+            None => return Vec::new(),
+        };
+        let removed_size: usize = items.iter().map(Vec::len).sum();
+        self.used_bytes -= removed_size;
+
+        match self.dumpdb_key(name) {
+            Ok(_) => items
+                .iter()
+                .filter_map(|item| self.serializer.deserialize_data::<V>(item))
+                .collect(),
+            Err(_) => {
+                self.used_bytes += removed_size;
+                let same_list = self.list_map.get_mut(name).unwrap();
+                same_list.splice(0..0, items);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Pop the last item out of a list.
+    ///
+    /// This method is the same as calling [lpop()](#method.lpop) with the position of the last item
+    /// in the list, which is useful for using a list as a stack.
+    ///
+    /// If the list is not found in the DB or it's empty `None` will be returned. `None` may also be
+    /// returned if removing the item fails, which may happen mostly in cases where this action
+    /// triggers a DB dump (which is decided according to the dump policy).
+    /// Otherwise the item will be removed and `Some(V)` will be returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the list key
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// db.lcreate("list1").unwrap().lextend(&vec![1, 2, 3]);
+    ///
+    /// // last contains 3 and the list now looks like this: [1, 2]
+    /// let last = db.lpop_back::<i32>("list1").unwrap();
+    /// ```
+    ///
+    pub fn lpop_back<V>(&mut self, name: &str) -> Option<V>
+    where
+        V: DeserializeOwned,
+    {
+        match self.llen(name) {
+            0 => None,
+            len => self.lpop(name, len - 1),
+        }
+    }
+
+    /// Pop an item out of a list, where negative positions count from the end of the list.
+    ///
+    /// This method is similar to [lpop()](#method.lpop) but also accepts negative positions like Redis
+    /// does: -1 is the last item, -2 is the one before it and so on, and `-len` is the first item.
+    /// Non-negative positions are the same as in [lpop()](#method.lpop).
+    ///
+    /// If the list is not found in the DB or the given position is out of bounds (lower than `-len` or
+    /// not lower than `len`) no item will be removed and `None` will be returned. `None` may also be
+    /// returned if removing the item fails, which may happen mostly in cases where this action
+    /// triggers a DB dump (which is decided according to the dump policy).
+    /// Otherwise the item will be removed and `Some(V)` will be returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the list key
+    /// * `pos` - the position of the item to remove, negative positions count from the end of the list
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// db.lcreate("list1").unwrap().lextend(&vec![1, 2, 3, 4]);
+    ///
+    /// // item contains 3 and the list now looks like this: [1, 2, 4]
+    /// let item = db.lpop_at::<i32>("list1", -2).unwrap();
+    /// ```
+    ///
+    pub fn lpop_at<V>(&mut self, name: &str, pos: isize) -> Option<V>
+    where
+        V: DeserializeOwned,
+    {
+        let pos = if pos < 0 {
+            self.llen(name).checked_sub(pos.unsigned_abs())?
+        } else {
+            pos as usize
+        };
+        self.lpop(name, pos)
+    }
+
+    /// Remove an item out of a list.
+    ///
+    /// This method takes a list name and a reference to a value, removes the first instance of the
+    /// value if it exists in the list, and returns an indication whether the item was removed or not.
+    ///
+    /// If the list is not found in the DB or the given value isn't found in the list, no item will
+    /// be removed and `Ok(false)` will be returned.
+    /// If removing the item fails, which may happen mostly in cases where this action triggers
+    /// a DB dump (which is decided according to the dump policy), an
+    /// `Err(`[Error](error/struct.Error.html)`)` is returned.
+    /// Otherwise the item will be removed and `Ok(true)` will be returned.
+    ///
+    /// This method is very similar to [lpop()](#method.lpop), the only difference is that this
+    /// methods returns an indication and [lpop()](#method.lpop) returns the actual item that was removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the list key
+    /// * `value` - the item to remove
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// // create a list
+    /// db.lcreate("list1");
+    ///
+    /// // add 4 items to the list
+    /// db.lextend("list1", &vec![1,2,3,4]);
+    ///
+    /// // remove 2
+    /// db.lrem_value("list1", &2).unwrap();
+    ///
+    /// // The list now looks like this: [1, 3, 4]
+    ///
+    /// // remove 3
+    /// db.lrem_value("list1", &3).unwrap();
+    ///
+    /// // The list now looks like this: [1, 4]
+    /// ```
+    ///
+    pub fn lrem_value<V>(&mut self, name: &str, value: &V) -> Result<bool>
+    where
+        V: Serialize,
+    {
+        match self.list_map.get_mut(name) {
+            Some(list) => {
+                let serialized_value = match self.serializer.serialize_data(&value) {
+                    Ok(val) => val,
+                    Err(err_str) => return Err(PickleDb::serialization_error(name, err_str)),
+                };
+
+                match list.iter().position(|x| *x == serialized_value) {
+                    Some(pos) => {
+                        list.remove(pos);
+                        self.used_bytes -= serialized_value.len();
+                        match self.dumpdb_key(name) {
+                            Ok(_) => Ok(true),
+                            Err(err) => {
+                                self.used_bytes += serialized_value.len();
+                                let same_list = self.list_map.get_mut(name).unwrap();
+                                same_list.insert(pos, serialized_value);
+                                Err(err)
+                            }
+                        }
+                    }
+
+                    None => Ok(false),
+                }
+            }
+
+            None => Ok(false),
+        }
+    }
+
+    /// Sort the items of a list in place.
+    ///
+    /// This method deserializes all the items in the list to `V` and sorts the list in ascending order
+    /// according to their values. The sort is stable, meaning equal items keep their original order.
+    /// The stored items themselves aren't changed, only their order, and a single DB dump (which is decided
+    /// according to the dump policy) is triggered after sorting.
+    ///
+    /// This method returns `Ok(true)` if the list was found and sorted or `Ok(false)` if the list wasn't found.
+    /// It returns an `Err(`[Error](error/struct.Error.html)`)` if one of the items can't be deserialized to `V`
+    /// or if the dump fails, in which case the list is left unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the list key
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// db.lcreate("list1").unwrap().lextend(&vec![3, 1, 2]);
+    ///
+    /// // now the list looks like this: [1, 2, 3]
+    /// db.lsort::<i32>("list1").unwrap();
+    /// ```
+    ///
+    pub fn lsort<V>(&mut self, name: &str) -> Result<bool>
+    where
+        V: DeserializeOwned + Ord,
+    {
+        self.lsort_by(name, |a: &V, b: &V| a.cmp(b))
+    }
+
+    /// Sort the items of a list in place using a comparator function.
+    ///
+    /// This method is similar to [lsort()](#method.lsort) but the order is determined by `compare`,
+    /// which is called with the deserialized values of the items. This enables sorting in descending
+    /// order, sorting by a field of a struct or sorting values that don't implement `Ord`, such as floating
+    /// point numbers.
+    ///
+    /// This method returns `Ok(true)` if the list was found and sorted or `Ok(false)` if the list wasn't found.
+    /// It returns an `Err(`[Error](error/struct.Error.html)`)` if one of the items can't be deserialized to `V`
+    /// or if the dump fails, in which case the list is left unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the list key
+    /// * `compare` - a function that compares two deserialized items
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// db.lcreate("scores").unwrap().lextend(&vec![2.5, 9.1, 4.7]);
+    ///
+    /// // sort in descending order, now the list looks like this: [9.1, 4.7, 2.5]
+    /// db.lsort_by("scores", |a: &f64, b: &f64| b.partial_cmp(a).unwrap()).unwrap();
+    /// ```
+    ///
+    pub fn lsort_by<V, F>(&mut self, name: &str, mut compare: F) -> Result<bool>
+    where
+        V: DeserializeOwned,
+        F: FnMut(&V, &V) -> Ordering,
+    {
+        let list = match self.list_map.get(name) {
+            Some(list) => list,
+            None => return Ok(false),
+        };
+
+        let mut items = Vec::with_capacity(list.len());
+        for item in list.iter() {
+            match self.serializer.deserialize_data_result::<V>(item) {
+                Ok(value) => items.push((value, item.to_vec())),
+                Err(err_str) => return Err(PickleDb::deserialization_error(name, err_str)),
+            }
+        }
+
+        items.sort_by(|a, b| compare(&a.0, &b.0));
+        let sorted_list = items.into_iter().map(|(_, item)| item).collect();
+
+        let orig_list = self.list_map.insert(String::from(name), sorted_list);
+        match self.dumpdb_key(name) {
+            Ok(_) => Ok(true),
+            Err(err) => {
+                if let Some(orig_list) = orig_list {
+                    self.list_map.insert(String::from(name), orig_list);
+                }
+                Err(err)
+            }
         }
-        self.list_map.insert(String::from(name), new_list);
-        self.dumpdb()?;
-        Ok(PickleDbListExtender {
-            db: self,
-            list_name: String::from(name),
-        })
     }
 
-    /// Check if a list exists.
+    /// Transform all the items of a list in place.
     ///
-    /// This method returns `true` if the list name exists and `false` otherwise.
-    /// The difference between this method and [exists()](#method.exists) is that this methods checks only
-    /// for lists with that name (key) and [exists()](#method.exists) checks for both values and lists.
+    /// This method deserializes each item in the list to `V`, passes it to `f` and stores the value `f` returns
+    /// instead of it, keeping the order of the items. A single DB dump (which is decided according to the dump
+    /// policy) is triggered after all the items are transformed.
+    ///
+    /// The return value of this method is the number of items that were transformed. If the list doesn't exist
+    /// a value of zero (0) is returned. An `Err(`[Error](error/struct.Error.html)`)` is returned if one of the
+    /// items can't be deserialized to `V` (the error message contains its index), if a new item can't be
+    /// serialized or if the dump fails, in which case the list is left unchanged.
     ///
     /// # Arguments
     ///
-    /// * `name` - the list key to check
+    /// * `name` - the list key
+    /// * `f` - a function that takes an item and returns its new value
     ///
-    pub fn lexists(&self, name: &str) -> bool {
-        self.list_map.get(name).is_some()
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// db.lcreate("list1").unwrap().lextend(&vec![1, 2, 3]);
+    ///
+    /// // now the list looks like this: [10, 20, 30]
+    /// assert_eq!(db.lmap("list1", |x: i32| x * 10).unwrap(), 3);
+    /// ```
+    ///
+    pub fn lmap<V, F>(&mut self, name: &str, mut f: F) -> Result<usize>
+    where
+        V: Serialize + DeserializeOwned,
+        F: FnMut(V) -> V,
+    {
+        let list = match self.list_map.get(name) {
+            Some(list) if !list.is_empty() => list,
+            _ => return Ok(0),
+        };
+
+        let mut new_list = Vec::with_capacity(list.len());
+        for (index, item) in list.iter().enumerate() {
+            let value = match self.serializer.deserialize_data_result::<V>(item) {
+                Ok(value) => value,
+                Err(err_str) => {
+                    return Err(Error::new(ErrorCode::Serialization(format!(
+                        "Cannot deserialize item {} of list '{}': {}",
+                        index, name, err_str
+                    )))
+                    .with_key(name))
+                }
+            };
+            match self.serializer.serialize_data(&f(value)) {
+                Ok(new_item) => new_list.push(new_item),
+                Err(err_str) => return Err(PickleDb::serialization_error(name, err_str)),
+            }
+        }
+
+        let removed: usize = list.iter().map(Vec::len).sum();
+        let added: usize = new_list.iter().map(Vec::len).sum();
+        self.check_size_limit(removed, added)?;
+
+        let count = new_list.len();
+        let orig_list = self.list_map.insert(String::from(name), new_list);
+        self.used_bytes = self.used_bytes - removed + added;
+        match self.dumpdb_key(name) {
+            Ok(_) => Ok(count),
+            Err(err) => {
+                if let Some(orig_list) = orig_list {
+                    self.list_map.insert(String::from(name), orig_list);
+                }
+                self.used_bytes = self.used_bytes - added + removed;
+                Err(err)
+            }
+        }
     }
 
-    /// Add a single item to an existing list.
+    /// Rotate the items of a list in place.
     ///
-    /// As mentioned before, the lists are heterogeneous, meaning a single list can contain
-    /// items of different types. That means that the item can be of any type that is serializable.
-    /// That includes all primitive types, vectors, tuples and every struct that has the
-    /// `#[derive(Serialize, Deserialize)` attribute.
+    /// This method rotates the list to the left by `n` positions, meaning the first `n` items are moved to
+    /// the end of the list, or to the right if `n` is negative, meaning the last `n` items are moved to the
+    /// beginning of the list. Rotating by more than the length of the list wraps around. The items are moved
+    /// as they're stored, without deserializing them, so this method works for lists of any type.
     ///
-    /// If the item was added successfully the method returns
-    /// `Some(`[PickleDbListExtender](struct.PickleDbListExtender.html)`)` which enables to add more
-    /// items to the list. Alternatively the method returns `None` if the list isn't found in the DB
-    /// or if a failure happened while extending the list. Failures are not likely to happen but may
-    /// occur mostly in cases where this action triggers a DB dump (which is decided according to the dump policy)
+    /// This method returns `Ok(true)` if the list was rotated or `Ok(false)` if it doesn't exist or is empty.
+    /// It may also return an `Err(`[Error](error/struct.Error.html)`)` if the DB dump (which is decided
+    /// according to the dump policy) fails, in which case the list is left unchanged.
     ///
     /// # Arguments
     ///
     /// * `name` - the list key
-    /// * `value` - a reference of the item to add to the list
+    /// * `n` - the number of positions to rotate the list by, to the left if positive or to the right if negative
     ///
     /// # Examples
     ///
     /// ```no_run
     /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
-    /// // create a new list
-    /// db.lcreate("list1");
+    /// db.lcreate("list1").unwrap().lextend(&vec![1, 2, 3, 4]);
     ///
-    /// // add items of different types to the list
-    /// db.ladd("list1", &100).unwrap()
-    ///   .ladd(&String::from("my string"))
-    ///   .ladd(&vec!["aa", "bb", "cc"]);
+    /// // now the list looks like this: [2, 3, 4, 1]
+    /// db.lrotate("list1", 1).unwrap();
+    ///
+    /// // now the list looks like this: [3, 4, 1, 2]
+    /// db.lrotate("list1", -3).unwrap();
     /// ```
     ///
-    pub fn ladd<V>(&mut self, name: &str, value: &V) -> Option<PickleDbListExtender>
-    where
-        V: Serialize,
-    {
-        self.lextend(name, &[value])
+    pub fn lrotate(&mut self, name: &str, n: isize) -> Result<bool> {
+        let list = match self.list_map.get_mut(name) {
+            Some(list) if !list.is_empty() => list,
+            _ => return Ok(false),
+        };
+
+        let mid = n.rem_euclid(list.len() as isize) as usize;
+        if mid == 0 {
+            return Ok(true);
+        }
+        list.rotate_left(mid);
+
+        match self.dumpdb_key(name) {
+            Ok(_) => Ok(true),
+            Err(err) => {
+                let same_list = self.list_map.get_mut(name).unwrap();
+                same_list.rotate_right(mid);
+                Err(err)
+            }
+        }
     }
 
-    /// Add multiple items to an existing list.
+    /// Remove items from the beginning of a list so it's no longer than a given length.
     ///
-    /// As mentioned before, the lists are heterogeneous, meaning a single list can contain
-    /// items of different types. That means that the item can be of any type that is serializable.
-    /// That includes all primitive types, vectors, tuples and every struct that has the
-    /// `#[derive(Serialize, Deserialize)` attribute.
-    /// This method adds multiple items to the list, but since they're in a vector that means all
-    /// of them are of the same type. Of course it doesn't mean that the list cannot contain items
-    /// of other types as well, as you can see in the example below.
+    /// This method keeps only the last `max_len` items of the list, which is useful for keeping a bounded
+    /// history where new items are added to the end of the list. A single DB dump (which is decided according
+    /// to the dump policy) is triggered after the items are removed.
     ///
-    /// If all items were added successfully the method returns
-    /// `Some(`[PickleDbListExtender](struct.PickleDbListExtender.html)`)` which enables to add more
-    /// items to the list. Alternatively the method returns `None` if the list isn't found in the DB
-    /// or if a failure happened while extending the list. Failures are not likely to happen but may
-    /// occur mostly in cases where this action triggers a DB dump (which is decided according to the dump policy)
+    /// The return value of this method is the number of items that were removed. If the list doesn't exist
+    /// or isn't longer than `max_len` a value of zero (0) is returned. An `Err(`[Error](error/struct.Error.html)`)`
+    /// is returned if the dump fails, in which case the list is left unchanged.
     ///
     /// # Arguments
     ///
     /// * `name` - the list key
-    /// * `seq` - an iterator containing references to the new items to add to the list
+    /// * `max_len` - the maximal number of items to keep
     ///
     /// # Examples
     ///
     /// ```no_run
     /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
-    /// // create a new list
-    /// db.lcreate("list1");
+    /// db.lcreate("list1").unwrap().lextend(&vec![1, 2, 3, 4, 5]);
     ///
-    /// // add a bunch of numbers to the list
-    /// db.lextend("list1", &vec![100, 200, 300]).unwrap()
+    /// // now the list looks like this: [4, 5]
+    /// assert_eq!(db.ltruncate_front("list1", 2).unwrap(), 3);
+    /// ```
     ///
-    /// // add a String item to the list
-    ///   .ladd(&String::from("my string"))
+    pub fn ltruncate_front(&mut self, name: &str, max_len: usize) -> Result<usize> {
+        self.ltruncate(name, max_len, true)
+    }
+
+    /// Remove items from the end of a list so it's no longer than a given length.
     ///
-    /// // add a vector item to the list
-    ///   .ladd(&vec!["aa", "bb", "cc"]);
+    /// This method is similar to [ltruncate_front()](#method.ltruncate_front) but keeps the first `max_len`
+    /// items of the list and removes the rest.
     ///
-    /// // now the list contains 5 items and looks like this: [100, 200, 300, "my string", ["aa, "bb", "cc"]]
-    /// ```
+    /// # Arguments
     ///
-    pub fn lextend<'a, V, I>(&mut self, name: &str, seq: I) -> Option<PickleDbListExtender>
-    where
-        V: 'a + Serialize,
-        I: IntoIterator<Item = &'a V>,
-    {
-        let serializer = &self.serializer;
-        match self.list_map.get_mut(name) {
-            Some(list) => {
-                let original_len = list.len();
-                let serialized: Vec<Vec<u8>> = seq
-                    .into_iter()
-                    .map(|x| serializer.serialize_data(x).unwrap())
-                    .collect();
-                list.extend(serialized);
-                match self.dumpdb() {
-                    Ok(_) => (),
-                    Err(_) => {
-                        let same_list = self.list_map.get_mut(name).unwrap();
-                        same_list.truncate(original_len);
-                        return None;
-                    }
+    /// * `name` - the list key
+    /// * `max_len` - the maximal number of items to keep
+    ///
+    pub fn ltruncate_back(&mut self, name: &str, max_len: usize) -> Result<usize> {
+        self.ltruncate(name, max_len, false)
+    }
+
+    fn ltruncate(&mut self, name: &str, max_len: usize, front: bool) -> Result<usize> {
+        let removed_items = match self.list_map.get_mut(name) {
+            Some(list) if list.len() > max_len => {
+                if front {
+                    let count = list.len() - max_len;
+                    list.drain(..count).collect::<Vec<_>>()
+                } else {
+                    list.split_off(max_len)
                 }
-                Some(PickleDbListExtender {
-                    db: self,
-                    list_name: String::from(name),
-                })
             }
+            _ => return Ok(0),
+        };
 
-            None => None,
+        let count = removed_items.len();
+        let removed_size: usize = removed_items.iter().map(Vec::len).sum();
+        self.used_bytes -= removed_size;
+        match self.dumpdb_key(name) {
+            Ok(_) => Ok(count),
+            Err(err) => {
+                self.used_bytes += removed_size;
+                let same_list = self.list_map.get_mut(name).unwrap();
+                if front {
+                    same_list.splice(0..0, removed_items);
+                } else {
+                    same_list.extend(removed_items);
+                }
+                Err(err)
+            }
         }
     }
 
-    /// Get an item of of a certain list in a certain position.
+    /// Create a new set.
     ///
-    /// This method takes a list name and a position inside the list
-    /// and retrieves the item in this position. It's the user's responsibility
-    /// to know what is the correct type of the item and give it while calling this method.
-    /// Since the item in the lists are stored in a serialized way the returned object
-    /// is not a reference to the item stored in a DB but actually a new instance of it.
-    /// If the list is not found in the DB or the given position is out of bounds
-    /// of the list `None` will be returned. Otherwise `Some(V)` will be returned.
+    /// Sets are similar to lists, but each member is stored only once and the members aren't kept in any
+    /// particular order. Like list items, members can be of any serializable type and they're compared by
+    /// their serialized bytes, so please note the caveat about comparing serialized values that is described
+    /// in [lcontains()](#method.lcontains).
+    ///
+    /// This method just creates a new empty set. If a value, a list, a hash or another set is already stored
+    /// under this key, it will be overridden.
+    ///
+    /// In case of a failure an `Err(`[Error](error/struct.Error.html)`)` is returned and the DB is left
+    /// unchanged. Failures are not likely to happen but may occur mostly in cases where this action triggers
+    /// a DB dump (which is decided according to the dump policy)
     ///
     /// # Arguments
     ///
-    /// * `name` - the list key
-    /// * `pos` - the position of the item inside the list. Expected value is >= 0
+    /// * `name` - the key of the set that will be created
     ///
     /// # Examples
+    ///
     /// ```no_run
     /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
-    /// // create a list
-    /// db.lcreate("list1");
+    /// db.screate("tags").unwrap();
+    /// db.sadd("tags", &"red").unwrap();
+    /// db.sadd("tags", &"green").unwrap();
     ///
-    /// // add a number to list1
-    /// db.ladd("list1", &100);
+    /// // "red" is already in the set, so it isn't added again
+    /// assert!(!db.sadd("tags", &"red").unwrap());
+    /// assert_eq!(db.slen("tags"), 2);
+    /// ```
     ///
-    /// // add a string to list1
-    /// db.ladd("list1", &String::from("my string"));
+    pub fn screate(&mut self, name: &str) -> Result<()> {
+        let counted = self.key_footprint(name);
+        self.check_size_limit(counted, name.len())?;
+
+        let orig_value = self.map.remove(name);
+        let orig_expiry = self.expiry_map.remove(name);
+        let orig_list = self.list_map.remove(name);
+        let orig_hash = self.hash_map.remove(name);
+        let orig_set = self.set_map.insert(String::from(name), HashSet::new());
+        self.recount_key(name, counted);
+        match self.dumpdb_key(name) {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                let counted = self.key_footprint(name);
+                self.set_map.remove(name);
+                if let Some(orig_value) = orig_value {
+                    self.map.insert(String::from(name), orig_value);
+                }
+                if let Some(orig_expiry) = orig_expiry {
+                    self.expiry_map.insert(String::from(name), orig_expiry);
+                }
+                if let Some(orig_list) = orig_list {
+                    self.list_map.insert(String::from(name), orig_list);
+                }
+                if let Some(orig_hash) = orig_hash {
+                    self.hash_map.insert(String::from(name), orig_hash);
+                }
+                if let Some(orig_set) = orig_set {
+                    self.set_map.insert(String::from(name), orig_set);
+                }
+                self.recount_key(name, counted);
+                Err(err)
+            }
+        }
+    }
+
+    /// Check if a set exists.
     ///
-    /// // read the first item in the list - int
-    /// let x = db.lget::<i32>("list1", 0).unwrap();
+    /// This method returns `true` if a set is stored under this name and `false` otherwise.
+    /// Unlike [exists()](#method.exists) it doesn't check for values or lists.
     ///
-    /// // read the second item in the list - string
-    /// let s = db.lget::<String>("list1", 1).unwrap();
-    /// ```
-    pub fn lget<V>(&self, name: &str, pos: usize) -> Option<V>
+    /// # Arguments
+    ///
+    /// * `name` - the set key to check
+    ///
+    pub fn sexists(&self, name: &str) -> bool {
+        self.set_map.contains_key(name)
+    }
+
+    /// Add a member to an existing set.
+    ///
+    /// This method serializes the value and adds it to the set, unless the set already contains a member
+    /// that is equal to it. A DB dump (which is decided according to the dump policy) is triggered only if
+    /// the member was added.
+    ///
+    /// This method returns `Ok(true)` if the member was added, `Ok(false)` if the set already contains it, or an
+    /// `Err(`[Error](error/struct.Error.html)`)` if the set doesn't exist (of type
+    /// [ErrorType::SetNotFound](error/enum.ErrorType.html#variant.SetNotFound)), if serialization fails or if
+    /// the dump fails, in which case the set is left unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the set key
+    /// * `value` - a reference of the member to add
+    ///
+    pub fn sadd<V>(&mut self, name: &str, value: &V) -> Result<bool>
     where
-        V: DeserializeOwned,
+        V: Serialize,
     {
-        match self.list_map.get(name) {
-            Some(list) => match list.get(pos) {
-                Some(val) => self.serializer.deserialize_data::<V>(val),
-                None => None,
-            },
-            None => None,
+        let serialized_value = match self.serializer.serialize_data(value) {
+            Ok(val) => val,
+            Err(err_str) => return Err(PickleDb::serialization_error(name, err_str)),
+        };
+
+        match self.set_map.get(name) {
+            Some(set) if set.contains(&serialized_value) => return Ok(false),
+            Some(_) => (),
+            None => return Err(Error::new(ErrorCode::SetNotFound(String::from(name)))),
+        }
+
+        self.check_size_limit(0, serialized_value.len())?;
+        self.set_map
+            .get_mut(name)
+            .unwrap()
+            .insert(serialized_value.clone());
+        self.used_bytes += serialized_value.len();
+        match self.dumpdb_key(name) {
+            Ok(_) => Ok(true),
+            Err(err) => {
+                self.set_map
+                    .get_mut(name)
+                    .unwrap()
+                    .remove(&serialized_value);
+                self.used_bytes -= serialized_value.len();
+                Err(err)
+            }
         }
     }
 
-    /// Get the length of a list.
+    /// Remove a member from a set.
     ///
-    /// If the list is empty or if it doesn't exist the value of 0 is returned.
+    /// This method returns `Ok(true)` if the member was found in the set and removed, or `Ok(false)` if the set
+    /// doesn't exist or doesn't contain it. It may also return an `Err(`[Error](error/struct.Error.html)`)` if
+    /// serialization or the DB dump (which is decided according to the dump policy) fails, in which case the set
+    /// is left unchanged.
     ///
     /// # Arguments
     ///
-    /// * `name` - the list key
+    /// * `name` - the set key
+    /// * `value` - a reference of the member to remove
     ///
-    pub fn llen(&self, name: &str) -> usize {
-        match self.list_map.get(name) {
-            Some(list) => list.len(),
-            None => 0,
+    pub fn srem<V>(&mut self, name: &str, value: &V) -> Result<bool>
+    where
+        V: Serialize,
+    {
+        let serialized_value = match self.serializer.serialize_data(value) {
+            Ok(val) => val,
+            Err(err_str) => return Err(PickleDb::serialization_error(name, err_str)),
+        };
+
+        let removed = match self.set_map.get_mut(name) {
+            Some(set) => set.remove(&serialized_value),
+            None => false,
+        };
+        if !removed {
+            return Ok(false);
+        }
+        self.used_bytes -= serialized_value.len();
+
+        match self.dumpdb_key(name) {
+            Ok(_) => Ok(true),
+            Err(err) => {
+                self.used_bytes += serialized_value.len();
+                self.set_map.get_mut(name).unwrap().insert(serialized_value);
+                Err(err)
+            }
         }
     }
 
-    /// Remove a list.
+    /// Check if a set contains a certain member.
     ///
-    /// This method is somewhat similar to [rem()](#method.rem) but with 2 small differences:
-    /// * This method only removes lists and not key-value pairs
-    /// * The return value of this method is the number of items that were in
-    ///   the list that was removed. If the list doesn't exist a value of zero (0) is
-    ///   returned. In case of a failure an `Err(`[Error](error/struct.Error.html)`)` is returned.
-    ///   Failures are not likely to happen but may occur mostly in cases where this action triggers a
-    ///   DB dump (which is decided according to the dump policy)
+    /// This method returns `true` if the member is found and `false` if it isn't found, if the set doesn't
+    /// exist or if the value can't be serialized.
     ///
     /// # Arguments
     ///
-    /// * `name` - the list key to remove
+    /// * `name` - the set key
+    /// * `value` - a reference of the member to look for
     ///
-    pub fn lrem_list(&mut self, name: &str) -> Result<usize> {
-        let res = self.llen(name);
-        match self.list_map.remove(name) {
-            Some(list) => match self.dumpdb() {
-                Ok(_) => Ok(res),
-                Err(err) => {
-                    self.list_map.insert(String::from(name), list);
-                    Err(err)
-                }
+    pub fn scontains<V>(&self, name: &str, value: &V) -> bool
+    where
+        V: Serialize,
+    {
+        match self.set_map.get(name) {
+            Some(set) => match self.serializer.serialize_data(value) {
+                Ok(serialized_value) => set.contains(&serialized_value),
+                Err(_) => false,
             },
-            None => Ok(res),
+            None => false,
+        }
+    }
+
+    /// Get all the members of a set.
+    ///
+    /// It's the user's responsibility to know what is the correct type of the members and give it while
+    /// calling this method. The members are returned in no particular order.
+    ///
+    /// If the set is not found in the DB or one of its members can't be deserialized into `V`, `None` is
+    /// returned. Otherwise `Some(Vec<V>)` will be returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the set key
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// db.screate("ids").unwrap();
+    /// db.sadd("ids", &3).unwrap();
+    /// db.sadd("ids", &1).unwrap();
+    ///
+    /// let mut ids = db.smembers::<i32>("ids").unwrap();
+    /// ids.sort();
+    /// assert_eq!(ids, vec![1, 3]);
+    /// ```
+    ///
+    pub fn smembers<V>(&self, name: &str) -> Option<Vec<V>>
+    where
+        V: DeserializeOwned,
+    {
+        self.set_map
+            .get(name)?
+            .iter()
+            .map(|member| self.serializer.deserialize_data::<V>(member))
+            .collect()
+    }
+
+    /// Get the number of members in a set.
+    ///
+    /// If the set is empty or if it doesn't exist the value of 0 is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the set key
+    ///
+    pub fn slen(&self, name: &str) -> usize {
+        match self.set_map.get(name) {
+            Some(set) => set.len(),
+            None => 0,
         }
     }
 
-    /// Pop an item out of a list.
+    /// Set a field of a hash.
     ///
-    /// This method takes a list name and a position inside the list, removes the
-    /// item in this position and returns it to the user. It's the user's responsibility
-    /// to know what is the correct type of the item and give it while calling this method.
-    /// Since the item in the lists are stored in a serialized way the returned object
-    /// is not a reference to the item stored in a DB but actually a new instance of it.
+    /// A hash is a map of fields to values that is stored under a single key, similar to Redis' hashes. The
+    /// fields are strings and the values can be of any serializable type. Unlike storing a whole `HashMap` as
+    /// a single value, each field is serialized separately, so setting a field doesn't deserialize or serialize
+    /// the other fields of the hash.
     ///
-    /// If the list is not found in the DB or the given position is out of bounds no item
-    /// will be removed and `None` will be returned. `None` may also be returned
-    /// if removing the item fails, which may happen mostly in cases where this action
-    /// triggers a DB dump (which is decided according to the dump policy).
-    /// Otherwise the item will be removed and `Some(V)` will be returned.
+    /// If no hash is stored under `name` a new hash is created, and any value, list or set that is stored under
+    /// this key is overridden. If the field already exists its value is replaced.
     ///
-    /// This method is very similar to [lrem_value()](#method.lrem_value), the only difference is that this
-    /// methods returns the value and [lrem_value()](#method.lrem_value) returns only an indication whether
-    /// the item was removed or not.
+    /// This method returns `Ok(true)` if a new field was added, `Ok(false)` if an existing field was updated, or
+    /// an `Err(`[Error](error/struct.Error.html)`)` if serialization or the DB dump (which is decided according
+    /// to the dump policy) fails, in which case the DB is left unchanged.
     ///
     /// # Arguments
     ///
-    /// * `name` - the list key
-    /// * `pos` - the position of the item to remove
+    /// * `name` - the hash key
+    /// * `field` - the field to set
+    /// * `value` - a value of any serializable type
     ///
     /// # Examples
     ///
     /// ```no_run
     /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
-    /// // create a list
-    /// db.lcreate("list1");
-    ///
-    /// // add 4 items to the list
-    /// db.lextend("list1", &vec![1,2,3,4]);
-    ///
-    /// // remove item in position 2
-    /// let item2 = db.lpop::<i32>("list1", 2);
+    /// db.hset("user:1", "name", &String::from("John")).unwrap();
+    /// db.hset("user:1", "age", &30).unwrap();
     ///
-    /// // item2 contains 3 and the list now looks like this: [1, 2, 4]
-    ///
-    /// // remove item in position 1
-    /// let item1 = db.lpop::<i32>("list1", 1);
-    ///
-    /// // item1 contains 2 and the list now looks like this: [1, 4]
+    /// // update a single field
+    /// assert!(!db.hset("user:1", "age", &31).unwrap());
+    /// assert_eq!(db.hget::<i32>("user:1", "age").unwrap(), 31);
     /// ```
     ///
-    pub fn lpop<V>(&mut self, name: &str, pos: usize) -> Option<V>
+    pub fn hset<V>(&mut self, name: &str, field: &str, value: &V) -> Result<bool>
     where
-        V: DeserializeOwned,
+        V: Serialize,
     {
-        match self.list_map.get_mut(name) {
-            Some(list) => {
-                if pos < list.len() {
-                    let res = list.remove(pos);
-                    match self.dumpdb() {
-                        Ok(_) => self.serializer.deserialize_data::<V>(&res),
-                        Err(_) => {
-                            let same_list = self.list_map.get_mut(name).unwrap();
-                            same_list.insert(pos, res);
-                            None
-                        }
+        let ser_data = match self.serializer.serialize_data(value) {
+            Ok(data) => data,
+            Err(err_str) => return Err(PickleDb::serialization_error(name, err_str)),
+        };
+
+        if !self.hash_map.contains_key(name) {
+            let counted = self.key_footprint(name);
+            self.check_size_limit(counted, name.len() + field.len() + ser_data.len())?;
+            let orig_value = self.map.remove(name);
+            let orig_expiry = self.expiry_map.remove(name);
+            let orig_list = self.list_map.remove(name);
+            let orig_set = self.set_map.remove(name);
+            let mut hash = HashMap::new();
+            hash.insert(String::from(field), ser_data);
+            self.hash_map.insert(String::from(name), hash);
+            self.recount_key(name, counted);
+            return match self.dumpdb_key(name) {
+                Ok(_) => Ok(true),
+                Err(err) => {
+                    let counted = self.key_footprint(name);
+                    self.hash_map.remove(name);
+                    if let Some(orig_value) = orig_value {
+                        self.map.insert(String::from(name), orig_value);
                     }
-                } else {
-                    None
+                    if let Some(orig_expiry) = orig_expiry {
+                        self.expiry_map.insert(String::from(name), orig_expiry);
+                    }
+                    if let Some(orig_list) = orig_list {
+                        self.list_map.insert(String::from(name), orig_list);
+                    }
+                    if let Some(orig_set) = orig_set {
+                        self.set_map.insert(String::from(name), orig_set);
+                    }
+                    self.recount_key(name, counted);
+                    Err(err)
                 }
-            }
+            };
+        }
 
-            None => None,
+        let removed = match self.hash_map[name].get(field) {
+            Some(orig_value) => field.len() + orig_value.len(),
+            None => 0,
+        };
+        let added = field.len() + ser_data.len();
+        self.check_size_limit(removed, added)?;
+        let orig_value = self
+            .hash_map
+            .get_mut(name)
+            .unwrap()
+            .insert(String::from(field), ser_data);
+        self.used_bytes = self.used_bytes - removed + added;
+        match self.dumpdb_key(name) {
+            Ok(_) => Ok(orig_value.is_none()),
+            Err(err) => {
+                self.used_bytes = self.used_bytes - added + removed;
+                let same_hash = self.hash_map.get_mut(name).unwrap();
+                match orig_value {
+                    Some(orig_value) => same_hash.insert(String::from(field), orig_value),
+                    None => same_hash.remove(field),
+                };
+                Err(err)
+            }
         }
     }
 
-    /// Remove an item out of a list.
+    /// Get the value of a field of a hash.
     ///
-    /// This method takes a list name and a reference to a value, removes the first instance of the
-    /// value if it exists in the list, and returns an indication whether the item was removed or not.
+    /// It's the user's responsibility to know what is the correct type of the value and give it while
+    /// calling this method. If the hash or the field doesn't exist, or if the value can't be deserialized
+    /// into `V`, `None` is returned.
     ///
-    /// If the list is not found in the DB or the given value isn't found in the list, no item will
-    /// be removed and `Ok(false)` will be returned.
-    /// If removing the item fails, which may happen mostly in cases where this action triggers
-    /// a DB dump (which is decided according to the dump policy), an
-    /// `Err(`[Error](error/struct.Error.html)`)` is returned.
-    /// Otherwise the item will be removed and `Ok(true)` will be returned.
+    /// # Arguments
     ///
-    /// This method is very similar to [lpop()](#method.lpop), the only difference is that this
-    /// methods returns an indication and [lpop()](#method.lpop) returns the actual item that was removed.
+    /// * `name` - the hash key
+    /// * `field` - the field to get
+    ///
+    pub fn hget<V>(&self, name: &str, field: &str) -> Option<V>
+    where
+        V: DeserializeOwned,
+    {
+        let value = self.hash_map.get(name)?.get(field)?;
+        self.serializer.deserialize_data(value)
+    }
+
+    /// Remove a field from a hash.
+    ///
+    /// The hash itself is kept even if it becomes empty, use [rem()](#method.rem) to remove it.
+    ///
+    /// This method returns `Ok(true)` if the field was found and removed, or `Ok(false)` if the hash doesn't
+    /// exist or doesn't contain it. It may also return an `Err(`[Error](error/struct.Error.html)`)` if the DB
+    /// dump (which is decided according to the dump policy) fails, in which case the hash is left unchanged.
     ///
     /// # Arguments
     ///
-    /// * `name` - the list key
-    /// * `value` - the item to remove
+    /// * `name` - the hash key
+    /// * `field` - the field to remove
     ///
-    /// # Examples
+    pub fn hdel(&mut self, name: &str, field: &str) -> Result<bool> {
+        let orig_value = match self.hash_map.get_mut(name) {
+            Some(hash) => hash.remove(field),
+            None => None,
+        };
+        let orig_value = match orig_value {
+            Some(orig_value) => orig_value,
+            None => return Ok(false),
+        };
+        let removed = field.len() + orig_value.len();
+        self.used_bytes -= removed;
+
+        match self.dumpdb_key(name) {
+            Ok(_) => Ok(true),
+            Err(err) => {
+                self.used_bytes += removed;
+                self.hash_map
+                    .get_mut(name)
+                    .unwrap()
+                    .insert(String::from(field), orig_value);
+                Err(err)
+            }
+        }
+    }
+
+    /// Get all the fields of a hash and their values.
     ///
-    /// ```no_run
-    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
-    /// // create a list
-    /// db.lcreate("list1");
+    /// Since the values are returned in a single map all of them are deserialized into the same type `V`.
+    /// If the hash is not found in the DB or one of its values can't be deserialized into `V`, `None` is
+    /// returned.
     ///
-    /// // add 4 items to the list
-    /// db.lextend("list1", &vec![1,2,3,4]);
+    /// # Arguments
     ///
-    /// // remove 2
-    /// db.lrem_value("list1", &2).unwrap();
+    /// * `name` - the hash key
     ///
-    /// // The list now looks like this: [1, 3, 4]
+    /// # Examples
     ///
-    /// // remove 3
-    /// db.lrem_value("list1", &3).unwrap();
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// db.hset("scores", "player1", &10).unwrap();
+    /// db.hset("scores", "player2", &20).unwrap();
     ///
-    /// // The list now looks like this: [1, 4]
+    /// for (player, score) in db.hgetall::<i32>("scores").unwrap() {
+    ///     println!("{}: {}", player, score);
+    /// }
     /// ```
     ///
-    pub fn lrem_value<V>(&mut self, name: &str, value: &V) -> Result<bool>
+    pub fn hgetall<V>(&self, name: &str) -> Option<HashMap<String, V>>
     where
-        V: Serialize,
+        V: DeserializeOwned,
     {
-        match self.list_map.get_mut(name) {
-            Some(list) => {
-                let serialized_value = match self.serializer.serialize_data(&value) {
-                    Ok(val) => val,
-                    Err(err_str) => return Err(Error::new(ErrorCode::Serialization(err_str))),
-                };
-
-                match list.iter().position(|x| *x == serialized_value) {
-                    Some(pos) => {
-                        list.remove(pos);
-                        match self.dumpdb() {
-                            Ok(_) => Ok(true),
-                            Err(err) => {
-                                let same_list = self.list_map.get_mut(name).unwrap();
-                                same_list.insert(pos, serialized_value);
-                                Err(err)
-                            }
-                        }
-                    }
-
-                    None => Ok(false),
-                }
-            }
+        self.hash_map
+            .get(name)?
+            .iter()
+            .map(|(field, value)| {
+                self.serializer
+                    .deserialize_data::<V>(value)
+                    .map(|value| (field.to_string(), value))
+            })
+            .collect()
+    }
 
-            None => Ok(false),
+    /// Get the number of fields in a hash.
+    ///
+    /// If the hash is empty or if it doesn't exist the value of 0 is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the hash key
+    ///
+    pub fn hlen(&self, name: &str) -> usize {
+        match self.hash_map.get(name) {
+            Some(hash) => hash.len(),
+            None => 0,
         }
     }
 
@@ -1006,15 +6441,53 @@ impl PickleDb {
     /// }
     /// ```
     ///
-    pub fn iter(&self) -> PickleDbIterator {
+    pub fn iter(&self) -> PickleDbIterator<'_> {
+        let now = SystemTime::now();
         PickleDbIterator {
             map_iter: self.map.iter(),
+            expiry_map: &self.expiry_map,
+            now,
+            remaining: self.map.len() - self.expired_count(now),
             serializer: &self.serializer,
         }
     }
 
+    /// Return an iterator over the keys and values in the DB whose values are of type `V`.
+    ///
+    /// This method is similar to [iter()](#method.iter) but deserializes each value into `V`, which is
+    /// convenient when all or most of the values in the DB are of the same type. Values that can't be
+    /// deserialized into `V` are skipped rather than causing an error. Please note that for serialization
+    /// methods that don't store the type of the data, such as
+    /// [SerializationMethod::Bin](enum.SerializationMethod.html#variant.Bin), a value of a different type may
+    /// still be deserialized successfully.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_json("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// db.set("player1", &10).unwrap();
+    /// db.set("player2", &20).unwrap();
+    /// db.set("title", &String::from("High scores")).unwrap();
+    ///
+    /// // "title" isn't a number, so it's skipped
+    /// for (player, score) in db.iter_typed::<i32>() {
+    ///     println!("{}: {}", player, score);
+    /// }
+    /// ```
+    ///
+    pub fn iter_typed<V>(&self) -> impl Iterator<Item = (&str, V)>
+    where
+        V: DeserializeOwned,
+    {
+        self.iter()
+            .filter_map(|kv| kv.get_value::<V>().map(|value| (kv.key, value)))
+    }
+
     /// Return an iterator over the items in certain list.
     ///
+    /// Please note that this method panics if the list doesn't exist. Use [try_liter()](#method.try_liter)
+    /// if the list may not exist.
+    ///
     /// # Arguments
     ///
     /// * `name` - the list name. If the list doesn't exist an exception is thrown
@@ -1031,25 +6504,234 @@ impl PickleDb {
     /// for item_iter in db.liter("list1") {
     ///     println!("Current item is: {}", item_iter.get_item::<i32>().unwrap());
     /// }
+    ///
+    /// // iterate over the items in list1 in reverse order
+    /// for item_iter in db.liter("list1").rev() {
+    ///     println!("Current item is: {}", item_iter.get_item::<i32>().unwrap());
+    /// }
+    ///
+    /// // get the position of each item as well
+    /// for item_iter in db.liter("list1") {
+    ///     println!("Item {} is: {}", item_iter.index(), item_iter.get_item::<i32>().unwrap());
+    /// }
+    /// ```
+    ///
+    pub fn liter(&self, name: &str) -> PickleDbListIterator<'_> {
+        match self.try_liter(name) {
+            Ok(list_iter) => list_iter,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Return an iterator over the items in certain list, or an error if the list doesn't exist.
+    ///
+    /// This method is similar to [liter()](#method.liter) but instead of panicking when the list
+    /// doesn't exist, it returns an `Err(`[Error](error/struct.Error.html)`)` of type
+    /// [ErrorType::ListNotFound](error/enum.ErrorType.html#variant.ListNotFound).
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the list name
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// match db.try_liter("list1") {
+    ///     Ok(list_iter) => {
+    ///         for item_iter in list_iter {
+    ///             println!("Current item is: {}", item_iter.get_item::<i32>().unwrap());
+    ///         }
+    ///     }
+    ///     Err(err) => println!("Cannot iterate over list1: {}", err),
+    /// }
     /// ```
     ///
-    pub fn liter(&self, name: &str) -> PickleDbListIterator {
+    pub fn try_liter(&self, name: &str) -> Result<PickleDbListIterator<'_>> {
         match self.list_map.get(name) {
-            Some(list) => PickleDbListIterator {
+            Some(list) => Ok(PickleDbListIterator {
                 list_iter: list.iter(),
+                pos: 0,
                 serializer: &self.serializer,
-            },
-            None => panic!("List '{}' doesn't exist", name),
+            }),
+            None => Err(Error::new(ErrorCode::ListNotFound(String::from(name)))),
+        }
+    }
+
+    /// Return an iterator over the items in certain list, or an empty iterator if the list doesn't exist.
+    ///
+    /// This method is similar to [liter()](#method.liter) but instead of panicking when the list
+    /// doesn't exist, it returns an iterator that doesn't yield any item. Use [try_liter()](#method.try_liter)
+    /// to tell a list that doesn't exist apart from an empty list.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the list name
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// // nothing happens if list1 doesn't exist
+    /// for item_iter in db.liter_or_empty("list1") {
+    ///     println!("Current item is: {}", item_iter.get_item::<i32>().unwrap());
+    /// }
+    /// ```
+    ///
+    pub fn liter_or_empty(&self, name: &str) -> PickleDbListIterator<'_> {
+        let list: &[Vec<u8>] = match self.list_map.get(name) {
+            Some(list) => list,
+            None => &[],
+        };
+        PickleDbListIterator {
+            list_iter: list.iter(),
+            pos: 0,
+            serializer: &self.serializer,
+        }
+    }
+
+    /// Return an iterator over all the lists in the DB.
+    ///
+    /// Each item returned by this iterator provides the list name and can be iterated itself
+    /// in order to go over the items in the list.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// // iterate over all lists and over the items in each list
+    /// for list in db.liter_all() {
+    ///     println!("Items of list {}:", list.name());
+    ///     for item_iter in list {
+    ///         println!("Current item is: {}", item_iter.get_item::<i32>().unwrap());
+    ///     }
+    /// }
+    /// ```
+    ///
+    pub fn liter_all(&self) -> PickleDbListsIterator<'_> {
+        PickleDbListsIterator {
+            lists_iter: self.list_map.iter(),
+            serializer: &self.serializer,
+        }
+    }
+}
+
+impl Clone for PickleDb {
+    /// Create an in-memory copy of the DB.
+    ///
+    /// The copy holds all the keys, values, lists, sets and hashes of the original DB, as well as its serialization
+    /// method and other settings, and changing one of them doesn't affect the other. This is useful
+    /// for experimenting with the data without touching the file.
+    ///
+    /// **Please note:** the copy refers to the same DB file as the original, so to prevent the two
+    /// from overriding each other's changes, the dump policy of the copy is always
+    /// [PickleDbDumpPolicy::NeverDump](enum.PickleDbDumpPolicy.html#variant.NeverDump) and nothing it
+    /// does is ever written to the file. In order to keep the copy, write it to a different file using
+    /// [save_as()](#method.save_as) and load it from there. Callbacks registered using
+    /// [on_dump()](#method.on_dump) and [on_change()](#method.on_change) aren't copied either.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// let mut copy = db.clone();
+    /// copy.set("key1", &100).unwrap();
+    ///
+    /// // the original DB and its file aren't changed
+    /// assert!(!db.exists("key1"));
+    ///
+    /// // keep the copy in a new file
+    /// copy.save_as("2.db").unwrap();
+    /// ```
+    ///
+    fn clone(&self) -> PickleDb {
+        PickleDb {
+            map: self.map.clone(),
+            list_map: self.list_map.clone(),
+            expiry_map: self.expiry_map.clone(),
+            set_map: self.set_map.clone(),
+            hash_map: self.hash_map.clone(),
+            serializer: self.serializer.clone(),
+            db_file_path: self.db_file_path.clone(),
+            dump_policy: PickleDbDumpPolicy::NeverDump,
+            last_dump: Instant::now(),
+            changes_since_dump: 0,
+            temp_dir: self.temp_dir.clone(),
+            create_dirs: self.create_dirs,
+            flush_on_drop: false,
+            dirty: self.dirty,
+            wal: self.wal,
+            wal_keys: HashSet::new(),
+            wal_full_dump: true,
+            wal_size: self.wal_size,
+            db_file_size: self.db_file_size,
+            db_file_crc: self.db_file_crc,
+            last_dump_hash: None,
+            max_bytes: self.max_bytes,
+            used_bytes: self.used_bytes,
+            #[cfg(feature = "lock")]
+            file_lock: None,
+            #[cfg(feature = "compression")]
+            compression: self.compression,
+            #[cfg(feature = "crypt")]
+            encryption_key: self.encryption_key,
+            batch_depth: 0,
+            batch_changed: false,
+            on_dump: None,
+            on_change: None,
+            value_cache: Mutex::new(HashMap::new()),
         }
     }
 }
 
+impl PartialEq for PickleDb {
+    /// Compare the content of two DBs.
+    ///
+    /// Two DBs are equal if they hold the same keys with the same values, the same lists with the same
+    /// items in the same order, the same sets with the same members and the same hashes with the same fields
+    /// and values. Values and items are compared by their serialized bytes, so DBs that use
+    /// different serialization methods are usually not equal even if they hold the same data. Settings such
+    /// as the file path and the dump policy, as well as the expiry times of keys, aren't compared. Keys that have
+    /// expired are ignored, even if they weren't removed from memory yet.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// let mut expected = pickledb::PickleDb::new_bin("expected.db", pickledb::PickleDbDumpPolicy::NeverDump);
+    /// expected.set("key1", &100).unwrap();
+    ///
+    /// db.set("key1", &100).unwrap();
+    /// assert!(db == expected);
+    /// ```
+    ///
+    fn eq(&self, other: &PickleDb) -> bool {
+        // every live value of `db` is also a live value of `other_db`
+        let live_values_contained = |db: &PickleDb, other_db: &PickleDb| {
+            db.map
+                .iter()
+                .filter(|(key, _)| !db.is_expired(key))
+                .all(|(key, value)| {
+                    !other_db.is_expired(key) && other_db.map.get(key) == Some(value)
+                })
+        };
+
+        live_values_contained(self, other)
+            && live_values_contained(other, self)
+            && self.list_map == other.list_map
+            && self.set_map == other.set_map
+            && self.hash_map == other.hash_map
+    }
+}
+
 impl Drop for PickleDb {
     fn drop(&mut self) {
-        if !matches!(
-            self.dump_policy,
-            PickleDbDumpPolicy::NeverDump | PickleDbDumpPolicy::DumpUponRequest
-        ) {
+        if self.flush_on_drop
+            && !matches!(
+                self.dump_policy,
+                PickleDbDumpPolicy::NeverDump | PickleDbDumpPolicy::DumpUponRequest
+            )
+        {
             // try to dump, ignore if fails
             let _ = self.dump();
         }
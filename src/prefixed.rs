@@ -0,0 +1,129 @@
+use crate::error::Result;
+use crate::iterators::{PickleDbIterator, PickleDbIteratorItem};
+use crate::pickledb::PickleDb;
+use serde::{de::DeserializeOwned, Serialize};
+
+/// A view of PickleDB that only accesses keys starting with a certain prefix. Returned in
+/// [PickleDb::with_prefix()](struct.PickleDb.html#method.with_prefix)
+///
+/// The prefix is added to every key given to this object's methods, and removed from the keys
+/// returned when iterating over it. This enables different parts of an application to share a
+/// single DB without their keys colliding.
+pub struct PrefixedDb<'a> {
+    pub(crate) db: &'a mut PickleDb,
+    pub(crate) prefix: String,
+}
+
+impl<'a> PrefixedDb<'a> {
+    fn prefixed_key(&self, key: &str) -> String {
+        format!("{}{}", self.prefix, key)
+    }
+
+    /// Get the prefix of this view
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Set a key-value pair under the prefix. See [PickleDb::set()](struct.PickleDb.html#method.set)
+    /// for more information.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - a string key, without the prefix
+    /// * `value` - a value of any serializable type
+    ///
+    pub fn set<V>(&mut self, key: &str, value: &V) -> Result<()>
+    where
+        V: Serialize,
+    {
+        let prefixed_key = self.prefixed_key(key);
+        self.db.set(&prefixed_key, value)
+    }
+
+    /// Get a value of a key under the prefix. See [PickleDb::get()](struct.PickleDb.html#method.get)
+    /// for more information.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - a string key, without the prefix
+    ///
+    pub fn get<V>(&self, key: &str) -> Option<V>
+    where
+        V: DeserializeOwned,
+    {
+        self.db.get(&self.prefixed_key(key))
+    }
+
+    /// Check if a key exists under the prefix. See [PickleDb::exists()](struct.PickleDb.html#method.exists)
+    /// for more information.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - the key to check, without the prefix
+    ///
+    pub fn exists(&self, key: &str) -> bool {
+        self.db.exists(&self.prefixed_key(key))
+    }
+
+    /// Remove a key-value pair or a list under the prefix. See [PickleDb::rem()](struct.PickleDb.html#method.rem)
+    /// for more information.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - the key or list name to remove, without the prefix
+    ///
+    pub fn rem(&mut self, key: &str) -> Result<bool> {
+        let prefixed_key = self.prefixed_key(key);
+        self.db.rem(&prefixed_key)
+    }
+
+    /// Return an iterator over the keys and values under the prefix.
+    ///
+    /// Only keys that start with the prefix are returned, and the prefix is removed from them.
+    /// The items are of type [PickleDbIteratorItem](struct.PickleDbIteratorItem.html), the same
+    /// as in [PickleDb::iter()](struct.PickleDb.html#method.iter).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+    /// let mut users = db.with_prefix("users:");
+    /// users.set("john", &30).unwrap();
+    ///
+    /// // prints "john: 30"
+    /// for kv in users.iter() {
+    ///     println!("{}: {}", kv.get_key(), kv.get_value::<i32>().unwrap());
+    /// }
+    /// ```
+    ///
+    pub fn iter(&self) -> PrefixedDbIterator<'_> {
+        PrefixedDbIterator {
+            db_iter: self.db.iter(),
+            prefix: &self.prefix,
+        }
+    }
+}
+
+/// Iterator object for iterating over keys and values under a prefix. Returned in
+/// [PrefixedDb::iter()](struct.PrefixedDb.html#method.iter)
+pub struct PrefixedDbIterator<'a> {
+    db_iter: PickleDbIterator<'a>,
+    prefix: &'a str,
+}
+
+impl<'a> Iterator for PrefixedDbIterator<'a> {
+    type Item = PickleDbIteratorItem<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for item in self.db_iter.by_ref() {
+            if let Some(key) = item.key.strip_prefix(self.prefix) {
+                return Some(PickleDbIteratorItem {
+                    key,
+                    value: item.value,
+                    serializer: item.serializer,
+                });
+            }
+        }
+        None
+    }
+}
@@ -1,13 +1,25 @@
-use serde::{de::DeserializeOwned, Serialize};
-use std::collections::HashMap;
+use serde::de::{DeserializeOwned, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::ser::{SerializeMap, SerializeSeq};
+use serde::{Deserialize, Deserializer, Serialize, Serializer as SerdeSerializer};
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
 use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use crate::error::{Error, ErrorCode};
 
 type DbMap = HashMap<String, Vec<u8>>;
 type DbListMap = HashMap<String, Vec<Vec<u8>>>;
+type DbExpiryMap = HashMap<String, SystemTime>;
+type DbSetMap = HashMap<String, HashSet<Vec<u8>>>;
+type DbHashMap = HashMap<String, HashMap<String, Vec<u8>>>;
+type DbMaps = (DbMap, DbListMap, DbExpiryMap, DbSetMap, DbHashMap);
 
 /// An enum for specifying the serialization method to use when creating a new PickleDB database
 /// or loading one from a file
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SerializationMethod {
     /// [JSON serialization](https://crates.io/crates/serde_json)
     Json,
@@ -20,8 +32,81 @@ pub enum SerializationMethod {
 
     /// [CBOR serialization](https://crates.io/crates/serde_cbor)
     Cbor,
+
+    /// [MessagePack serialization](https://crates.io/crates/rmp-serde)
+    MsgPack,
+
+    /// [TOML serialization](https://crates.io/crates/toml)
+    Toml,
+
+    /// A serialization implemented by the user, see [PickleDbSerializer](trait.PickleDbSerializer.html).
+    /// DBs that use it are created with [PickleDb::new_with_serializer()](struct.PickleDb.html#method.new_with_serializer)
+    /// and loaded with [PickleDb::load_with_serializer()](struct.PickleDb.html#method.load_with_serializer).
+    /// Passing this value to other methods that take a serialization method is the same as passing a serialization
+    /// method whose feature isn't enabled: serializing or deserializing data fails with an
+    /// [Error](error/struct.Error.html) of type [ErrorType::Serialization](error/enum.ErrorType.html#variant.Serialization)
+    Custom,
+}
+
+impl SerializationMethod {
+    /// Check if the feature of the serialization method is enabled, meaning it was compiled in and can be used.
+    ///
+    /// Each serialization method is enabled by the feature with the same name (`json`, `bincode`, `yaml`,
+    /// `cbor`, `msgpack` and `toml`). [SerializationMethod::Custom](#variant.Custom) isn't backed by any
+    /// feature, so it's never considered enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pickledb::SerializationMethod;
+    ///
+    /// // JSON is enabled by default
+    /// assert!(SerializationMethod::Json.is_enabled());
+    /// ```
+    pub fn is_enabled(&self) -> bool {
+        match self {
+            SerializationMethod::Json => cfg!(feature = "json"),
+            SerializationMethod::Bin => cfg!(feature = "bincode"),
+            SerializationMethod::Yaml => cfg!(feature = "yaml"),
+            SerializationMethod::Cbor => cfg!(feature = "cbor"),
+            SerializationMethod::MsgPack => cfg!(feature = "msgpack"),
+            SerializationMethod::Toml => cfg!(feature = "toml"),
+            SerializationMethod::Custom => false,
+        }
+    }
+
+    /// Get all the serialization methods whose features are enabled, see [is_enabled()](#method.is_enabled).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pickledb::SerializationMethod;
+    ///
+    /// // prefer MessagePack if it's available
+    /// let ser_method = if SerializationMethod::MsgPack.is_enabled() {
+    ///     SerializationMethod::MsgPack
+    /// } else {
+    ///     SerializationMethod::enabled()[0]
+    /// };
+    /// ```
+    pub fn enabled() -> Vec<SerializationMethod> {
+        [
+            SerializationMethod::Json,
+            SerializationMethod::Bin,
+            SerializationMethod::Yaml,
+            SerializationMethod::Cbor,
+            SerializationMethod::MsgPack,
+            SerializationMethod::Toml,
+        ]
+        .iter()
+        .copied()
+        .filter(SerializationMethod::is_enabled)
+        .collect()
+    }
 }
 
+/// Convert a number to a serialization method: 0 is Json, 1 is Bin, 2 is Yaml, 3 is Cbor, 4 is MsgPack
+/// and 5 is Toml. Any other number is converted to Json.
 impl From<i32> for SerializationMethod {
     fn from(item: i32) -> Self {
         match item {
@@ -29,6 +114,8 @@ impl From<i32> for SerializationMethod {
             1 => SerializationMethod::Bin,
             2 => SerializationMethod::Yaml,
             3 => SerializationMethod::Cbor,
+            4 => SerializationMethod::MsgPack,
+            5 => SerializationMethod::Toml,
             _ => SerializationMethod::Json,
         }
     }
@@ -40,22 +127,467 @@ impl fmt::Display for SerializationMethod {
     }
 }
 
+/// Parse a serialization method from its name, for example when reading it from a config file.
+///
+/// The names are case-insensitive: `"json"`, `"bin"` or `"bincode"`, `"yaml"`, `"cbor"`, `"msgpack"`
+/// and `"toml"`, so the string returned by `to_string()` can be parsed back for all the methods except
+/// [SerializationMethod::Custom](enum.SerializationMethod.html#variant.Custom): a custom serializer can't be
+/// created from its name. Parsing `"custom"` or any other string returns an [Error](error/struct.Error.html)
+/// of type [ErrorType::Serialization](error/enum.ErrorType.html#variant.Serialization).
+///
+/// # Examples
+///
+/// ```
+/// use pickledb::SerializationMethod;
+///
+/// let ser_method: SerializationMethod = "cbor".parse().unwrap();
+/// assert_eq!(ser_method, SerializationMethod::Cbor);
+/// ```
+impl FromStr for SerializationMethod {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "json" => Ok(SerializationMethod::Json),
+            "bin" | "bincode" => Ok(SerializationMethod::Bin),
+            "yaml" => Ok(SerializationMethod::Yaml),
+            "cbor" => Ok(SerializationMethod::Cbor),
+            "msgpack" => Ok(SerializationMethod::MsgPack),
+            "toml" => Ok(SerializationMethod::Toml),
+            _ => Err(Error::new(ErrorCode::Serialization(format!(
+                "Unknown serialization method '{}'",
+                s
+            )))),
+        }
+    }
+}
+
+/// A trait for implementing a serialization method that PickleDB doesn't provide, for example a custom
+/// binary layout.
+///
+/// Since values of any type are serialized, the trait works with type-erased Serde values using the
+/// [erased-serde](https://crates.io/crates/erased-serde) crate, which is re-exported as `pickledb::erased_serde`.
+/// Only [serialize_data()](#tymethod.serialize_data) and [deserialize_data()](#tymethod.deserialize_data) must be
+/// implemented: by default the entire DB is stored as a single value that contains all key-value pairs, lists,
+/// expiry times, sets and hashes, but this can be changed by implementing [serialize_db()](#method.serialize_db) and
+/// [deserialize_db()](#method.deserialize_db) as well.
+///
+/// Since values are read back without knowing the types they were written with, the format should be
+/// self-describing (like JSON or CBOR) in order to support all of PickleDB's features.
+///
+/// # Examples
+///
+/// ```
+/// use pickledb::erased_serde;
+/// use pickledb::{PickleDb, PickleDbDumpPolicy, PickleDbSerializer};
+///
+/// struct MyJsonSerializer;
+///
+/// impl PickleDbSerializer for MyJsonSerializer {
+///     fn serialize_data(&self, data: &dyn erased_serde::Serialize) -> Result<Vec<u8>, String> {
+///         serde_json::to_vec(data).map_err(|err| err.to_string())
+///     }
+///
+///     fn deserialize_data(
+///         &self,
+///         ser_data: &[u8],
+///         deserialize: &mut dyn FnMut(&mut dyn erased_serde::Deserializer<'_>) -> Result<(), erased_serde::Error>,
+///     ) -> Result<(), String> {
+///         let mut deserializer = serde_json::Deserializer::from_slice(ser_data);
+///         deserialize(&mut <dyn erased_serde::Deserializer>::erase(&mut deserializer))
+///             .map_err(|err| err.to_string())
+///     }
+/// }
+///
+/// let mut db = PickleDb::new_with_serializer("example.db", PickleDbDumpPolicy::NeverDump, Box::new(MyJsonSerializer));
+/// db.set("key1", &100).unwrap();
+/// assert_eq!(db.get::<i32>("key1").unwrap(), 100);
+/// ```
+pub trait PickleDbSerializer: Send + Sync {
+    /// Serialize a single value. Returns the serialized value or an error message.
+    fn serialize_data(&self, data: &dyn erased_serde::Serialize) -> Result<Vec<u8>, String>;
+
+    /// Deserialize a single value: create a deserializer of the format for `ser_data` and pass it to `deserialize`,
+    /// which deserializes the value using it. Returns an error message if the value can't be deserialized.
+    #[allow(clippy::type_complexity)]
+    fn deserialize_data(
+        &self,
+        ser_data: &[u8],
+        deserialize: &mut dyn FnMut(
+            &mut dyn erased_serde::Deserializer<'_>,
+        ) -> Result<(), erased_serde::Error>,
+    ) -> Result<(), String>;
+
+    /// Serialize the entire DB: the key-value pairs, the lists, the expiry times of keys that have a TTL, the sets
+    /// and the hashes. Values, list items, set members and hash field values are already serialized using
+    /// [serialize_data()](#tymethod.serialize_data).
+    fn serialize_db(
+        &self,
+        map: &HashMap<String, Vec<u8>>,
+        list_map: &HashMap<String, Vec<Vec<u8>>>,
+        expiry_map: &HashMap<String, SystemTime>,
+        set_map: &HashMap<String, HashSet<Vec<u8>>>,
+        hash_map: &HashMap<String, HashMap<String, Vec<u8>>>,
+    ) -> Result<Vec<u8>, String> {
+        // the set map and the hash map are written only if they're needed, which keeps the DB readable
+        // by versions that don't support sets or hashes
+        if !hash_map.is_empty() {
+            self.serialize_data(&(map, list_map, expiry_map, set_map, hash_map))
+        } else if !set_map.is_empty() {
+            self.serialize_data(&(map, list_map, expiry_map, set_map))
+        } else {
+            self.serialize_data(&(map, list_map, expiry_map))
+        }
+    }
+
+    /// Deserialize the entire DB that was serialized using [serialize_db()](#method.serialize_db).
+    #[allow(clippy::type_complexity)]
+    fn deserialize_db(
+        &self,
+        ser_db: &[u8],
+    ) -> Result<
+        (
+            HashMap<String, Vec<u8>>,
+            HashMap<String, Vec<Vec<u8>>>,
+            HashMap<String, SystemTime>,
+            HashMap<String, HashSet<Vec<u8>>>,
+            HashMap<String, HashMap<String, Vec<u8>>>,
+        ),
+        String,
+    > {
+        if let Ok(maps) = deserialize_custom(self, ser_db) {
+            return Ok(maps);
+        }
+
+        if let Ok((map, list_map, expiry_map, set_map)) = deserialize_custom(self, ser_db) {
+            return Ok((map, list_map, expiry_map, set_map, HashMap::new()));
+        }
+
+        deserialize_custom::<Self, (DbMap, DbListMap, DbExpiryMap)>(self, ser_db).map(
+            |(map, list_map, expiry_map)| {
+                (map, list_map, expiry_map, HashMap::new(), HashMap::new())
+            },
+        )
+    }
+}
+
+fn deserialize_custom<S, V>(serializer: &S, ser_data: &[u8]) -> Result<V, String>
+where
+    S: PickleDbSerializer + ?Sized,
+    V: DeserializeOwned,
+{
+    let mut data = None;
+    serializer.deserialize_data(ser_data, &mut |deserializer| {
+        data = Some(erased_serde::deserialize::<V>(deserializer)?);
+        Ok(())
+    })?;
+    match data {
+        Some(data) => Ok(data),
+        None => Err(String::from("Cannot deserialize value")),
+    }
+}
+
+/// An enum that describes the kind of a value stored in the DB. Returned by
+/// [PickleDb::value_kind()](struct.PickleDb.html#method.value_kind)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    /// A number, either an integer or a floating point number
+    Number,
+
+    /// A boolean
+    Bool,
+
+    /// A string or a single character
+    String,
+
+    /// A sequence such as a `Vec`, a tuple or a byte array
+    Array,
+
+    /// A map or a struct
+    Object,
+
+    /// A null value, such as `None` or `()`
+    Null,
+}
+
+// a wrapper that deserializes only the kind of a value, while skipping the value itself. It relies
+// on `deserialize_any()` so it works only for self-describing serialization methods
+pub(crate) struct DeserializedValueKind(pub(crate) ValueKind);
+
+impl<'de> Deserialize<'de> for DeserializedValueKind {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(ValueKindVisitor)
+    }
+}
+
+// a value of any type that can be deserialized from any self-describing serialization method and then
+// serialized using another one. It's used for converting values between serialization methods
+#[derive(Debug, PartialEq)]
+pub(crate) enum GenericValue {
+    Null,
+    Bool(bool),
+    I64(i64),
+    U64(u64),
+    F64(f64),
+    String(String),
+    Bytes(Vec<u8>),
+    Seq(Vec<GenericValue>),
+    Map(Vec<(GenericValue, GenericValue)>),
+}
+
+impl Serialize for GenericValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: SerdeSerializer,
+    {
+        match self {
+            GenericValue::Null => serializer.serialize_none(),
+            GenericValue::Bool(v) => serializer.serialize_bool(*v),
+            GenericValue::I64(v) => serializer.serialize_i64(*v),
+            GenericValue::U64(v) => serializer.serialize_u64(*v),
+            GenericValue::F64(v) => serializer.serialize_f64(*v),
+            GenericValue::String(v) => serializer.serialize_str(v),
+            GenericValue::Bytes(v) => serializer.serialize_bytes(v),
+            GenericValue::Seq(v) => {
+                let mut seq = serializer.serialize_seq(Some(v.len()))?;
+                for item in v {
+                    seq.serialize_element(item)?;
+                }
+                seq.end()
+            }
+            GenericValue::Map(v) => {
+                let mut map = serializer.serialize_map(Some(v.len()))?;
+                for (key, value) in v {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for GenericValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(GenericValueVisitor)
+    }
+}
+
+struct GenericValueVisitor;
+
+impl<'de> Visitor<'de> for GenericValueVisitor {
+    type Value = GenericValue;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any value")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(GenericValue::Bool(v))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(GenericValue::I64(v))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E> {
+        // some serialization methods deserialize non-negative integers as u64 and some as i64, so
+        // store them the same way to be able to compare values of different serialization methods
+        match i64::try_from(v) {
+            Ok(v) => Ok(GenericValue::I64(v)),
+            Err(_) => Ok(GenericValue::U64(v)),
+        }
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E> {
+        Ok(GenericValue::F64(v))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(GenericValue::String(v.to_string()))
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(GenericValue::Bytes(v.to_vec()))
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E> {
+        Ok(GenericValue::Null)
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(GenericValue::Null)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        GenericValue::deserialize(deserializer)
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        GenericValue::deserialize(deserializer)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut items = Vec::new();
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(GenericValue::Seq(items))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entries = Vec::new();
+        while let Some(entry) = map.next_entry()? {
+            entries.push(entry);
+        }
+        Ok(GenericValue::Map(entries))
+    }
+}
+
+struct ValueKindVisitor;
+
+impl ValueKindVisitor {
+    fn kind<E>(kind: ValueKind) -> Result<DeserializedValueKind, E> {
+        Ok(DeserializedValueKind(kind))
+    }
+}
+
+impl<'de> Visitor<'de> for ValueKindVisitor {
+    type Value = DeserializedValueKind;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any value")
+    }
+
+    fn visit_bool<E>(self, _v: bool) -> Result<Self::Value, E> {
+        Self::kind(ValueKind::Bool)
+    }
+
+    fn visit_i64<E>(self, _v: i64) -> Result<Self::Value, E> {
+        Self::kind(ValueKind::Number)
+    }
+
+    fn visit_i128<E>(self, _v: i128) -> Result<Self::Value, E> {
+        Self::kind(ValueKind::Number)
+    }
+
+    fn visit_u64<E>(self, _v: u64) -> Result<Self::Value, E> {
+        Self::kind(ValueKind::Number)
+    }
+
+    fn visit_u128<E>(self, _v: u128) -> Result<Self::Value, E> {
+        Self::kind(ValueKind::Number)
+    }
+
+    fn visit_f64<E>(self, _v: f64) -> Result<Self::Value, E> {
+        Self::kind(ValueKind::Number)
+    }
+
+    fn visit_char<E>(self, _v: char) -> Result<Self::Value, E> {
+        Self::kind(ValueKind::String)
+    }
+
+    fn visit_str<E>(self, _v: &str) -> Result<Self::Value, E> {
+        Self::kind(ValueKind::String)
+    }
+
+    fn visit_bytes<E>(self, _v: &[u8]) -> Result<Self::Value, E> {
+        Self::kind(ValueKind::Array)
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E> {
+        Self::kind(ValueKind::Null)
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Self::kind(ValueKind::Null)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        DeserializedValueKind::deserialize(deserializer)
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        DeserializedValueKind::deserialize(deserializer)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        while seq.next_element::<IgnoredAny>()?.is_some() {}
+        Self::kind(ValueKind::Array)
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        while map.next_entry::<IgnoredAny, IgnoredAny>()?.is_some() {}
+        Self::kind(ValueKind::Object)
+    }
+}
+
+// the JSON and YAML DB files store the serialized data of each value as a string, so it must be valid
+// UTF-8. Data that isn't (for example raw bytes that were stored using another serialization method)
+// fails the serialization instead of being written
+#[cfg(any(feature = "json", feature = "yaml"))]
+fn data_as_str<'a>(key: &str, data: &'a [u8]) -> Result<&'a str, String> {
+    match std::str::from_utf8(data) {
+        Ok(data_str) => Ok(data_str),
+        Err(err) => Err(format!("The data of '{}' isn't valid UTF-8: {}", key, err)),
+    }
+}
+
 #[cfg(feature = "json")]
-struct JsonSerializer {}
+struct JsonSerializer {
+    pretty: bool,
+}
 
 #[cfg(feature = "json")]
 impl JsonSerializer {
     fn new() -> JsonSerializer {
-        JsonSerializer {}
+        JsonSerializer { pretty: false }
     }
 
-    fn deserialize_data<V>(&self, ser_data: &[u8]) -> Option<V>
+    fn to_string<T: Serialize>(&self, data: &T) -> serde_json::Result<String> {
+        if self.pretty {
+            serde_json::to_string_pretty(data)
+        } else {
+            serde_json::to_string(data)
+        }
+    }
+
+    fn deserialize_data<V>(&self, ser_data: &[u8]) -> Result<V, String>
     where
         V: DeserializeOwned,
     {
-        match serde_json::from_str(std::str::from_utf8(ser_data).unwrap()) {
-            Ok(val) => Some(val),
-            Err(_) => None,
+        match serde_json::from_slice(ser_data) {
+            Ok(data) => Ok(data),
+            Err(err) => Err(err.to_string()),
         }
     }
 
@@ -69,32 +601,112 @@ impl JsonSerializer {
         }
     }
 
-    fn serialize_db(&self, map: &DbMap, list_map: &DbListMap) -> Result<Vec<u8>, String> {
+    fn serialize_db(
+        &self,
+        map: &DbMap,
+        list_map: &DbListMap,
+        expiry_map: &DbExpiryMap,
+        set_map: &DbSetMap,
+        hash_map: &DbHashMap,
+    ) -> Result<Vec<u8>, String> {
         let mut json_map: HashMap<&str, &str> = HashMap::new();
         for (key, value) in map.iter() {
-            json_map.insert(key, std::str::from_utf8(value).unwrap());
+            json_map.insert(key, data_as_str(key, value)?);
         }
 
         let mut json_list_map: HashMap<&str, Vec<&str>> = HashMap::new();
         for (key, list) in list_map.iter() {
-            let json_list: Vec<&str> = list
+            let json_list = list
                 .iter()
-                .map(|item| std::str::from_utf8(item).unwrap())
-                .collect();
+                .map(|item| data_as_str(key, item))
+                .collect::<Result<Vec<_>, _>>()?;
             json_list_map.insert(key, json_list);
         }
 
-        match serde_json::to_string(&(json_map, json_list_map)) {
+        let mut json_set_map: HashMap<&str, Vec<&str>> = HashMap::new();
+        for (key, set) in set_map.iter() {
+            let json_set = set
+                .iter()
+                .map(|item| data_as_str(key, item))
+                .collect::<Result<Vec<_>, _>>()?;
+            json_set_map.insert(key, json_set);
+        }
+
+        let mut json_hash_map: HashMap<&str, HashMap<&str, &str>> = HashMap::new();
+        for (key, hash) in hash_map.iter() {
+            let json_hash = hash
+                .iter()
+                .map(|(field, value)| Ok((field.as_str(), data_as_str(key, value)?)))
+                .collect::<Result<HashMap<_, _>, String>>()?;
+            json_hash_map.insert(key, json_hash);
+        }
+
+        // each of the optional maps is written only if it or one of the maps after it isn't empty,
+        // which keeps the file readable by versions that don't support them
+        let ser_db = if !hash_map.is_empty() {
+            self.to_string(&(
+                json_map,
+                json_list_map,
+                expiry_map,
+                json_set_map,
+                json_hash_map,
+            ))
+        } else if !set_map.is_empty() {
+            self.to_string(&(json_map, json_list_map, expiry_map, json_set_map))
+        } else if !expiry_map.is_empty() {
+            self.to_string(&(json_map, json_list_map, expiry_map))
+        } else {
+            self.to_string(&(json_map, json_list_map))
+        };
+
+        match ser_db {
             Ok(ser_db) => Ok(ser_db.into_bytes()),
             Err(err) => Err(err.to_string()),
         }
     }
 
-    fn deserialize_db(&self, ser_db: &[u8]) -> Result<(DbMap, DbListMap), String> {
-        match serde_json::from_str::<(HashMap<String, String>, HashMap<String, Vec<String>>)>(
-            std::str::from_utf8(ser_db).unwrap(),
-        ) {
-            Ok((json_map, json_list_map)) => {
+    fn deserialize_db(&self, ser_db: &[u8]) -> Result<DbMaps, String> {
+        type JsonMap = HashMap<String, String>;
+        type JsonListMap = HashMap<String, Vec<String>>;
+        type JsonHashMap = HashMap<String, HashMap<String, String>>;
+
+        // files without hashes don't have a hash map, files without sets don't have a set map, and
+        // files without TTLs don't have an expiry map
+        let ser_str = match std::str::from_utf8(ser_db) {
+            Ok(ser_str) => ser_str,
+            Err(err) => return Err(err.to_string()),
+        };
+        let maps =
+            serde_json::from_str::<(JsonMap, JsonListMap, DbExpiryMap, JsonListMap, JsonHashMap)>(
+                ser_str,
+            )
+            .or_else(|_| {
+                serde_json::from_str::<(JsonMap, JsonListMap, DbExpiryMap, JsonListMap)>(ser_str)
+                    .map(|(map, list_map, expiry_map, set_map)| {
+                        (map, list_map, expiry_map, set_map, HashMap::new())
+                    })
+            })
+            .or_else(|_| {
+                serde_json::from_str::<(JsonMap, JsonListMap, DbExpiryMap)>(ser_str).map(
+                    |(map, list_map, expiry_map)| {
+                        (map, list_map, expiry_map, HashMap::new(), HashMap::new())
+                    },
+                )
+            })
+            .or_else(|_| {
+                serde_json::from_str::<(JsonMap, JsonListMap)>(ser_str).map(|(map, list_map)| {
+                    (
+                        map,
+                        list_map,
+                        HashMap::new(),
+                        HashMap::new(),
+                        HashMap::new(),
+                    )
+                })
+            });
+
+        match maps {
+            Ok((json_map, json_list_map, expiry_map, json_set_map, json_hash_map)) => {
                 let mut byte_map: DbMap = HashMap::new();
                 for (key, value) in json_map.iter() {
                     byte_map.insert(key.to_string(), value.as_bytes().to_vec());
@@ -107,7 +719,29 @@ impl JsonSerializer {
                     byte_list_map.insert(key.to_string(), byte_list);
                 }
 
-                Ok((byte_map, byte_list_map))
+                let mut byte_set_map: DbSetMap = HashMap::new();
+                for (key, set) in json_set_map.iter() {
+                    let byte_set: HashSet<Vec<u8>> =
+                        set.iter().map(|item| item.as_bytes().to_vec()).collect();
+                    byte_set_map.insert(key.to_string(), byte_set);
+                }
+
+                let mut byte_hash_map: DbHashMap = HashMap::new();
+                for (key, hash) in json_hash_map.iter() {
+                    let byte_hash: HashMap<String, Vec<u8>> = hash
+                        .iter()
+                        .map(|(field, value)| (field.to_string(), value.as_bytes().to_vec()))
+                        .collect();
+                    byte_hash_map.insert(key.to_string(), byte_hash);
+                }
+
+                Ok((
+                    byte_map,
+                    byte_list_map,
+                    expiry_map,
+                    byte_set_map,
+                    byte_hash_map,
+                ))
             }
 
             Err(err) => Err(err.to_string()),
@@ -124,13 +758,13 @@ impl YamlSerializer {
         YamlSerializer {}
     }
 
-    fn deserialize_data<V>(&self, ser_data: &[u8]) -> Option<V>
+    fn deserialize_data<V>(&self, ser_data: &[u8]) -> Result<V, String>
     where
         V: DeserializeOwned,
     {
-        match serde_yaml::from_str(std::str::from_utf8(ser_data).unwrap()) {
-            Ok(val) => Some(val),
-            Err(_) => None,
+        match serde_yaml::from_slice(ser_data) {
+            Ok(data) => Ok(data),
+            Err(err) => Err(err.to_string()),
         }
     }
 
@@ -144,32 +778,112 @@ impl YamlSerializer {
         }
     }
 
-    fn serialize_db(&self, map: &DbMap, list_map: &DbListMap) -> Result<Vec<u8>, String> {
+    fn serialize_db(
+        &self,
+        map: &DbMap,
+        list_map: &DbListMap,
+        expiry_map: &DbExpiryMap,
+        set_map: &DbSetMap,
+        hash_map: &DbHashMap,
+    ) -> Result<Vec<u8>, String> {
         let mut yaml_map: HashMap<&str, &str> = HashMap::new();
         for (key, value) in map.iter() {
-            yaml_map.insert(key, std::str::from_utf8(value).unwrap());
+            yaml_map.insert(key, data_as_str(key, value)?);
         }
 
         let mut yaml_list_map: HashMap<&str, Vec<&str>> = HashMap::new();
         for (key, list) in list_map.iter() {
-            let yaml_list: Vec<&str> = list
+            let yaml_list = list
                 .iter()
-                .map(|item| std::str::from_utf8(item).unwrap())
-                .collect();
+                .map(|item| data_as_str(key, item))
+                .collect::<Result<Vec<_>, _>>()?;
             yaml_list_map.insert(key, yaml_list);
         }
 
-        match serde_yaml::to_string(&(yaml_map, yaml_list_map)) {
+        let mut yaml_set_map: HashMap<&str, Vec<&str>> = HashMap::new();
+        for (key, set) in set_map.iter() {
+            let yaml_set = set
+                .iter()
+                .map(|item| data_as_str(key, item))
+                .collect::<Result<Vec<_>, _>>()?;
+            yaml_set_map.insert(key, yaml_set);
+        }
+
+        let mut yaml_hash_map: HashMap<&str, HashMap<&str, &str>> = HashMap::new();
+        for (key, hash) in hash_map.iter() {
+            let yaml_hash = hash
+                .iter()
+                .map(|(field, value)| Ok((field.as_str(), data_as_str(key, value)?)))
+                .collect::<Result<HashMap<_, _>, String>>()?;
+            yaml_hash_map.insert(key, yaml_hash);
+        }
+
+        // each of the optional maps is written only if it or one of the maps after it isn't empty,
+        // which keeps the file readable by versions that don't support them
+        let ser_db = if !hash_map.is_empty() {
+            serde_yaml::to_string(&(
+                yaml_map,
+                yaml_list_map,
+                expiry_map,
+                yaml_set_map,
+                yaml_hash_map,
+            ))
+        } else if !set_map.is_empty() {
+            serde_yaml::to_string(&(yaml_map, yaml_list_map, expiry_map, yaml_set_map))
+        } else if !expiry_map.is_empty() {
+            serde_yaml::to_string(&(yaml_map, yaml_list_map, expiry_map))
+        } else {
+            serde_yaml::to_string(&(yaml_map, yaml_list_map))
+        };
+
+        match ser_db {
             Ok(ser_db) => Ok(ser_db.into_bytes()),
             Err(err) => Err(err.to_string()),
         }
     }
 
-    fn deserialize_db(&self, ser_db: &[u8]) -> Result<(DbMap, DbListMap), String> {
-        match serde_yaml::from_str::<(HashMap<String, String>, HashMap<String, Vec<String>>)>(
-            std::str::from_utf8(ser_db).unwrap(),
-        ) {
-            Ok((yaml_map, yaml_list_map)) => {
+    fn deserialize_db(&self, ser_db: &[u8]) -> Result<DbMaps, String> {
+        type YamlMap = HashMap<String, String>;
+        type YamlListMap = HashMap<String, Vec<String>>;
+        type YamlHashMap = HashMap<String, HashMap<String, String>>;
+
+        // files without hashes don't have a hash map, files without sets don't have a set map, and
+        // files without TTLs don't have an expiry map
+        let ser_str = match std::str::from_utf8(ser_db) {
+            Ok(ser_str) => ser_str,
+            Err(err) => return Err(err.to_string()),
+        };
+        let maps =
+            serde_yaml::from_str::<(YamlMap, YamlListMap, DbExpiryMap, YamlListMap, YamlHashMap)>(
+                ser_str,
+            )
+            .or_else(|_| {
+                serde_yaml::from_str::<(YamlMap, YamlListMap, DbExpiryMap, YamlListMap)>(ser_str)
+                    .map(|(map, list_map, expiry_map, set_map)| {
+                        (map, list_map, expiry_map, set_map, HashMap::new())
+                    })
+            })
+            .or_else(|_| {
+                serde_yaml::from_str::<(YamlMap, YamlListMap, DbExpiryMap)>(ser_str).map(
+                    |(map, list_map, expiry_map)| {
+                        (map, list_map, expiry_map, HashMap::new(), HashMap::new())
+                    },
+                )
+            })
+            .or_else(|_| {
+                serde_yaml::from_str::<(YamlMap, YamlListMap)>(ser_str).map(|(map, list_map)| {
+                    (
+                        map,
+                        list_map,
+                        HashMap::new(),
+                        HashMap::new(),
+                        HashMap::new(),
+                    )
+                })
+            });
+
+        match maps {
+            Ok((yaml_map, yaml_list_map, expiry_map, yaml_set_map, yaml_hash_map)) => {
                 let mut byte_map: DbMap = HashMap::new();
                 for (key, value) in yaml_map.iter() {
                     byte_map.insert(key.to_string(), value.as_bytes().to_vec());
@@ -182,7 +896,29 @@ impl YamlSerializer {
                     byte_list_map.insert(key.to_string(), byte_list);
                 }
 
-                Ok((byte_map, byte_list_map))
+                let mut byte_set_map: DbSetMap = HashMap::new();
+                for (key, set) in yaml_set_map.iter() {
+                    let byte_set: HashSet<Vec<u8>> =
+                        set.iter().map(|item| item.as_bytes().to_vec()).collect();
+                    byte_set_map.insert(key.to_string(), byte_set);
+                }
+
+                let mut byte_hash_map: DbHashMap = HashMap::new();
+                for (key, hash) in yaml_hash_map.iter() {
+                    let byte_hash: HashMap<String, Vec<u8>> = hash
+                        .iter()
+                        .map(|(field, value)| (field.to_string(), value.as_bytes().to_vec()))
+                        .collect();
+                    byte_hash_map.insert(key.to_string(), byte_hash);
+                }
+
+                Ok((
+                    byte_map,
+                    byte_list_map,
+                    expiry_map,
+                    byte_set_map,
+                    byte_hash_map,
+                ))
             }
 
             Err(err) => Err(err.to_string()),
@@ -199,13 +935,13 @@ impl BincodeSerializer {
         BincodeSerializer {}
     }
 
-    fn deserialize_data<V>(&self, ser_data: &[u8]) -> Option<V>
+    fn deserialize_data<V>(&self, ser_data: &[u8]) -> Result<V, String>
     where
         V: DeserializeOwned,
     {
         match bincode::deserialize(ser_data) {
-            Ok(val) => Some(val),
-            Err(_) => None,
+            Ok(data) => Ok(data),
+            Err(err) => Err(err.to_string()),
         }
     }
 
@@ -219,14 +955,47 @@ impl BincodeSerializer {
         }
     }
 
-    fn serialize_db(&self, map: &DbMap, list_map: &DbListMap) -> Result<Vec<u8>, String> {
-        self.serialize_data(&(map, list_map))
+    fn serialize_db(
+        &self,
+        map: &DbMap,
+        list_map: &DbListMap,
+        expiry_map: &DbExpiryMap,
+        set_map: &DbSetMap,
+        hash_map: &DbHashMap,
+    ) -> Result<Vec<u8>, String> {
+        if !hash_map.is_empty() {
+            self.serialize_data(&(map, list_map, expiry_map, set_map, hash_map))
+        } else if !set_map.is_empty() {
+            self.serialize_data(&(map, list_map, expiry_map, set_map))
+        } else if !expiry_map.is_empty() {
+            self.serialize_data(&(map, list_map, expiry_map))
+        } else {
+            self.serialize_data(&(map, list_map))
+        }
     }
 
-    fn deserialize_db(&self, ser_db: &[u8]) -> Result<(DbMap, DbListMap), String> {
+    fn deserialize_db(&self, ser_db: &[u8]) -> Result<DbMaps, String> {
+        if let Ok(maps) = self.deserialize_data(ser_db) {
+            return Ok(maps);
+        }
+
+        if let Ok((map, list_map, expiry_map, set_map)) = self.deserialize_data(ser_db) {
+            return Ok((map, list_map, expiry_map, set_map, HashMap::new()));
+        }
+
+        if let Ok((map, list_map, expiry_map)) = self.deserialize_data(ser_db) {
+            return Ok((map, list_map, expiry_map, HashMap::new(), HashMap::new()));
+        }
+
         match self.deserialize_data(ser_db) {
-            Some((map, list_map)) => Ok((map, list_map)),
-            None => Err(String::from("Cannot deserialize DB")),
+            Ok((map, list_map)) => Ok((
+                map,
+                list_map,
+                HashMap::new(),
+                HashMap::new(),
+                HashMap::new(),
+            )),
+            Err(_) => Err(String::from("Cannot deserialize DB")),
         }
     }
 }
@@ -240,13 +1009,13 @@ impl CborSerializer {
         CborSerializer {}
     }
 
-    fn deserialize_data<V>(&self, ser_data: &[u8]) -> Option<V>
+    fn deserialize_data<V>(&self, ser_data: &[u8]) -> Result<V, String>
     where
         V: DeserializeOwned,
     {
         match serde_cbor::from_slice(ser_data) {
-            Ok(val) => Some(val),
-            Err(_) => None,
+            Ok(data) => Ok(data),
+            Err(err) => Err(err.to_string()),
         }
     }
 
@@ -260,20 +1029,389 @@ impl CborSerializer {
         }
     }
 
-    fn serialize_db(&self, map: &DbMap, list_map: &DbListMap) -> Result<Vec<u8>, String> {
-        self.serialize_data(&(map, list_map))
+    fn serialize_db(
+        &self,
+        map: &DbMap,
+        list_map: &DbListMap,
+        expiry_map: &DbExpiryMap,
+        set_map: &DbSetMap,
+        hash_map: &DbHashMap,
+    ) -> Result<Vec<u8>, String> {
+        if !hash_map.is_empty() {
+            self.serialize_data(&(map, list_map, expiry_map, set_map, hash_map))
+        } else if !set_map.is_empty() {
+            self.serialize_data(&(map, list_map, expiry_map, set_map))
+        } else if !expiry_map.is_empty() {
+            self.serialize_data(&(map, list_map, expiry_map))
+        } else {
+            self.serialize_data(&(map, list_map))
+        }
     }
 
-    fn deserialize_db(&self, ser_db: &[u8]) -> Result<(DbMap, DbListMap), String> {
+    fn deserialize_db(&self, ser_db: &[u8]) -> Result<DbMaps, String> {
+        if let Ok(maps) = self.deserialize_data(ser_db) {
+            return Ok(maps);
+        }
+
+        if let Ok((map, list_map, expiry_map, set_map)) = self.deserialize_data(ser_db) {
+            return Ok((map, list_map, expiry_map, set_map, HashMap::new()));
+        }
+
+        if let Ok((map, list_map, expiry_map)) = self.deserialize_data(ser_db) {
+            return Ok((map, list_map, expiry_map, HashMap::new(), HashMap::new()));
+        }
+
         match self.deserialize_data(ser_db) {
-            Some((map, list_map)) => Ok((map, list_map)),
-            None => Err(String::from("Cannot deserialize DB")),
+            Ok((map, list_map)) => Ok((
+                map,
+                list_map,
+                HashMap::new(),
+                HashMap::new(),
+                HashMap::new(),
+            )),
+            Err(_) => Err(String::from("Cannot deserialize DB")),
         }
     }
 }
 
+#[cfg(feature = "msgpack")]
+struct MsgPackSerializer {}
+
+#[cfg(feature = "msgpack")]
+impl MsgPackSerializer {
+    fn new() -> MsgPackSerializer {
+        MsgPackSerializer {}
+    }
+
+    fn deserialize_data<V>(&self, ser_data: &[u8]) -> Result<V, String>
+    where
+        V: DeserializeOwned,
+    {
+        match rmp_serde::from_slice(ser_data) {
+            Ok(data) => Ok(data),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+
+    fn serialize_data<V>(&self, data: &V) -> Result<Vec<u8>, String>
+    where
+        V: Serialize,
+    {
+        match rmp_serde::to_vec_named(data) {
+            Ok(ser_data) => Ok(ser_data),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+
+    fn serialize_db(
+        &self,
+        map: &DbMap,
+        list_map: &DbListMap,
+        expiry_map: &DbExpiryMap,
+        set_map: &DbSetMap,
+        hash_map: &DbHashMap,
+    ) -> Result<Vec<u8>, String> {
+        if !hash_map.is_empty() {
+            self.serialize_data(&(map, list_map, expiry_map, set_map, hash_map))
+        } else if !set_map.is_empty() {
+            self.serialize_data(&(map, list_map, expiry_map, set_map))
+        } else if !expiry_map.is_empty() {
+            self.serialize_data(&(map, list_map, expiry_map))
+        } else {
+            self.serialize_data(&(map, list_map))
+        }
+    }
+
+    fn deserialize_db(&self, ser_db: &[u8]) -> Result<DbMaps, String> {
+        if let Ok(maps) = self.deserialize_data(ser_db) {
+            return Ok(maps);
+        }
+
+        if let Ok((map, list_map, expiry_map, set_map)) = self.deserialize_data(ser_db) {
+            return Ok((map, list_map, expiry_map, set_map, HashMap::new()));
+        }
+
+        if let Ok((map, list_map, expiry_map)) = self.deserialize_data(ser_db) {
+            return Ok((map, list_map, expiry_map, HashMap::new(), HashMap::new()));
+        }
+
+        match self.deserialize_data(ser_db) {
+            Ok((map, list_map)) => Ok((
+                map,
+                list_map,
+                HashMap::new(),
+                HashMap::new(),
+                HashMap::new(),
+            )),
+            Err(_) => Err(String::from("Cannot deserialize DB")),
+        }
+    }
+}
+
+/// TOML documents must be tables, so every value is stored as a small document with a single
+/// `value` entry, for example: `value = 100`.
+///
+/// The DB file itself is a table with two sub-tables, which keeps it easy to edit by hand:
+///
+/// ```toml
+/// [values]
+/// key1 = 100
+/// key2 = "my string"
+///
+/// [lists]
+/// list1 = [1, 2, "three"]
+/// ```
+///
+/// If any key has a TTL, a third `[expiry]` sub-table maps these keys to their expiry time, for
+/// example: `key1 = { secs_since_epoch = 1700000000, nanos_since_epoch = 0 }`. This table is
+/// optional, so files without it are loaded with no TTLs.
+///
+/// Similarly, if there are sets, a `[sets]` sub-table maps every set to an array of its members,
+/// for example: `set1 = [1, 2, 3]`, and if there are hashes, a `[hashes]` sub-table maps every hash
+/// to a table of its fields, for example: `hash1 = { field1 = 1, field2 = "two" }`.
+///
+/// This layout is part of the file format and must not change between versions.
+#[cfg(feature = "toml")]
+struct TomlSerializer {}
+
+#[cfg(feature = "toml")]
+impl TomlSerializer {
+    const VALUE_KEY: &'static str = "value";
+    const VALUES_TABLE: &'static str = "values";
+    const LISTS_TABLE: &'static str = "lists";
+    const EXPIRY_TABLE: &'static str = "expiry";
+    const SETS_TABLE: &'static str = "sets";
+    const HASHES_TABLE: &'static str = "hashes";
+
+    fn new() -> TomlSerializer {
+        TomlSerializer {}
+    }
+
+    fn deserialize_data<V>(&self, ser_data: &[u8]) -> Result<V, String>
+    where
+        V: DeserializeOwned,
+    {
+        match self.deserialize_value(ser_data)?.try_into() {
+            Ok(data) => Ok(data),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+
+    fn serialize_data<V>(&self, data: &V) -> Result<Vec<u8>, String>
+    where
+        V: Serialize,
+    {
+        match toml::Value::try_from(data) {
+            Ok(value) => self.serialize_value(value),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+
+    fn serialize_value(&self, value: toml::Value) -> Result<Vec<u8>, String> {
+        let mut table = toml::Table::new();
+        table.insert(String::from(Self::VALUE_KEY), value);
+        match toml::to_string(&table) {
+            Ok(ser_data) => Ok(ser_data.into_bytes()),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+
+    fn deserialize_value(&self, ser_data: &[u8]) -> Result<toml::Value, String> {
+        let ser_str = match std::str::from_utf8(ser_data) {
+            Ok(ser_str) => ser_str,
+            Err(err) => return Err(err.to_string()),
+        };
+        match toml::from_str::<toml::Table>(ser_str) {
+            Ok(mut table) => match table.remove(Self::VALUE_KEY) {
+                Some(value) => Ok(value),
+                None => Err(String::from("Cannot deserialize value")),
+            },
+            Err(err) => Err(err.to_string()),
+        }
+    }
+
+    fn serialize_db(
+        &self,
+        map: &DbMap,
+        list_map: &DbListMap,
+        expiry_map: &DbExpiryMap,
+        set_map: &DbSetMap,
+        hash_map: &DbHashMap,
+    ) -> Result<Vec<u8>, String> {
+        let mut toml_map = toml::Table::new();
+        for (key, value) in map.iter() {
+            toml_map.insert(key.to_string(), self.deserialize_value(value)?);
+        }
+
+        let mut toml_list_map = toml::Table::new();
+        for (key, list) in list_map.iter() {
+            let toml_list = list
+                .iter()
+                .map(|item| self.deserialize_value(item))
+                .collect::<Result<Vec<toml::Value>, String>>()?;
+            toml_list_map.insert(key.to_string(), toml::Value::Array(toml_list));
+        }
+
+        let mut toml_db = toml::Table::new();
+        toml_db.insert(
+            String::from(Self::VALUES_TABLE),
+            toml::Value::Table(toml_map),
+        );
+        toml_db.insert(
+            String::from(Self::LISTS_TABLE),
+            toml::Value::Table(toml_list_map),
+        );
+
+        if !expiry_map.is_empty() {
+            let mut toml_expiry_map = toml::Table::new();
+            for (key, expiry) in expiry_map.iter() {
+                match toml::Value::try_from(expiry) {
+                    Ok(value) => toml_expiry_map.insert(key.to_string(), value),
+                    Err(err) => return Err(err.to_string()),
+                };
+            }
+            toml_db.insert(
+                String::from(Self::EXPIRY_TABLE),
+                toml::Value::Table(toml_expiry_map),
+            );
+        }
+
+        if !set_map.is_empty() {
+            let mut toml_set_map = toml::Table::new();
+            for (key, set) in set_map.iter() {
+                let toml_set = set
+                    .iter()
+                    .map(|item| self.deserialize_value(item))
+                    .collect::<Result<Vec<toml::Value>, String>>()?;
+                toml_set_map.insert(key.to_string(), toml::Value::Array(toml_set));
+            }
+            toml_db.insert(
+                String::from(Self::SETS_TABLE),
+                toml::Value::Table(toml_set_map),
+            );
+        }
+
+        if !hash_map.is_empty() {
+            let mut toml_hash_map = toml::Table::new();
+            for (key, hash) in hash_map.iter() {
+                let mut toml_hash = toml::Table::new();
+                for (field, value) in hash.iter() {
+                    toml_hash.insert(field.to_string(), self.deserialize_value(value)?);
+                }
+                toml_hash_map.insert(key.to_string(), toml::Value::Table(toml_hash));
+            }
+            toml_db.insert(
+                String::from(Self::HASHES_TABLE),
+                toml::Value::Table(toml_hash_map),
+            );
+        }
+
+        match toml::to_string(&toml_db) {
+            Ok(ser_db) => Ok(ser_db.into_bytes()),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+
+    fn deserialize_db(&self, ser_db: &[u8]) -> Result<DbMaps, String> {
+        let ser_str = match std::str::from_utf8(ser_db) {
+            Ok(ser_str) => ser_str,
+            Err(err) => return Err(err.to_string()),
+        };
+        let mut toml_db = match toml::from_str::<toml::Table>(ser_str) {
+            Ok(toml_db) => toml_db,
+            Err(err) => return Err(err.to_string()),
+        };
+
+        let mut byte_map: DbMap = HashMap::new();
+        match toml_db.remove(Self::VALUES_TABLE) {
+            Some(toml::Value::Table(toml_map)) => {
+                for (key, value) in toml_map.into_iter() {
+                    byte_map.insert(key, self.serialize_value(value)?);
+                }
+            }
+            _ => return Err(String::from("Cannot deserialize DB")),
+        }
+
+        let mut byte_list_map: DbListMap = HashMap::new();
+        match toml_db.remove(Self::LISTS_TABLE) {
+            Some(toml::Value::Table(toml_list_map)) => {
+                for (key, list) in toml_list_map.into_iter() {
+                    let byte_list = match list {
+                        toml::Value::Array(toml_list) => toml_list
+                            .into_iter()
+                            .map(|item| self.serialize_value(item))
+                            .collect::<Result<Vec<Vec<u8>>, String>>()?,
+                        _ => return Err(String::from("Cannot deserialize DB")),
+                    };
+                    byte_list_map.insert(key, byte_list);
+                }
+            }
+            _ => return Err(String::from("Cannot deserialize DB")),
+        }
+
+        let mut expiry_map: DbExpiryMap = HashMap::new();
+        match toml_db.remove(Self::EXPIRY_TABLE) {
+            Some(toml::Value::Table(toml_expiry_map)) => {
+                for (key, expiry) in toml_expiry_map.into_iter() {
+                    match expiry.try_into() {
+                        Ok(expiry) => expiry_map.insert(key, expiry),
+                        Err(err) => return Err(err.to_string()),
+                    };
+                }
+            }
+            None => (),
+            _ => return Err(String::from("Cannot deserialize DB")),
+        }
+
+        let mut byte_set_map: DbSetMap = HashMap::new();
+        match toml_db.remove(Self::SETS_TABLE) {
+            Some(toml::Value::Table(toml_set_map)) => {
+                for (key, set) in toml_set_map.into_iter() {
+                    let byte_set = match set {
+                        toml::Value::Array(toml_set) => toml_set
+                            .into_iter()
+                            .map(|item| self.serialize_value(item))
+                            .collect::<Result<HashSet<Vec<u8>>, String>>()?,
+                        _ => return Err(String::from("Cannot deserialize DB")),
+                    };
+                    byte_set_map.insert(key, byte_set);
+                }
+            }
+            None => (),
+            _ => return Err(String::from("Cannot deserialize DB")),
+        }
+
+        let mut byte_hash_map: DbHashMap = HashMap::new();
+        match toml_db.remove(Self::HASHES_TABLE) {
+            Some(toml::Value::Table(toml_hash_map)) => {
+                for (key, hash) in toml_hash_map.into_iter() {
+                    let byte_hash = match hash {
+                        toml::Value::Table(toml_hash) => toml_hash
+                            .into_iter()
+                            .map(|(field, value)| Ok((field, self.serialize_value(value)?)))
+                            .collect::<Result<HashMap<String, Vec<u8>>, String>>()?,
+                        _ => return Err(String::from("Cannot deserialize DB")),
+                    };
+                    byte_hash_map.insert(key, byte_hash);
+                }
+            }
+            None => (),
+            _ => return Err(String::from("Cannot deserialize DB")),
+        }
+
+        Ok((
+            byte_map,
+            byte_list_map,
+            expiry_map,
+            byte_set_map,
+            byte_hash_map,
+        ))
+    }
+}
+
 pub(crate) struct Serializer {
     ser_method: SerializationMethod,
+    custom_serializer: Option<Arc<dyn PickleDbSerializer>>,
     #[cfg(feature = "json")]
     json_serializer: JsonSerializer,
     #[cfg(feature = "bincode")]
@@ -282,12 +1420,27 @@ pub(crate) struct Serializer {
     yaml_serializer: YamlSerializer,
     #[cfg(feature = "cbor")]
     cbor_serializer: CborSerializer,
+    #[cfg(feature = "msgpack")]
+    msgpack_serializer: MsgPackSerializer,
+    #[cfg(feature = "toml")]
+    toml_serializer: TomlSerializer,
+}
+
+impl Clone for Serializer {
+    fn clone(&self) -> Serializer {
+        let mut serializer = Serializer::new(self.ser_method);
+        serializer.custom_serializer = self.custom_serializer.clone();
+        #[cfg(feature = "json")]
+        serializer.set_pretty_json(self.json_serializer.pretty);
+        serializer
+    }
 }
 
 impl Serializer {
     pub(crate) fn new(ser_method: SerializationMethod) -> Serializer {
         Serializer {
             ser_method,
+            custom_serializer: None,
             #[cfg(feature = "json")]
             json_serializer: JsonSerializer::new(),
             #[cfg(feature = "bincode")]
@@ -296,6 +1449,48 @@ impl Serializer {
             yaml_serializer: YamlSerializer::new(),
             #[cfg(feature = "cbor")]
             cbor_serializer: CborSerializer::new(),
+            #[cfg(feature = "msgpack")]
+            msgpack_serializer: MsgPackSerializer::new(),
+            #[cfg(feature = "toml")]
+            toml_serializer: TomlSerializer::new(),
+        }
+    }
+
+    pub(crate) fn new_custom(custom_serializer: Arc<dyn PickleDbSerializer>) -> Serializer {
+        let mut serializer = Serializer::new(SerializationMethod::Custom);
+        serializer.custom_serializer = Some(custom_serializer);
+        serializer
+    }
+
+    pub(crate) fn ser_method(&self) -> SerializationMethod {
+        self.ser_method
+    }
+
+    // whether the serialized data is plain text. The data of a custom serializer may be text too,
+    // so it's treated as text as well
+    pub(crate) fn is_text(&self) -> bool {
+        !matches!(
+            self.ser_method,
+            SerializationMethod::Bin | SerializationMethod::Cbor | SerializationMethod::MsgPack
+        )
+    }
+
+    #[cfg(feature = "json")]
+    pub(crate) fn set_pretty_json(&mut self, pretty: bool) {
+        self.json_serializer.pretty = pretty;
+    }
+
+    // the error of a serialization method whose feature isn't enabled, which would otherwise be
+    // silently replaced with a different serialization method
+    fn disabled_method_error(&self) -> String {
+        match self.ser_method {
+            SerializationMethod::Custom => String::from(
+                "The Custom serialization method can only be used with a custom serializer",
+            ),
+            ser_method => format!(
+                "The {} serialization method isn't enabled, please enable its feature",
+                ser_method
+            ),
         }
     }
 
@@ -303,7 +1498,17 @@ impl Serializer {
     where
         V: DeserializeOwned,
     {
-        #[allow(unreachable_patterns)]
+        self.deserialize_data_result(ser_data).ok()
+    }
+
+    pub(crate) fn deserialize_data_result<V>(&self, ser_data: &[u8]) -> Result<V, String>
+    where
+        V: DeserializeOwned,
+    {
+        if let Some(custom_serializer) = &self.custom_serializer {
+            return deserialize_custom(custom_serializer.as_ref(), ser_data);
+        }
+
         match self.ser_method {
             #[cfg(feature = "json")]
             SerializationMethod::Json => self.json_serializer.deserialize_data(ser_data),
@@ -313,14 +1518,11 @@ impl Serializer {
             SerializationMethod::Yaml => self.yaml_serializer.deserialize_data(ser_data),
             #[cfg(feature = "cbor")]
             SerializationMethod::Cbor => self.cbor_serializer.deserialize_data(ser_data),
-            #[cfg(feature = "json")]
-            _ => self.json_serializer.deserialize_data(ser_data),
-            #[cfg(feature = "bincode")]
-            _ => self.bincode_serializer.deserialize_data(ser_data),
-            #[cfg(feature = "yaml")]
-            _ => self.yaml_serializer.deserialize_data(ser_data),
-            #[cfg(feature = "cbor")]
-            _ => self.cbor_serializer.deserialize_data(ser_data),
+            #[cfg(feature = "msgpack")]
+            SerializationMethod::MsgPack => self.msgpack_serializer.deserialize_data(ser_data),
+            #[cfg(feature = "toml")]
+            SerializationMethod::Toml => self.toml_serializer.deserialize_data(ser_data),
+            _ => Err(self.disabled_method_error()),
         }
     }
 
@@ -328,7 +1530,10 @@ impl Serializer {
     where
         V: Serialize,
     {
-        #[allow(unreachable_patterns)]
+        if let Some(custom_serializer) = &self.custom_serializer {
+            return custom_serializer.serialize_data(data);
+        }
+
         match self.ser_method {
             #[cfg(feature = "json")]
             SerializationMethod::Json => self.json_serializer.serialize_data(data),
@@ -338,14 +1543,11 @@ impl Serializer {
             SerializationMethod::Yaml => self.yaml_serializer.serialize_data(data),
             #[cfg(feature = "cbor")]
             SerializationMethod::Cbor => self.cbor_serializer.serialize_data(data),
-            #[cfg(feature = "json")]
-            _ => self.json_serializer.serialize_data(data),
-            #[cfg(feature = "bincode")]
-            _ => self.bincode_serializer.serialize_data(data),
-            #[cfg(feature = "yaml")]
-            _ => self.yaml_serializer.serialize_data(data),
-            #[cfg(feature = "cbor")]
-            _ => self.cbor_serializer.serialize_data(data),
+            #[cfg(feature = "msgpack")]
+            SerializationMethod::MsgPack => self.msgpack_serializer.serialize_data(data),
+            #[cfg(feature = "toml")]
+            SerializationMethod::Toml => self.toml_serializer.serialize_data(data),
+            _ => Err(self.disabled_method_error()),
         }
     }
 
@@ -353,30 +1555,48 @@ impl Serializer {
         &self,
         map: &DbMap,
         list_map: &DbListMap,
+        expiry_map: &DbExpiryMap,
+        set_map: &DbSetMap,
+        hash_map: &DbHashMap,
     ) -> Result<Vec<u8>, String> {
-        #[allow(unreachable_patterns)]
+        if let Some(custom_serializer) = &self.custom_serializer {
+            return custom_serializer.serialize_db(map, list_map, expiry_map, set_map, hash_map);
+        }
+
         match self.ser_method {
             #[cfg(feature = "json")]
-            SerializationMethod::Json => self.json_serializer.serialize_db(map, list_map),
-            #[cfg(feature = "bincode")]
-            SerializationMethod::Bin => self.bincode_serializer.serialize_db(map, list_map),
-            #[cfg(feature = "yaml")]
-            SerializationMethod::Yaml => self.yaml_serializer.serialize_db(map, list_map),
-            #[cfg(feature = "cbor")]
-            SerializationMethod::Cbor => self.cbor_serializer.serialize_db(map, list_map),
-            #[cfg(feature = "json")]
-            _ => self.json_serializer.serialize_db(map, list_map),
+            SerializationMethod::Json => self
+                .json_serializer
+                .serialize_db(map, list_map, expiry_map, set_map, hash_map),
             #[cfg(feature = "bincode")]
-            _ => self.bincode_serializer.serialize_db(map, list_map),
+            SerializationMethod::Bin => self
+                .bincode_serializer
+                .serialize_db(map, list_map, expiry_map, set_map, hash_map),
             #[cfg(feature = "yaml")]
-            _ => self.yaml_serializer.serialize_db(map, list_map),
+            SerializationMethod::Yaml => self
+                .yaml_serializer
+                .serialize_db(map, list_map, expiry_map, set_map, hash_map),
             #[cfg(feature = "cbor")]
-            _ => self.cbor_serializer.serialize_db(map, list_map),
+            SerializationMethod::Cbor => self
+                .cbor_serializer
+                .serialize_db(map, list_map, expiry_map, set_map, hash_map),
+            #[cfg(feature = "msgpack")]
+            SerializationMethod::MsgPack => self
+                .msgpack_serializer
+                .serialize_db(map, list_map, expiry_map, set_map, hash_map),
+            #[cfg(feature = "toml")]
+            SerializationMethod::Toml => self
+                .toml_serializer
+                .serialize_db(map, list_map, expiry_map, set_map, hash_map),
+            _ => Err(self.disabled_method_error()),
         }
     }
 
-    pub(crate) fn deserialize_db(&self, ser_db: &[u8]) -> Result<(DbMap, DbListMap), String> {
-        #[allow(unreachable_patterns)]
+    pub(crate) fn deserialize_db(&self, ser_db: &[u8]) -> Result<DbMaps, String> {
+        if let Some(custom_serializer) = &self.custom_serializer {
+            return custom_serializer.deserialize_db(ser_db);
+        }
+
         match self.ser_method {
             #[cfg(feature = "json")]
             SerializationMethod::Json => self.json_serializer.deserialize_db(ser_db),
@@ -386,14 +1606,11 @@ impl Serializer {
             SerializationMethod::Yaml => self.yaml_serializer.deserialize_db(ser_db),
             #[cfg(feature = "cbor")]
             SerializationMethod::Cbor => self.cbor_serializer.deserialize_db(ser_db),
-            #[cfg(feature = "json")]
-            _ => self.json_serializer.deserialize_db(ser_db),
-            #[cfg(feature = "bincode")]
-            _ => self.bincode_serializer.deserialize_db(ser_db),
-            #[cfg(feature = "yaml")]
-            _ => self.yaml_serializer.deserialize_db(ser_db),
-            #[cfg(feature = "cbor")]
-            _ => self.cbor_serializer.deserialize_db(ser_db),
+            #[cfg(feature = "msgpack")]
+            SerializationMethod::MsgPack => self.msgpack_serializer.deserialize_db(ser_db),
+            #[cfg(feature = "toml")]
+            SerializationMethod::Toml => self.toml_serializer.deserialize_db(ser_db),
+            _ => Err(self.disabled_method_error()),
         }
     }
 }
@@ -0,0 +1,202 @@
+use serde::{de::DeserializeOwned, Serialize};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::error::Result;
+use crate::pickledb::PickleDb;
+
+/// A thread-safe wrapper around [PickleDb](struct.PickleDb.html) that can be shared between threads.
+///
+/// This object holds the DB in an `Arc<Mutex<PickleDb>>`, so cloning it is cheap and all clones
+/// refer to the same DB. Each method locks the DB for the duration of a single operation, calls the
+/// matching `PickleDb` method and releases the lock. This means each operation is atomic, but a sequence
+/// of operations isn't: another thread may change the DB between two calls. For running a few operations
+/// atomically, or for calling methods that aren't available on this wrapper (such as iterating over the DB),
+/// use [lock()](#method.lock) and hold the returned guard for as long as needed.
+///
+/// Values are always returned as owned objects (the same as in `PickleDb`, since the data is stored
+/// serialized), so no reference to the DB data outlives the lock. For the same reason, the list methods
+/// that return a [PickleDbListExtender](struct.PickleDbListExtender.html) in `PickleDb` return a simple
+/// success indication here.
+///
+/// If a thread panics while holding the lock the DB can still be used by the other threads.
+///
+/// # Examples
+///
+/// ```no_run
+/// use pickledb::{PickleDb, PickleDbDumpPolicy, SyncPickleDb};
+/// use std::thread;
+///
+/// let db = SyncPickleDb::new(PickleDb::new_json("example.db", PickleDbDumpPolicy::AutoDump));
+///
+/// let handles: Vec<_> = (0..4)
+///     .map(|i| {
+///         let db = db.clone();
+///         thread::spawn(move || db.set(&format!("key{}", i), &i).unwrap())
+///     })
+///     .collect();
+///
+/// for handle in handles {
+///     handle.join().unwrap();
+/// }
+///
+/// assert_eq!(db.total_keys(), 4);
+/// ```
+///
+#[derive(Clone)]
+pub struct SyncPickleDb {
+    db: Arc<Mutex<PickleDb>>,
+}
+
+impl SyncPickleDb {
+    /// Constructs a new `SyncPickleDb` that wraps an existing `PickleDb` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - the DB to wrap, which can be either a new DB or a DB that was loaded from a file
+    ///
+    pub fn new(db: PickleDb) -> SyncPickleDb {
+        SyncPickleDb {
+            db: Arc::new(Mutex::new(db)),
+        }
+    }
+
+    /// Lock the DB and get access to the underlying `PickleDb` instance.
+    ///
+    /// Other threads are blocked from accessing the DB until the returned guard is dropped.
+    ///
+    pub fn lock(&self) -> MutexGuard<'_, PickleDb> {
+        // the DB is never left in an inconsistent state, so it's safe to keep using it after a panic
+        match self.db.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Dump the data to the file. See [PickleDb::dump()](struct.PickleDb.html#method.dump).
+    ///
+    pub fn dump(&self) -> Result<()> {
+        self.lock().dump()
+    }
+
+    /// Set a key-value pair. See [PickleDb::set()](struct.PickleDb.html#method.set).
+    ///
+    pub fn set<V>(&self, key: &str, value: &V) -> Result<()>
+    where
+        V: Serialize,
+    {
+        self.lock().set(key, value)
+    }
+
+    /// Get a value of a key. See [PickleDb::get()](struct.PickleDb.html#method.get).
+    ///
+    pub fn get<V>(&self, key: &str) -> Option<V>
+    where
+        V: DeserializeOwned,
+    {
+        self.lock().get(key)
+    }
+
+    /// Check if a key exists. See [PickleDb::exists()](struct.PickleDb.html#method.exists).
+    ///
+    pub fn exists(&self, key: &str) -> bool {
+        self.lock().exists(key)
+    }
+
+    /// Get a vector of all the keys in the DB. See [PickleDb::get_all()](struct.PickleDb.html#method.get_all).
+    ///
+    pub fn get_all(&self) -> Vec<String> {
+        self.lock().get_all()
+    }
+
+    /// Get the total number of keys in the DB. See [PickleDb::total_keys()](struct.PickleDb.html#method.total_keys).
+    ///
+    pub fn total_keys(&self) -> usize {
+        self.lock().total_keys()
+    }
+
+    /// Remove a key-value pair or a list from the DB. See [PickleDb::rem()](struct.PickleDb.html#method.rem).
+    ///
+    pub fn rem(&self, key: &str) -> Result<bool> {
+        self.lock().rem(key)
+    }
+
+    /// Create a new list. See [PickleDb::lcreate()](struct.PickleDb.html#method.lcreate).
+    ///
+    pub fn lcreate(&self, name: &str) -> Result<()> {
+        self.lock().lcreate(name).map(|_| ())
+    }
+
+    /// Check if a list exists. See [PickleDb::lexists()](struct.PickleDb.html#method.lexists).
+    ///
+    pub fn lexists(&self, name: &str) -> bool {
+        self.lock().lexists(name)
+    }
+
+    /// Add a single item to an existing list. See [PickleDb::ladd()](struct.PickleDb.html#method.ladd).
+    ///
+    /// This method returns `true` if the item was added or `false` if the list isn't found or if adding it failed.
+    ///
+    pub fn ladd<V>(&self, name: &str, value: &V) -> bool
+    where
+        V: Serialize,
+    {
+        self.lock().ladd(name, value).is_some()
+    }
+
+    /// Add multiple items to an existing list. See [PickleDb::lextend()](struct.PickleDb.html#method.lextend).
+    ///
+    /// This method returns `true` if the items were added or `false` if the list isn't found or if adding them failed.
+    ///
+    pub fn lextend<'a, V, I>(&self, name: &str, seq: I) -> bool
+    where
+        V: 'a + Serialize,
+        I: IntoIterator<Item = &'a V>,
+    {
+        self.lock().lextend(name, seq).is_some()
+    }
+
+    /// Get an item of a list in a certain position. See [PickleDb::lget()](struct.PickleDb.html#method.lget).
+    ///
+    pub fn lget<V>(&self, name: &str, pos: usize) -> Option<V>
+    where
+        V: DeserializeOwned,
+    {
+        self.lock().lget(name, pos)
+    }
+
+    /// Get the length of a list. See [PickleDb::llen()](struct.PickleDb.html#method.llen).
+    ///
+    pub fn llen(&self, name: &str) -> usize {
+        self.lock().llen(name)
+    }
+
+    /// Remove a list. See [PickleDb::lrem_list()](struct.PickleDb.html#method.lrem_list).
+    ///
+    pub fn lrem_list(&self, name: &str) -> Result<usize> {
+        self.lock().lrem_list(name)
+    }
+
+    /// Pop an item out of a list. See [PickleDb::lpop()](struct.PickleDb.html#method.lpop).
+    ///
+    pub fn lpop<V>(&self, name: &str, pos: usize) -> Option<V>
+    where
+        V: DeserializeOwned,
+    {
+        self.lock().lpop(name, pos)
+    }
+
+    /// Remove an item out of a list. See [PickleDb::lrem_value()](struct.PickleDb.html#method.lrem_value).
+    ///
+    pub fn lrem_value<V>(&self, name: &str, value: &V) -> Result<bool>
+    where
+        V: Serialize,
+    {
+        self.lock().lrem_value(name, value)
+    }
+}
+
+impl From<PickleDb> for SyncPickleDb {
+    fn from(db: PickleDb) -> Self {
+        SyncPickleDb::new(db)
+    }
+}
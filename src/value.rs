@@ -0,0 +1,102 @@
+use serde::{Deserialize, Serialize};
+
+/// A tagged value that can be stored in PickleDB lists (or as a value of a key).
+///
+/// Lists in PickleDB are heterogeneous, but reading an item requires knowing its type in advance:
+/// reading it with the wrong type simply returns `None`. Items that are stored as `PickleValue`
+/// (for example using [PickleDb::ladd_value()](struct.PickleDb.html#method.ladd_value)) are stored together
+/// with their variant, so they can be read back without knowing their type and then matched on.
+///
+/// Please note that a `PickleValue` is stored differently than the plain value it holds, so items that
+/// were stored as `PickleValue` should also be read as `PickleValue`
+/// (for example using [PickleDb::lget_value()](struct.PickleDb.html#method.lget_value)) and vice versa.
+///
+/// # Examples
+///
+/// ```no_run
+/// use pickledb::PickleValue;
+///
+/// # let mut db = pickledb::PickleDb::new_bin("1.db", pickledb::PickleDbDumpPolicy::AutoDump);
+/// db.lcreate("list1").unwrap()
+///   .ladd_value(&PickleValue::from(100))
+///   .ladd_value(&PickleValue::from("my string"));
+///
+/// for item in db.liter("list1") {
+///     match item.get_item::<PickleValue>() {
+///         Some(PickleValue::Int(num)) => println!("A number: {}", num),
+///         Some(PickleValue::String(s)) => println!("A string: {}", s),
+///         _ => println!("Something else"),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PickleValue {
+    /// No value, such as `None` or `()`
+    Null,
+
+    /// A boolean
+    Bool(bool),
+
+    /// An integer
+    Int(i64),
+
+    /// A floating point number
+    Float(f64),
+
+    /// A string
+    String(String),
+
+    /// A sequence of values, such as a `Vec` or a tuple
+    List(Vec<PickleValue>),
+}
+
+impl From<bool> for PickleValue {
+    fn from(item: bool) -> Self {
+        PickleValue::Bool(item)
+    }
+}
+
+impl From<i32> for PickleValue {
+    fn from(item: i32) -> Self {
+        PickleValue::Int(i64::from(item))
+    }
+}
+
+impl From<i64> for PickleValue {
+    fn from(item: i64) -> Self {
+        PickleValue::Int(item)
+    }
+}
+
+impl From<f64> for PickleValue {
+    fn from(item: f64) -> Self {
+        PickleValue::Float(item)
+    }
+}
+
+impl From<&str> for PickleValue {
+    fn from(item: &str) -> Self {
+        PickleValue::String(String::from(item))
+    }
+}
+
+impl From<String> for PickleValue {
+    fn from(item: String) -> Self {
+        PickleValue::String(item)
+    }
+}
+
+impl<V: Into<PickleValue>> From<Vec<V>> for PickleValue {
+    fn from(item: Vec<V>) -> Self {
+        PickleValue::List(item.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<V: Into<PickleValue>> From<Option<V>> for PickleValue {
+    fn from(item: Option<V>) -> Self {
+        match item {
+            Some(value) => value.into(),
+            None => PickleValue::Null,
+        }
+    }
+}
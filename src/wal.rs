@@ -0,0 +1,243 @@
+use std::collections::{HashMap, HashSet};
+use std::convert::TryInto;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// The write-ahead log starts with a header that identifies the DB file it was written on top of:
+//
+// | magic | base file CRC32 (4 bytes) | base file length (8 bytes) |
+//
+// A log whose header doesn't match the DB file is stale, for example if the process died after a full
+// dump replaced the DB file but before the log was removed, so its records must not be applied.
+//
+// The header is followed by a sequence of records, each one holds the current state of a single key:
+//
+// | op (1 byte) | key length (4 bytes) | key | op specific data |
+//
+// - SET_VALUE: | has expiry (1 byte) | expiry secs (8 bytes) | expiry nanos (4 bytes) | value length (4 bytes) | value |
+// - SET_LIST: | items count (4 bytes) | (item length (4 bytes) | item) * items count |
+// - SET_SET: same as SET_LIST, with the members of the set as items
+// - SET_HASH: | fields count (4 bytes) | (field length (4 bytes) | field | value length (4 bytes) | value) * fields count |
+// - REMOVE: no data
+//
+// All numbers are little-endian. Values and list items are stored exactly as they're kept in
+// memory, i.e. serialized with the serialization method of the DB. Set members and hash field values
+// are stored the same way.
+const SET_VALUE: u8 = 0;
+const SET_LIST: u8 = 1;
+const REMOVE: u8 = 2;
+const SET_SET: u8 = 3;
+const SET_HASH: u8 = 4;
+
+const WAL_MAGIC: &[u8] = b"PICKLEDB-WAL ";
+const WAL_HEADER_LEN: usize = WAL_MAGIC.len() + 4 + 8;
+
+pub(crate) fn wal_path(db_path: &Path) -> PathBuf {
+    let mut wal_file_name = db_path.file_name().unwrap_or_default().to_os_string();
+    wal_file_name.push(".wal");
+    db_path.with_file_name(wal_file_name)
+}
+
+pub(crate) fn encode_header(buf: &mut Vec<u8>, base_crc: u32, base_len: u64) {
+    buf.extend_from_slice(WAL_MAGIC);
+    buf.extend_from_slice(&base_crc.to_le_bytes());
+    buf.extend_from_slice(&base_len.to_le_bytes());
+}
+
+// Get the records of the log, or None if the log wasn't written on top of the DB file with the given
+// CRC32 and length (or if its header is invalid)
+pub(crate) fn records_for_base(data: &[u8], base_crc: u32, base_len: u64) -> Option<&[u8]> {
+    let mut header = Vec::with_capacity(WAL_HEADER_LEN);
+    encode_header(&mut header, base_crc, base_len);
+    if data.starts_with(&header) {
+        Some(&data[WAL_HEADER_LEN..])
+    } else {
+        None
+    }
+}
+
+pub(crate) fn encode_record(
+    buf: &mut Vec<u8>,
+    key: &str,
+    value: Option<&Vec<u8>>,
+    list: Option<&Vec<Vec<u8>>>,
+    set: Option<&HashSet<Vec<u8>>>,
+    hash: Option<&HashMap<String, Vec<u8>>>,
+    expiry: Option<&SystemTime>,
+) {
+    match (value, list, set, hash) {
+        (Some(value), _, _, _) => {
+            encode_key(buf, SET_VALUE, key);
+            match expiry.and_then(|expiry| expiry.duration_since(UNIX_EPOCH).ok()) {
+                Some(expiry) => {
+                    buf.push(1);
+                    buf.extend_from_slice(&expiry.as_secs().to_le_bytes());
+                    buf.extend_from_slice(&expiry.subsec_nanos().to_le_bytes());
+                }
+                None => {
+                    buf.push(0);
+                    buf.extend_from_slice(&[0; 12]);
+                }
+            }
+            encode_bytes(buf, value);
+        }
+        (None, Some(list), _, _) => {
+            encode_key(buf, SET_LIST, key);
+            buf.extend_from_slice(&(list.len() as u32).to_le_bytes());
+            for item in list.iter() {
+                encode_bytes(buf, item);
+            }
+        }
+        (None, None, Some(set), _) => {
+            encode_key(buf, SET_SET, key);
+            buf.extend_from_slice(&(set.len() as u32).to_le_bytes());
+            for item in set.iter() {
+                encode_bytes(buf, item);
+            }
+        }
+        (None, None, None, Some(hash)) => {
+            encode_key(buf, SET_HASH, key);
+            buf.extend_from_slice(&(hash.len() as u32).to_le_bytes());
+            for (field, value) in hash.iter() {
+                encode_bytes(buf, field.as_bytes());
+                encode_bytes(buf, value);
+            }
+        }
+        (None, None, None, None) => encode_key(buf, REMOVE, key),
+    }
+}
+
+fn encode_key(buf: &mut Vec<u8>, op: u8, key: &str) {
+    buf.push(op);
+    encode_bytes(buf, key.as_bytes());
+}
+
+fn encode_bytes(buf: &mut Vec<u8>, bytes: &[u8]) {
+    buf.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+    buf.extend_from_slice(bytes);
+}
+
+// Apply all the records of the log to the maps. Returns false if the log ends with a partial or
+// invalid record (for example if a crash happened in the middle of writing it), in which case
+// all the records before it are still applied
+pub(crate) fn replay(
+    data: &[u8],
+    map: &mut HashMap<String, Vec<u8>>,
+    list_map: &mut HashMap<String, Vec<Vec<u8>>>,
+    expiry_map: &mut HashMap<String, SystemTime>,
+    set_map: &mut HashMap<String, HashSet<Vec<u8>>>,
+    hash_map: &mut HashMap<String, HashMap<String, Vec<u8>>>,
+) -> bool {
+    let mut reader = RecordReader { data, pos: 0 };
+    while reader.pos < data.len() {
+        if reader
+            .apply_record(map, list_map, expiry_map, set_map, hash_map)
+            .is_none()
+        {
+            return false;
+        }
+    }
+    true
+}
+
+struct RecordReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> RecordReader<'a> {
+    fn apply_record(
+        &mut self,
+        map: &mut HashMap<String, Vec<u8>>,
+        list_map: &mut HashMap<String, Vec<Vec<u8>>>,
+        expiry_map: &mut HashMap<String, SystemTime>,
+        set_map: &mut HashMap<String, HashSet<Vec<u8>>>,
+        hash_map: &mut HashMap<String, HashMap<String, Vec<u8>>>,
+    ) -> Option<()> {
+        let op = self.read(1)?[0];
+        let key = String::from_utf8(self.read_bytes()?.to_vec()).ok()?;
+        match op {
+            SET_VALUE => {
+                let has_expiry = self.read(1)?[0] == 1;
+                let secs = u64::from_le_bytes(self.read(8)?.try_into().ok()?);
+                let nanos = u32::from_le_bytes(self.read(4)?.try_into().ok()?);
+                let value = self.read_bytes()?.to_vec();
+
+                list_map.remove(&key);
+                set_map.remove(&key);
+                hash_map.remove(&key);
+                if has_expiry {
+                    expiry_map.insert(key.clone(), UNIX_EPOCH + Duration::new(secs, nanos));
+                } else {
+                    expiry_map.remove(&key);
+                }
+                map.insert(key, value);
+            }
+            SET_LIST => {
+                let count = self.read_u32()?;
+                let mut list = Vec::new();
+                for _ in 0..count {
+                    list.push(self.read_bytes()?.to_vec());
+                }
+
+                map.remove(&key);
+                expiry_map.remove(&key);
+                set_map.remove(&key);
+                hash_map.remove(&key);
+                list_map.insert(key, list);
+            }
+            SET_SET => {
+                let count = self.read_u32()?;
+                let mut set = HashSet::new();
+                for _ in 0..count {
+                    set.insert(self.read_bytes()?.to_vec());
+                }
+
+                map.remove(&key);
+                list_map.remove(&key);
+                expiry_map.remove(&key);
+                hash_map.remove(&key);
+                set_map.insert(key, set);
+            }
+            SET_HASH => {
+                let count = self.read_u32()?;
+                let mut hash = HashMap::new();
+                for _ in 0..count {
+                    let field = String::from_utf8(self.read_bytes()?.to_vec()).ok()?;
+                    hash.insert(field, self.read_bytes()?.to_vec());
+                }
+
+                map.remove(&key);
+                list_map.remove(&key);
+                expiry_map.remove(&key);
+                set_map.remove(&key);
+                hash_map.insert(key, hash);
+            }
+            REMOVE => {
+                map.remove(&key);
+                list_map.remove(&key);
+                expiry_map.remove(&key);
+                set_map.remove(&key);
+                hash_map.remove(&key);
+            }
+            _ => return None,
+        }
+        Some(())
+    }
+
+    fn read(&mut self, len: usize) -> Option<&'a [u8]> {
+        let end = self.pos.checked_add(len)?;
+        let bytes = self.data.get(self.pos..end)?;
+        self.pos = end;
+        Some(bytes)
+    }
+
+    fn read_u32(&mut self) -> Option<u32> {
+        Some(u32::from_le_bytes(self.read(4)?.try_into().ok()?))
+    }
+
+    fn read_bytes(&mut self) -> Option<&'a [u8]> {
+        let len = self.read_u32()? as usize;
+        self.read(len)
+    }
+}
@@ -1,9 +1,15 @@
-use pickledb::{PickleDb, PickleDbDumpPolicy, SerializationMethod};
+use pickledb::erased_serde;
+use pickledb::{
+    ChangeEvent, PickleDb, PickleDbBuilder, PickleDbDumpPolicy, PickleDbMergePolicy,
+    PickleDbSerializer, SerializationMethod,
+};
 use rand::distributions::Alphanumeric;
 use rand::seq::SliceRandom;
 use rand::{thread_rng, Rng};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::iter;
+use std::sync::{Arc, Mutex};
 
 mod common;
 
@@ -12,7 +18,7 @@ extern crate rstest;
 
 use rstest::rstest_parametrize;
 
-#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3))]
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
 fn lists_and_values(ser_method_int: i32) {
     test_setup!("lists_and_values", ser_method_int, db_name);
 
@@ -75,7 +81,7 @@ fn gen_random_string<T: Rng>(rng: &mut T, size: usize) -> String {
 }
 
 #[allow(clippy::cognitive_complexity)]
-#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3))]
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
 fn load_test(ser_method_int: i32) {
     test_setup!("load_test", ser_method_int, db_name);
 
@@ -230,3 +236,835 @@ fn load_test(ser_method_int: i32) {
     // check that the total number of keys in db equals to number of keys generated
     assert_eq!(read_db.total_keys(), generate_keys);
 }
+
+#[test]
+fn toml_hand_edited_file_test() {
+    set_test_rsc!("toml_hand_edited_file_test.db");
+
+    // write a TOML file like a user would write it by hand
+    std::fs::write(
+        "toml_hand_edited_file_test.db",
+        "[values]\nnum = 100\nstring = \"my string\"\n\n[lists]\nlist1 = [1, \"two\", 3.5]\n",
+    )
+    .unwrap();
+
+    // load it and verify all values and lists are read correctly
+    let mut db = PickleDb::load_toml(
+        "toml_hand_edited_file_test.db",
+        PickleDbDumpPolicy::AutoDump,
+    )
+    .unwrap();
+    assert_eq!(db.get::<i32>("num").unwrap(), 100);
+    assert_eq!(db.get::<String>("string").unwrap(), "my string");
+    assert_eq!(db.llen("list1"), 3);
+    assert_eq!(db.lget::<i32>("list1", 0).unwrap(), 1);
+    assert_eq!(db.lget::<String>("list1", 1).unwrap(), "two");
+    assert_eq!(db.lget::<f64>("list1", 2).unwrap(), 3.5);
+
+    // values that were loaded from the file and values that were set by the user should be equal
+    assert!(db.lrem_value("list1", &String::from("two")).unwrap());
+
+    // dump the DB and verify the file keeps the same layout
+    db.set("num", &200).unwrap();
+    let content = std::fs::read_to_string("toml_hand_edited_file_test.db").unwrap();
+    assert!(content.contains("[values]"));
+    assert!(content.contains("num = 200"));
+    assert!(content.contains("[lists]"));
+    assert!(content.contains("list1 = [1, 3.5]"));
+
+    // the dumped file is still a plain TOML file, so it can be edited again
+    std::fs::write(
+        "toml_hand_edited_file_test.db",
+        content.replace("num = 200", "num = 300"),
+    )
+    .unwrap();
+    let db = PickleDb::load_toml(
+        "toml_hand_edited_file_test.db",
+        PickleDbDumpPolicy::NeverDump,
+    )
+    .unwrap();
+    assert_eq!(db.get::<i32>("num").unwrap(), 300);
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn compression_test(ser_method_int: i32) {
+    test_setup!("compression_test", ser_method_int, db_name);
+    let plain_db_name = format!("plain_{}", db_name);
+    set_test_rsc!(&plain_db_name);
+
+    // create a compressed DB and a plain DB with the same repetitive data
+    let mut db = PickleDb::new_with_compression(
+        &db_name,
+        PickleDbDumpPolicy::DumpUponRequest,
+        ser_method!(ser_method_int),
+    );
+    let mut plain_db = PickleDb::new(
+        &plain_db_name,
+        PickleDbDumpPolicy::DumpUponRequest,
+        ser_method!(ser_method_int),
+    );
+    for i in 0..100 {
+        let key = format!("some_long_repetitive_key_{}", i);
+        db.set(&key, &String::from("some long repetitive value"))
+            .unwrap();
+        plain_db
+            .set(&key, &String::from("some long repetitive value"))
+            .unwrap();
+    }
+    db.lcreate("list1").unwrap().lextend(&[1, 2, 3]);
+    db.dump().unwrap();
+    plain_db.dump().unwrap();
+
+    // the compressed file is smaller
+    let compressed_size = std::fs::metadata(&db_name).unwrap().len();
+    let plain_size = std::fs::metadata(&plain_db_name).unwrap().len();
+    assert!(compressed_size < plain_size);
+
+    // the compressed file is detected and loaded
+    let mut loaded_db = PickleDb::load(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    )
+    .unwrap();
+    assert_eq!(loaded_db.total_keys(), 101);
+    assert_eq!(
+        loaded_db
+            .get::<String>("some_long_repetitive_key_50")
+            .unwrap(),
+        "some long repetitive value"
+    );
+    assert_eq!(loaded_db.llen("list1"), 3);
+
+    // a DB loaded from a compressed file stays compressed
+    loaded_db.set("key1", &1).unwrap();
+    assert!(std::fs::metadata(&db_name).unwrap().len() < plain_size);
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert_eq!(read_db.get::<i32>("key1").unwrap(), 1);
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn builder_test(ser_method_int: i32) {
+    test_setup!("builder_test", ser_method_int, db_name);
+
+    // build a new DB
+    let mut db = PickleDbBuilder::new("some_other_path.db")
+        .path(&db_name)
+        .dump_policy(PickleDbDumpPolicy::DumpUponRequest)
+        .serialization(ser_method!(ser_method_int))
+        .compression(true)
+        .build();
+    db.set("key1", &1).unwrap();
+    db.lcreate("list1").unwrap().lextend(&[1, 2, 3]);
+
+    // the dump policy is DumpUponRequest, so nothing is written yet
+    assert!(!std::path::Path::new(&db_name).exists());
+    db.dump().unwrap();
+
+    // load the DB
+    let mut loaded_db = PickleDbBuilder::new(&db_name)
+        .serialization(ser_method!(ser_method_int))
+        .load()
+        .unwrap();
+    assert_eq!(loaded_db.get::<i32>("key1").unwrap(), 1);
+    assert_eq!(loaded_db.llen("list1"), 3);
+
+    // the default dump policy is AutoDump
+    loaded_db.set("key2", &2).unwrap();
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert_eq!(read_db.get::<i32>("key2").unwrap(), 2);
+
+    // loading a non-existent file fails
+    assert!(PickleDbBuilder::new("non_existent_file.db")
+        .serialization(ser_method!(ser_method_int))
+        .load()
+        .is_err());
+}
+
+#[test]
+fn pretty_json_test() {
+    set_test_rsc!("pretty_json_test.db");
+
+    // a new DB is written as compact JSON by default, in a single line
+    let mut db = PickleDb::new_json("pretty_json_test.db", PickleDbDumpPolicy::AutoDump);
+    db.set("key1", &1).unwrap();
+    db.lcreate("list1").unwrap().lextend(&[1, 2, 3]);
+    let compact = std::fs::read_to_string("pretty_json_test.db").unwrap();
+    assert_eq!(compact.lines().count(), 1);
+
+    // load the compact file and write it as pretty JSON
+    let mut db = PickleDbBuilder::new("pretty_json_test.db")
+        .pretty_json(true)
+        .load()
+        .unwrap();
+    db.set("key2", &2).unwrap();
+    let pretty = std::fs::read_to_string("pretty_json_test.db").unwrap();
+    assert!(pretty.contains("\n  "));
+
+    // the option is kept when the DB is cloned
+    set_test_rsc!("pretty_json_test_copy.db");
+    db.clone().save_as("pretty_json_test_copy.db").unwrap();
+    assert!(std::fs::read_to_string("pretty_json_test_copy.db")
+        .unwrap()
+        .contains("\n  "));
+
+    // the pretty file is loaded just like a compact one
+    let read_db =
+        PickleDb::load_read_only("pretty_json_test.db", SerializationMethod::Json).unwrap();
+    assert_eq!(read_db.get::<i32>("key1").unwrap(), 1);
+    assert_eq!(read_db.get::<i32>("key2").unwrap(), 2);
+    assert_eq!(read_db.llen("list1"), 3);
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn merge_test(ser_method_int: i32) {
+    test_setup!("merge_test", ser_method_int, db_name);
+    let other_db_name = format!("other_{}", db_name);
+    set_test_rsc!(&other_db_name);
+
+    let create_db = || {
+        let mut db = PickleDb::new(
+            &db_name,
+            PickleDbDumpPolicy::AutoDump,
+            ser_method!(ser_method_int),
+        );
+        db.set("key1", &1).unwrap();
+        db.set("key2", &2).unwrap();
+        db.lcreate("list1").unwrap().lextend(&[1, 2]);
+        db
+    };
+
+    let mut other_db = PickleDb::new(
+        &other_db_name,
+        PickleDbDumpPolicy::NeverDump,
+        ser_method!(ser_method_int),
+    );
+    other_db.set("key2", &20).unwrap();
+    other_db.set("key3", &30).unwrap();
+    other_db.set("list1", &String::from("not a list")).unwrap();
+    other_db.lcreate("list2").unwrap().lextend(&[3, 4, 5]);
+
+    // merge and overwrite existing keys
+    {
+        let mut db = create_db();
+        db.merge(&other_db, PickleDbMergePolicy::Overwrite).unwrap();
+        assert_eq!(db.total_keys(), 5);
+        assert_eq!(db.get::<i32>("key1").unwrap(), 1);
+        assert_eq!(db.get::<i32>("key2").unwrap(), 20);
+        assert_eq!(db.get::<i32>("key3").unwrap(), 30);
+        assert_eq!(db.get::<String>("list1").unwrap(), "not a list");
+        assert!(!db.lexists("list1"));
+        assert_eq!(db.llen("list2"), 3);
+
+        // the merge is dumped to the file
+        let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+        assert_eq!(read_db.total_keys(), 5);
+        assert_eq!(read_db.get::<i32>("key2").unwrap(), 20);
+        assert_eq!(read_db.lget::<i32>("list2", 2).unwrap(), 5);
+    }
+
+    // merge and skip existing keys
+    {
+        let mut db = create_db();
+        db.merge(&other_db, PickleDbMergePolicy::SkipExisting)
+            .unwrap();
+        assert_eq!(db.total_keys(), 5);
+        assert_eq!(db.get::<i32>("key1").unwrap(), 1);
+        assert_eq!(db.get::<i32>("key2").unwrap(), 2);
+        assert_eq!(db.get::<i32>("key3").unwrap(), 30);
+        assert_eq!(db.llen("list1"), 2);
+        assert_eq!(db.llen("list2"), 3);
+    }
+
+    // the other DB isn't changed
+    assert_eq!(other_db.total_keys(), 4);
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn convert_to_test(ser_method_int: i32) {
+    test_setup!("convert_to_test", ser_method_int, db_name);
+
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+    db.set("int", &100).unwrap();
+    db.set("float", &1.5).unwrap();
+    db.set("string", &String::from("my string")).unwrap();
+    db.set("vec", &vec![1, 2, 3]).unwrap();
+    let mut map = HashMap::new();
+    map.insert(String::from("a"), 1);
+    db.set("map", &map).unwrap();
+    db.lcreate("list1").unwrap().lextend(&[1, 2, 3]);
+    db.lcreate("list2")
+        .unwrap()
+        .ladd(&String::from("my string"))
+        .ladd(&vec![1, 2]);
+
+    for new_ser_method_int in 0..6 {
+        let new_db_name = format!("converted_{}_{}", new_ser_method_int, db_name);
+        set_test_rsc!(&new_db_name);
+
+        let new_db = db.convert_to(&new_db_name, ser_method!(new_ser_method_int));
+
+        // Bincode isn't self-describing so it can't be converted
+        if ser_method_int == 1 || new_ser_method_int == 1 {
+            assert!(new_db.is_err());
+            assert!(!std::path::Path::new(&new_db_name).exists());
+            continue;
+        }
+
+        // the new DB is written to the file right away
+        drop(new_db.unwrap());
+        let new_db =
+            PickleDb::load_read_only(&new_db_name, ser_method!(new_ser_method_int)).unwrap();
+        assert_eq!(new_db.total_keys(), 7);
+        assert_eq!(new_db.get::<i32>("int").unwrap(), 100);
+        assert_eq!(new_db.get::<f64>("float").unwrap(), 1.5);
+        assert_eq!(new_db.get::<String>("string").unwrap(), "my string");
+        assert_eq!(new_db.get::<Vec<i32>>("vec").unwrap(), vec![1, 2, 3]);
+        assert_eq!(new_db.get::<HashMap<String, i32>>("map").unwrap(), map);
+        assert_eq!(new_db.lget::<i32>("list1", 2).unwrap(), 3);
+        assert_eq!(new_db.lget::<String>("list2", 0).unwrap(), "my string");
+        assert_eq!(new_db.lget::<Vec<i32>>("list2", 1).unwrap(), vec![1, 2]);
+    }
+
+    // the original DB isn't affected
+    assert_eq!(db.get::<i32>("int").unwrap(), 100);
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn on_change_test(ser_method_int: i32) {
+    test_setup!("on_change_test", ser_method_int, db_name);
+
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let events_clone = Arc::clone(&events);
+    db.on_change(move |event| events_clone.lock().unwrap().push(event));
+
+    db.set("key1", &1).unwrap();
+    db.lcreate("list1").unwrap();
+    db.ladd("list1", &1).unwrap();
+    db.lextend("list1", &[2, 3]).unwrap();
+    db.lpop::<i32>("list1", 0).unwrap();
+    db.lrem_value("list1", &2).unwrap();
+    db.rem("key1").unwrap();
+    db.lrem_list("list1").unwrap();
+
+    // nothing happens for keys that don't exist
+    db.rem("key1").unwrap();
+    db.lrem_list("list1").unwrap();
+
+    let key = |key: &str| String::from(key);
+    assert_eq!(
+        *events.lock().unwrap(),
+        vec![
+            ChangeEvent::Set(key("key1")),
+            ChangeEvent::ListModified(key("list1")),
+            ChangeEvent::ListModified(key("list1")),
+            ChangeEvent::ListModified(key("list1")),
+            ChangeEvent::ListModified(key("list1")),
+            ChangeEvent::ListModified(key("list1")),
+            ChangeEvent::Removed(key("key1")),
+            ChangeEvent::Removed(key("list1")),
+        ]
+    );
+
+    // a new callback replaces the previous one
+    let new_events = Arc::new(Mutex::new(Vec::new()));
+    let new_events_clone = Arc::clone(&new_events);
+    db.on_change(move |event| new_events_clone.lock().unwrap().push(event));
+    db.set("key2", &2).unwrap();
+    assert_eq!(events.lock().unwrap().len(), 8);
+    assert_eq!(
+        *new_events.lock().unwrap(),
+        vec![ChangeEvent::Set(key("key2"))]
+    );
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn reader_writer_test(ser_method_int: i32) {
+    test_setup!("reader_writer_test", ser_method_int, db_name);
+
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+    db.set("key1", &1).unwrap();
+    db.set("key2", &String::from("my string")).unwrap();
+    db.lcreate("list1").unwrap().lextend(&[1, 2, 3]);
+
+    // write the DB to a buffer
+    let mut buf = Vec::new();
+    db.dump_to_writer(&mut buf).unwrap();
+    assert!(!buf.is_empty());
+
+    // load the data from a reader
+    let mut read_db = PickleDb::load_from_reader(&buf[..], ser_method!(ser_method_int)).unwrap();
+    assert_eq!(read_db.total_keys(), 3);
+    assert_eq!(read_db.get::<i32>("key1").unwrap(), 1);
+    assert_eq!(read_db.get::<String>("key2").unwrap(), "my string");
+    assert_eq!(read_db.llen("list1"), 3);
+
+    // the loaded DB is never dumped
+    read_db.set("key1", &2).unwrap();
+    read_db.dump().unwrap();
+    assert_eq!(db.get::<i32>("key1").unwrap(), 1);
+    assert_eq!(
+        PickleDb::load_read_only(&db_name, ser_method!(ser_method_int))
+            .unwrap()
+            .get::<i32>("key1")
+            .unwrap(),
+        1
+    );
+
+    // loading invalid data fails
+    assert!(PickleDb::load_from_reader(&b"\x01\x02"[..], ser_method!(ser_method_int)).is_err());
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn encryption_test(ser_method_int: i32) {
+    test_setup!("encryption_test", ser_method_int, db_name);
+    let key = [7u8; 32];
+
+    // create an encrypted DB
+    let mut db = PickleDb::new_with_encryption(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+        key,
+    );
+    db.set("key1", &String::from("my secret value")).unwrap();
+    db.lcreate("list1").unwrap().lextend(&[1, 2, 3]);
+
+    // the data isn't stored as plain text
+    let content = std::fs::read(&db_name).unwrap();
+    assert!(!content
+        .windows("my secret value".len())
+        .any(|window| window == b"my secret value"));
+
+    // every dump uses a different nonce
+    db.set("key2", &2).unwrap();
+    db.rem("key2").unwrap();
+    assert_ne!(std::fs::read(&db_name).unwrap(), content);
+
+    // load the DB with the same key
+    let mut loaded_db = PickleDb::load_with_encryption(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+        key,
+    )
+    .unwrap();
+    assert_eq!(loaded_db.get::<String>("key1").unwrap(), "my secret value");
+    assert_eq!(loaded_db.llen("list1"), 3);
+
+    // the loaded DB keeps being encrypted
+    loaded_db.set("key3", &3).unwrap();
+    let read_db = PickleDbBuilder::new(&db_name)
+        .serialization(ser_method!(ser_method_int))
+        .encryption_key(key)
+        .load()
+        .unwrap();
+    assert_eq!(read_db.get::<i32>("key3").unwrap(), 3);
+
+    // compression and encryption together
+    let mut db = PickleDbBuilder::new(&db_name)
+        .serialization(ser_method!(ser_method_int))
+        .compression(true)
+        .encryption_key(key)
+        .build();
+    db.set("key1", &"x".repeat(1000)).unwrap();
+    assert!(std::fs::metadata(&db_name).unwrap().len() < 1000);
+    let read_db = PickleDb::load_with_encryption(
+        &db_name,
+        PickleDbDumpPolicy::NeverDump,
+        ser_method!(ser_method_int),
+        key,
+    )
+    .unwrap();
+    assert_eq!(read_db.get::<String>("key1").unwrap(), "x".repeat(1000));
+
+    // a plain DB can be loaded with a key and it's encrypted on the next dump
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+    db.set("key1", &1).unwrap();
+    let mut db = PickleDb::load_with_encryption(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+        key,
+    )
+    .unwrap();
+    db.set("key2", &2).unwrap();
+    assert!(PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).is_err());
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn checksum_test(ser_method_int: i32) {
+    test_setup!("checksum_test", ser_method_int, db_name);
+
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+    db.set("num", &100).unwrap();
+    db.lcreate("list1").unwrap().lextend(&[1, 2, 3]);
+
+    // binary files start with a checksum header line, text files are written without it
+    let is_binary = matches!(
+        ser_method!(ser_method_int),
+        SerializationMethod::Bin | SerializationMethod::Cbor | SerializationMethod::MsgPack
+    );
+    let content = std::fs::read(&db_name).unwrap();
+    assert_eq!(content.starts_with(b"PICKLEDB-CRC32 "), is_binary);
+
+    // a file without the header, like files written by older versions, is loaded as is
+    if is_binary {
+        let header_len = content.iter().position(|byte| *byte == b'\n').unwrap() + 1;
+        std::fs::write(&db_name, &content[header_len..]).unwrap();
+    }
+    let mut read_db = PickleDb::load(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    )
+    .unwrap();
+    assert_eq!(read_db.get::<i32>("num").unwrap(), 100);
+    assert_eq!(read_db.llen("list1"), 3);
+
+    // the header is added on the next dump of a binary file
+    read_db.set("num", &200).unwrap();
+    assert_eq!(
+        std::fs::read(&db_name)
+            .unwrap()
+            .starts_with(b"PICKLEDB-CRC32 "),
+        is_binary
+    );
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert_eq!(read_db.get::<i32>("num").unwrap(), 200);
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(2), case(5))]
+fn hand_edited_file_test(ser_method_int: i32) {
+    test_setup!("hand_edited_file_test", ser_method_int, db_name);
+
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+    db.set("num", &100).unwrap();
+    db.set("string", &String::from("my string")).unwrap();
+
+    // a text file can be edited by hand and loaded again
+    let content = std::fs::read_to_string(&db_name).unwrap();
+    std::fs::write(&db_name, content.replace("my string", "edited string")).unwrap();
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert_eq!(read_db.get::<i32>("num").unwrap(), 100);
+    assert_eq!(
+        read_db.get::<String>("string").unwrap(),
+        String::from("edited string")
+    );
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn serialization_method_test(ser_method_int: i32) {
+    test_setup!("serialization_method_test", ser_method_int, db_name);
+    let ser_method = ser_method!(ser_method_int);
+
+    // a new DB
+    let mut db = PickleDb::new(&db_name, PickleDbDumpPolicy::AutoDump, ser_method);
+    assert_eq!(db.serialization_method(), ser_method);
+    db.set("num", &100).unwrap();
+
+    // a loaded DB
+    let read_db = PickleDb::load_read_only(&db_name, ser_method).unwrap();
+    assert_eq!(read_db.serialization_method(), ser_method);
+
+    // a DB that was built or loaded with the builder
+    let built_db = PickleDbBuilder::new(&db_name)
+        .serialization(ser_method)
+        .load()
+        .unwrap();
+    assert_eq!(built_db.serialization_method(), ser_method);
+
+    // an in-memory DB
+    assert_eq!(
+        PickleDb::new_in_memory(ser_method).serialization_method(),
+        ser_method
+    );
+
+    // parse the serialization method from its name
+    let name = ser_method.to_string();
+    assert_eq!(name.parse::<SerializationMethod>().unwrap(), ser_method);
+    assert_eq!(
+        name.to_lowercase().parse::<SerializationMethod>().unwrap(),
+        ser_method
+    );
+    assert_eq!(
+        name.to_uppercase().parse::<SerializationMethod>().unwrap(),
+        ser_method
+    );
+}
+
+#[test]
+fn enabled_serialization_methods_test() {
+    let all_methods = [
+        (SerializationMethod::Json, cfg!(feature = "json")),
+        (SerializationMethod::Bin, cfg!(feature = "bincode")),
+        (SerializationMethod::Yaml, cfg!(feature = "yaml")),
+        (SerializationMethod::Cbor, cfg!(feature = "cbor")),
+        (SerializationMethod::MsgPack, cfg!(feature = "msgpack")),
+        (SerializationMethod::Toml, cfg!(feature = "toml")),
+    ];
+
+    // each method is enabled according to its feature
+    for (ser_method, feature_enabled) in all_methods.iter() {
+        assert_eq!(ser_method.is_enabled(), *feature_enabled);
+    }
+    assert!(!SerializationMethod::Custom.is_enabled());
+
+    // the enabled methods are listed in order
+    let enabled: Vec<SerializationMethod> = all_methods
+        .iter()
+        .filter(|(_, feature_enabled)| *feature_enabled)
+        .map(|(ser_method, _)| *ser_method)
+        .collect();
+    assert_eq!(SerializationMethod::enabled(), enabled);
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn size_test(ser_method_int: i32) {
+    test_setup!("size_test", ser_method_int, db_name);
+
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::DumpUponRequest,
+        ser_method!(ser_method_int),
+    );
+
+    // nothing was dumped yet
+    assert!(db.file_size().is_err());
+    assert_eq!(db.memory_footprint(), 0);
+
+    // the footprint counts the keys and the serialized values and list items
+    db.set("key1", &100).unwrap();
+    let value_size = db.get_raw("key1").unwrap().len();
+    assert_eq!(db.memory_footprint(), 4 + value_size);
+
+    db.lcreate("list1").unwrap();
+    assert_eq!(db.memory_footprint(), 4 + value_size + 5);
+    db.ladd("list1", &100);
+    assert_eq!(db.memory_footprint(), 4 + value_size + 5 + value_size);
+
+    // the file size reflects the last dump
+    db.dump().unwrap();
+    let file_size = std::fs::metadata(&db_name).unwrap().len();
+    assert_eq!(db.file_size().unwrap(), file_size);
+    db.set("key2", &"a long string value").unwrap();
+    assert_eq!(db.file_size().unwrap(), file_size);
+    db.dump().unwrap();
+    assert!(db.file_size().unwrap() > file_size);
+
+    // removing data reduces the footprint
+    db.rem("key2").unwrap();
+    db.rem("list1").unwrap();
+    assert_eq!(db.memory_footprint(), 4 + value_size);
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn clone_test(ser_method_int: i32) {
+    test_setup!("clone_test", ser_method_int, db_name);
+
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+    db.set("key1", &100).unwrap();
+    db.lcreate("list1").unwrap().lextend(&[1, 2, 3]);
+
+    // the copy holds the same data
+    let mut copy = db.clone();
+    assert_eq!(copy.get::<i32>("key1").unwrap(), 100);
+    assert_eq!(copy.llen("list1"), 3);
+    assert_eq!(copy.lget::<i32>("list1", 2).unwrap(), 3);
+    assert_eq!(copy.serialization_method(), db.serialization_method());
+
+    // changing the copy doesn't affect the original or the file
+    copy.set("key1", &200).unwrap();
+    copy.set("key2", &300).unwrap();
+    copy.ladd("list1", &4).unwrap();
+    copy.dump().unwrap();
+    assert_eq!(db.get::<i32>("key1").unwrap(), 100);
+    assert!(!db.exists("key2"));
+    assert_eq!(db.llen("list1"), 3);
+    drop(copy);
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert_eq!(read_db.get::<i32>("key1").unwrap(), 100);
+    assert!(!read_db.exists("key2"));
+
+    // changing the original doesn't affect the copy
+    let copy = db.clone();
+    db.rem("key1").unwrap();
+    assert_eq!(copy.get::<i32>("key1").unwrap(), 100);
+
+    // the copy can be kept in a different file
+    let copy_name = format!("{}.copy", db_name);
+    copy.save_as(&copy_name).unwrap();
+    let copy_db = PickleDb::load_read_only(&copy_name, ser_method!(ser_method_int)).unwrap();
+    assert_eq!(copy_db.get::<i32>("key1").unwrap(), 100);
+    assert_eq!(copy_db.llen("list1"), 3);
+    std::fs::remove_file(&copy_name).unwrap();
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn eq_test(ser_method_int: i32) {
+    test_setup!("eq_test", ser_method_int, db_name);
+
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+    let mut expected = PickleDb::new_in_memory(ser_method!(ser_method_int));
+    assert!(db == expected);
+
+    for db in [&mut db, &mut expected] {
+        db.set("key1", &100).unwrap();
+        db.set("key2", &"a string").unwrap();
+        db.lcreate("list1").unwrap().lextend(&[1, 2, 3]);
+    }
+    assert!(db == expected);
+
+    // a DB that was loaded from the file is equal to the original
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert!(read_db == db);
+
+    // a different value
+    expected.set("key1", &200).unwrap();
+    assert!(db != expected);
+    expected.set("key1", &100).unwrap();
+    assert!(db == expected);
+
+    // a missing key
+    expected.rem("key2").unwrap();
+    assert!(db != expected);
+    expected.set("key2", &"a string").unwrap();
+    assert!(db == expected);
+
+    // an expired key that wasn't removed from memory yet is ignored
+    expected
+        .set_with_ttl("key3", &1, std::time::Duration::from_millis(1))
+        .unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(10));
+    assert!(db == expected);
+    assert!(expected == db);
+
+    // list items in a different order
+    expected.lrem_list("list1").unwrap();
+    expected.lcreate("list1").unwrap().lextend(&[3, 2, 1]);
+    assert!(db != expected);
+
+    // a value and a list with the same name
+    expected.rem("list1").unwrap();
+    expected.set("list1", &vec![1, 2, 3]).unwrap();
+    assert!(db != expected);
+}
+
+// a JSON based serializer that marks every serialized value with a '#' prefix
+struct MarkedJsonSerializer;
+
+impl PickleDbSerializer for MarkedJsonSerializer {
+    fn serialize_data(&self, data: &dyn erased_serde::Serialize) -> Result<Vec<u8>, String> {
+        let mut ser_data = vec![b'#'];
+        serde_json::to_writer(&mut ser_data, data).map_err(|err| err.to_string())?;
+        Ok(ser_data)
+    }
+
+    fn deserialize_data(
+        &self,
+        ser_data: &[u8],
+        deserialize: &mut dyn FnMut(
+            &mut dyn erased_serde::Deserializer<'_>,
+        ) -> Result<(), erased_serde::Error>,
+    ) -> Result<(), String> {
+        let ser_data = match ser_data.split_first() {
+            Some((b'#', ser_data)) => ser_data,
+            _ => return Err(String::from("Missing mark")),
+        };
+        let mut deserializer = serde_json::Deserializer::from_slice(ser_data);
+        deserialize(&mut <dyn erased_serde::Deserializer>::erase(
+            &mut deserializer,
+        ))
+        .map_err(|err| err.to_string())
+    }
+}
+
+#[test]
+fn custom_serializer_test() {
+    set_test_rsc!("custom_serializer_test.db");
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Coor {
+        x: i32,
+        y: i32,
+    }
+
+    // create a DB with a custom serializer
+    let mut db = PickleDb::new_with_serializer(
+        "custom_serializer_test.db",
+        PickleDbDumpPolicy::AutoDump,
+        Box::new(MarkedJsonSerializer),
+    );
+    assert_eq!(db.serialization_method(), SerializationMethod::Custom);
+
+    db.set("num", &100).unwrap();
+    db.set("string", &String::from("my string")).unwrap();
+    db.set("coor", &Coor { x: 1, y: 2 }).unwrap();
+    db.set_with_ttl("ttl", &1, std::time::Duration::from_secs(3600))
+        .unwrap();
+    db.lcreate("list1")
+        .unwrap()
+        .lextend(&[1, 2, 3])
+        .ladd(&String::from("four"));
+
+    // values are stored using the custom serializer
+    assert_eq!(db.get_raw("num").unwrap(), b"#100".to_vec());
+    assert_eq!(db.get::<i32>("num").unwrap(), 100);
+    assert_eq!(db.get::<String>("string").unwrap(), "my string");
+    assert_eq!(db.get::<Coor>("coor").unwrap(), Coor { x: 1, y: 2 });
+    assert!(db.get::<String>("num").is_none());
+    assert_eq!(db.lget::<String>("list1", 3).unwrap(), "four");
+
+    // load the DB with the same serializer
+    let read_db = PickleDb::load_with_serializer(
+        "custom_serializer_test.db",
+        PickleDbDumpPolicy::NeverDump,
+        Box::new(MarkedJsonSerializer),
+    )
+    .unwrap();
+    assert_eq!(read_db.serialization_method(), SerializationMethod::Custom);
+    assert_eq!(read_db.get::<i32>("num").unwrap(), 100);
+    assert_eq!(read_db.get::<String>("string").unwrap(), "my string");
+    assert_eq!(read_db.get::<Coor>("coor").unwrap(), Coor { x: 1, y: 2 });
+    assert_eq!(read_db.get::<i32>("ttl").unwrap(), 1);
+    assert_eq!(read_db.llen("list1"), 4);
+    assert_eq!(read_db.lget::<i32>("list1", 2).unwrap(), 3);
+
+    // the file can't be loaded with a different serialization method
+    assert!(
+        PickleDb::load_read_only("custom_serializer_test.db", SerializationMethod::Json).is_err()
+    );
+}
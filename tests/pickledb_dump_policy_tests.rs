@@ -1,4 +1,6 @@
-use pickledb::{PickleDb, PickleDbDumpPolicy, SerializationMethod};
+use pickledb::{PickleDb, PickleDbBuilder, PickleDbDumpPolicy, SerializationMethod};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use std::{thread, time};
 
@@ -9,7 +11,7 @@ extern crate rstest;
 
 use rstest::rstest_parametrize;
 
-#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3))]
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
 fn auto_dump_policy_test(ser_method_int: i32) {
     test_setup!("auto_dump_policy_test", ser_method_int, db_name);
 
@@ -85,7 +87,7 @@ fn auto_dump_policy_test(ser_method_int: i32) {
     }
 }
 
-#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3))]
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
 fn read_only_policy_test(ser_method_int: i32) {
     test_setup!("read_only_policy_test", ser_method_int, db_name);
 
@@ -132,7 +134,7 @@ fn read_only_policy_test(ser_method_int: i32) {
     }
 }
 
-#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3))]
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
 fn dump_upon_request_policy_test(ser_method_int: i32) {
     test_setup!("dump_upon_request_policy_test", ser_method_int, db_name);
 
@@ -170,7 +172,7 @@ fn dump_upon_request_policy_test(ser_method_int: i32) {
     }
 }
 
-#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3))]
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
 fn periodic_dump_policy_test(ser_method_int: i32) {
     test_setup!("periodic_dump_policy_test", ser_method_int, db_name);
 
@@ -246,3 +248,943 @@ fn periodic_dump_policy_test(ser_method_int: i32) {
         assert!(read_db.exists("key5"));
     }
 }
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn auto_dump_every_policy_test(ser_method_int: i32) {
+    test_setup!("auto_dump_every_policy_test", ser_method_int, db_name);
+
+    // create a DB that is dumped every 3 changes
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDumpEvery(3),
+        ser_method!(ser_method_int),
+    );
+    assert!(db.set("key1", &1).is_ok());
+    assert!(db.set("key2", &2).is_ok());
+
+    // verify file is not yet created
+    assert!(PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).is_err());
+
+    // the third change triggers a dump
+    assert!(db.lcreate("list1").is_ok());
+    {
+        let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+        assert!(read_db.exists("key1"));
+        assert!(read_db.exists("key2"));
+        assert!(read_db.lexists("list1"));
+    }
+
+    // the count starts over after the dump
+    db.ladd("list1", &1);
+    db.ladd("list1", &2);
+    {
+        let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+        assert_eq!(read_db.llen("list1"), 0);
+    }
+    assert!(db.rem("key1").unwrap());
+    {
+        let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+        assert_eq!(read_db.llen("list1"), 2);
+        assert!(!read_db.exists("key1"));
+    }
+
+    // a manual dump also starts the count over
+    assert!(db.set("key3", &3).is_ok());
+    assert!(db.dump().is_ok());
+    assert!(db.set("key4", &4).is_ok());
+    assert!(db.set("key5", &5).is_ok());
+    {
+        let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+        assert!(read_db.exists("key3"));
+        assert!(!read_db.exists("key4"));
+    }
+
+    // drop DB and verify change is written to DB
+    drop(db);
+    {
+        let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+        assert!(read_db.exists("key4"));
+        assert!(read_db.exists("key5"));
+    }
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn batch_auto_dump_test(ser_method_int: i32) {
+    test_setup!("batch_auto_dump_test", ser_method_int, db_name);
+
+    // create a DB with AutoDump policy
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+    db.set("key1", &1).unwrap();
+
+    {
+        let mut batch = db.batch();
+
+        // make some changes
+        batch.set("key2", &2).unwrap();
+        batch.rem("key1").unwrap();
+        batch.lcreate("list1").unwrap().lextend(&[1, 2, 3]);
+        assert!(batch.exists("key2"));
+
+        // verify the changes weren't dumped yet
+        {
+            let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+            assert_eq!(read_db.get::<i32>("key1").unwrap(), 1);
+            assert!(!read_db.exists("key2"));
+            assert!(!read_db.exists("list1"));
+        }
+
+        // commit the batch
+        batch.commit().unwrap();
+    }
+
+    // verify all changes were dumped
+    {
+        let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+        assert!(!read_db.exists("key1"));
+        assert_eq!(read_db.get::<i32>("key2").unwrap(), 2);
+        assert_eq!(read_db.llen("list1"), 3);
+    }
+
+    // changes of a batch that isn't committed are dumped when the batch is dropped
+    {
+        let mut batch = db.batch();
+        batch.set("key3", &3).unwrap();
+    }
+    {
+        let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+        assert_eq!(read_db.get::<i32>("key3").unwrap(), 3);
+    }
+
+    // after the batch is done changes are dumped immediately again
+    db.set("key4", &4).unwrap();
+    {
+        let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+        assert_eq!(read_db.get::<i32>("key4").unwrap(), 4);
+    }
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn nested_batch_test(ser_method_int: i32) {
+    test_setup!("nested_batch_test", ser_method_int, db_name);
+
+    // create a DB with AutoDump policy
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+    db.set("key1", &1).unwrap();
+    let load_db = || PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+
+    {
+        let mut batch = db.batch();
+        batch.set("key2", &2).unwrap();
+
+        // committing or dropping a nested batch doesn't dump anything
+        {
+            let mut inner_batch = batch.batch();
+            inner_batch.set("key3", &3).unwrap();
+            inner_batch.commit().unwrap();
+        }
+        {
+            let mut inner_batch = batch.batch();
+            inner_batch.set("key4", &4).unwrap();
+        }
+        assert!(!load_db().exists("key2"));
+        assert!(!load_db().exists("key3"));
+        assert!(!load_db().exists("key4"));
+
+        // a nested batch without changes doesn't drop the changes of the outer batch
+        batch.batch().commit().unwrap();
+        batch.set("key5", &5).unwrap();
+        assert!(!load_db().exists("key5"));
+    }
+
+    // the changes of the outer batch and of the nested batches are dumped together
+    let read_db = load_db();
+    for i in 1..=5 {
+        assert_eq!(read_db.get::<i32>(&format!("key{}", i)).unwrap(), i);
+    }
+
+    // after the batches are done changes are dumped immediately again
+    db.set("key6", &6).unwrap();
+    assert_eq!(load_db().get::<i32>("key6").unwrap(), 6);
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn batch_dump_upon_request_test(ser_method_int: i32) {
+    test_setup!("batch_dump_upon_request_test", ser_method_int, db_name);
+
+    // create a DB with DumpUponRequest policy
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::DumpUponRequest,
+        ser_method!(ser_method_int),
+    );
+    db.dump().unwrap();
+
+    // committing a batch follows the dump policy, so nothing should be dumped
+    let mut batch = db.batch();
+    batch.set("key1", &1).unwrap();
+    batch.commit().unwrap();
+    {
+        let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+        assert!(!read_db.exists("key1"));
+    }
+
+    // dump explicitly
+    db.dump().unwrap();
+    {
+        let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+        assert_eq!(read_db.get::<i32>("key1").unwrap(), 1);
+    }
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn save_as_test(ser_method_int: i32) {
+    test_setup!("save_as_test", ser_method_int, db_name);
+    let backup_name = format!("{}.backup", db_name);
+    set_test_rsc!(&backup_name);
+
+    // create a DB with DumpUponRequest policy
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::DumpUponRequest,
+        ser_method!(ser_method_int),
+    );
+    db.set("key1", &1).unwrap();
+    db.lcreate("list1").unwrap().lextend(&[1, 2]);
+
+    // save a backup, the DB file itself isn't written
+    db.save_as(&backup_name).unwrap();
+    assert!(!Path::new(&db_name).exists());
+    {
+        let backup_db =
+            PickleDb::load_read_only(&backup_name, ser_method!(ser_method_int)).unwrap();
+        assert_eq!(backup_db.get::<i32>("key1").unwrap(), 1);
+        assert_eq!(backup_db.llen("list1"), 2);
+    }
+
+    // dumping writes the DB file and doesn't change the backup
+    db.set("key2", &2).unwrap();
+    db.dump().unwrap();
+    {
+        let backup_db =
+            PickleDb::load_read_only(&backup_name, ser_method!(ser_method_int)).unwrap();
+        assert!(!backup_db.exists("key2"));
+    }
+
+    // a read-only DB can be saved too
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    read_db.save_as(&backup_name).unwrap();
+    let backup_db = PickleDb::load_read_only(&backup_name, ser_method!(ser_method_int)).unwrap();
+    assert_eq!(backup_db.get::<i32>("key2").unwrap(), 2);
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn temp_files_cleanup_test(ser_method_int: i32) {
+    test_setup!("temp_files_cleanup_test", ser_method_int, db_name);
+    // no process can have this ID, it's above the maximal process ID
+    let stale_temp_file = format!("{}.temp.4294967295.5678.0", db_name);
+    set_test_rsc!(&stale_temp_file);
+    let live_temp_file = format!("{}.temp.{}.5678.0", db_name, std::process::id());
+    set_test_rsc!(&live_temp_file);
+
+    // dump many times in a row, no temp files should be left
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+    for i in 0..20 {
+        db.set("key1", &i).unwrap();
+    }
+    let temp_file_prefix = format!("{}.temp.", db_name);
+    let temp_files_count = || {
+        std::fs::read_dir(".")
+            .unwrap()
+            .filter(|entry| {
+                entry
+                    .as_ref()
+                    .unwrap()
+                    .file_name()
+                    .to_str()
+                    .unwrap()
+                    .starts_with(&temp_file_prefix)
+            })
+            .count()
+    };
+    assert_eq!(temp_files_count(), 0);
+
+    // simulate a temp file left by a dump that didn't complete, and a temp file of a dump that
+    // is still in progress in a running process
+    std::fs::write(&stale_temp_file, "some data").unwrap();
+    std::fs::write(&live_temp_file, "some data").unwrap();
+    assert_eq!(temp_files_count(), 2);
+
+    // loading a read-only DB doesn't remove any temp file
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert_eq!(read_db.get::<i32>("key1").unwrap(), 19);
+    assert_eq!(temp_files_count(), 2);
+
+    // loading the DB removes the stale temp file only
+    let db = PickleDb::load(
+        &db_name,
+        PickleDbDumpPolicy::DumpUponRequest,
+        ser_method!(ser_method_int),
+    )
+    .unwrap();
+    assert_eq!(db.get::<i32>("key1").unwrap(), 19);
+    assert!(!Path::new(&stale_temp_file).exists());
+    assert!(Path::new(&live_temp_file).exists());
+    assert_eq!(temp_files_count(), 1);
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn temp_dir_test(ser_method_int: i32) {
+    test_setup!("temp_dir_test", ser_method_int, db_name);
+    let temp_dir = format!("{}_temp_dir", db_name);
+    let _ignore = std::fs::remove_dir_all(&temp_dir);
+    std::fs::create_dir(&temp_dir).unwrap();
+    let temp_files_count = || std::fs::read_dir(&temp_dir).unwrap().count();
+
+    // dump many times in a row, temp files are written to the temp dir and none are left
+    let mut db = PickleDbBuilder::new(&db_name)
+        .serialization(ser_method!(ser_method_int))
+        .temp_dir(&temp_dir)
+        .build();
+    for i in 0..20 {
+        db.set("key1", &i).unwrap();
+    }
+    assert_eq!(temp_files_count(), 0);
+
+    // simulate a temp file left in the temp dir by a dump that didn't complete
+    let stale_temp_file = Path::new(&temp_dir).join(format!("{}.temp.4294967295.5678.0", db_name));
+    std::fs::write(&stale_temp_file, "some data").unwrap();
+    assert_eq!(temp_files_count(), 1);
+
+    // loading the DB with the same temp dir removes the stale temp file
+    let loaded_db = PickleDbBuilder::new(&db_name)
+        .serialization(ser_method!(ser_method_int))
+        .temp_dir(&temp_dir)
+        .load()
+        .unwrap();
+    assert_eq!(loaded_db.get::<i32>("key1").unwrap(), 19);
+    assert_eq!(temp_files_count(), 0);
+
+    // dumping to a non-existent temp dir fails and leaves the DB file intact
+    let mut db = PickleDbBuilder::new(&db_name)
+        .dump_policy(PickleDbDumpPolicy::DumpUponRequest)
+        .serialization(ser_method!(ser_method_int))
+        .temp_dir(format!("{}_non_existent", temp_dir))
+        .build();
+    db.set("key1", &100).unwrap();
+    assert!(db.dump().is_err());
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert_eq!(read_db.get::<i32>("key1").unwrap(), 19);
+
+    std::fs::remove_dir(&temp_dir).unwrap();
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn create_dirs_test(ser_method_int: i32) {
+    test_setup!("create_dirs_test", ser_method_int, db_name);
+    let root_dir = format!("{}_dirs", db_name);
+    let _ignore = std::fs::remove_dir_all(&root_dir);
+    let db_path = Path::new(&root_dir).join("dir1").join(&db_name);
+
+    // by default the directory of the DB file isn't created, so the dump fails
+    let mut db = PickleDbBuilder::new(&db_path)
+        .dump_policy(PickleDbDumpPolicy::DumpUponRequest)
+        .serialization(ser_method!(ser_method_int))
+        .build();
+    db.set("key1", &1).unwrap();
+    assert!(db.dump().is_err());
+    assert!(!Path::new(&root_dir).exists());
+    db.set_flush_on_drop(false);
+
+    // the directories are created on the first dump
+    let mut db = PickleDbBuilder::new(&db_path)
+        .serialization(ser_method!(ser_method_int))
+        .create_dirs(true)
+        .build();
+    assert!(!Path::new(&root_dir).exists());
+    db.set("key1", &1).unwrap();
+    assert!(db_path.exists());
+    db.set("key2", &2).unwrap();
+
+    let read_db = PickleDb::load_read_only(&db_path, ser_method!(ser_method_int)).unwrap();
+    assert_eq!(read_db.get::<i32>("key1").unwrap(), 1);
+    assert_eq!(read_db.get::<i32>("key2").unwrap(), 2);
+
+    drop(db);
+    std::fs::remove_dir_all(&root_dir).unwrap();
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn close_test(ser_method_int: i32) {
+    test_setup!("close_test", ser_method_int, db_name);
+
+    // closing the DB dumps it even if the dump policy is DumpUponRequest
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::DumpUponRequest,
+        ser_method!(ser_method_int),
+    );
+    db.set("key1", &1).unwrap();
+    assert!(!Path::new(&db_name).exists());
+    db.close().unwrap();
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert_eq!(read_db.get::<i32>("key1").unwrap(), 1);
+
+    // closing a NeverDump DB doesn't write anything
+    let mut db = PickleDb::load(
+        &db_name,
+        PickleDbDumpPolicy::NeverDump,
+        ser_method!(ser_method_int),
+    )
+    .unwrap();
+    db.set("key1", &2).unwrap();
+    db.close().unwrap();
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert_eq!(read_db.get::<i32>("key1").unwrap(), 1);
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn flush_on_drop_test(ser_method_int: i32) {
+    test_setup!("flush_on_drop_test", ser_method_int, db_name);
+
+    // by default a PeriodicDump DB is dumped when it's dropped
+    {
+        let mut db = PickleDb::new(
+            &db_name,
+            PickleDbDumpPolicy::PeriodicDump(Duration::from_secs(100)),
+            ser_method!(ser_method_int),
+        );
+        db.set("key1", &1).unwrap();
+    }
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert_eq!(read_db.get::<i32>("key1").unwrap(), 1);
+
+    // disable the dump on drop, the change isn't written
+    {
+        let mut db = PickleDb::load(
+            &db_name,
+            PickleDbDumpPolicy::PeriodicDump(Duration::from_secs(100)),
+            ser_method!(ser_method_int),
+        )
+        .unwrap();
+        db.set_flush_on_drop(false);
+        db.set("key1", &2).unwrap();
+    }
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert_eq!(read_db.get::<i32>("key1").unwrap(), 1);
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn is_dirty_test(ser_method_int: i32) {
+    test_setup!("is_dirty_test", ser_method_int, db_name);
+
+    // a new DB is dirty until it's dumped
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::DumpUponRequest,
+        ser_method!(ser_method_int),
+    );
+    assert!(db.is_dirty());
+    db.dump().unwrap();
+    assert!(!db.is_dirty());
+
+    // changes make the DB dirty
+    db.set("key1", &1).unwrap();
+    assert!(db.is_dirty());
+    db.dump().unwrap();
+    assert!(!db.is_dirty());
+    db.lcreate("list1").unwrap().ladd(&1);
+    assert!(db.is_dirty());
+    db.dump().unwrap();
+
+    // dumping a DB that isn't dirty doesn't write the file
+    std::fs::remove_file(&db_name).unwrap();
+    db.dump().unwrap();
+    assert!(!Path::new(&db_name).exists());
+    db.rem("key1").unwrap();
+    db.dump().unwrap();
+    assert!(Path::new(&db_name).exists());
+
+    // a loaded DB isn't dirty until it's changed
+    let mut db = PickleDb::load(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    )
+    .unwrap();
+    assert!(!db.is_dirty());
+    db.set("key2", &2).unwrap();
+    // AutoDump dumps the change right away
+    assert!(!db.is_dirty());
+
+    // save_as doesn't affect the dirty flag
+    let db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::DumpUponRequest,
+        ser_method!(ser_method_int),
+    );
+    let other_db_name = format!("other_{}", db_name);
+    set_test_rsc!(&other_db_name);
+    db.save_as(&other_db_name).unwrap();
+    assert!(db.is_dirty());
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn dump_if_dirty_test(ser_method_int: i32) {
+    test_setup!("dump_if_dirty_test", ser_method_int, db_name);
+
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::DumpUponRequest,
+        ser_method!(ser_method_int),
+    );
+
+    // a new DB is dirty, so it's dumped
+    assert!(db.dump_if_dirty().unwrap());
+    assert!(Path::new(&db_name).exists());
+
+    // nothing changed, so nothing is written
+    assert!(!db.dump_if_dirty().unwrap());
+
+    // the time since the last dump grows until the next dump
+    thread::sleep(time::Duration::from_millis(100));
+    assert!(db.last_dump_elapsed() >= Duration::from_millis(100));
+    db.set("key1", &1).unwrap();
+    assert!(db.last_dump_elapsed() >= Duration::from_millis(100));
+    assert!(db.dump_if_dirty().unwrap());
+    assert!(db.last_dump_elapsed() < Duration::from_millis(100));
+    assert!(!db.dump_if_dirty().unwrap());
+
+    // verify the change was dumped to the file
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert_eq!(read_db.get::<i32>("key1").unwrap(), 1);
+
+    // a DB with NeverDump policy is never dumped
+    let mut read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    read_db.set("key2", &2).unwrap();
+    assert!(read_db.is_dirty());
+    assert!(!read_db.dump_if_dirty().unwrap());
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn in_memory_test(ser_method_int: i32) {
+    test_setup!("in_memory_test", ser_method_int, db_name);
+
+    // all DB operations work on an in-memory DB
+    let mut db = PickleDb::new_in_memory(ser_method!(ser_method_int));
+    db.set("key1", &1).unwrap();
+    db.lcreate("list1").unwrap().lextend(&[1, 2, 3]);
+    assert_eq!(db.get::<i32>("key1").unwrap(), 1);
+    assert_eq!(db.llen("list1"), 3);
+    assert!(db.rem("key1").unwrap());
+    db.set("key2", &2).unwrap();
+
+    // dumping does nothing
+    db.dump().unwrap();
+
+    // the DB can be written explicitly to a file
+    db.save_as(&db_name).unwrap();
+    db.close().unwrap();
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert!(!read_db.exists("key1"));
+    assert_eq!(read_db.get::<i32>("key2").unwrap(), 2);
+    assert_eq!(read_db.llen("list1"), 3);
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn wal_test(ser_method_int: i32) {
+    test_setup!("wal_test", ser_method_int, db_name);
+    let wal_name = format!("{}.wal", db_name);
+    set_test_rsc!(&wal_name);
+    let file_len = |file_name: &str| std::fs::metadata(file_name).unwrap().len();
+    let load_db = || PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+
+    // the first dump of a new DB writes the whole file. Store a large value so the log doesn't
+    // become larger than the file too quickly
+    let mut db = PickleDbBuilder::new(&db_name)
+        .serialization(ser_method!(ser_method_int))
+        .wal(true)
+        .build();
+    db.set("key0", &"x".repeat(1000)).unwrap();
+    assert!(Path::new(&db_name).exists());
+    assert!(!Path::new(&wal_name).exists());
+    db.set("key1", &1).unwrap();
+    let db_len = file_len(&db_name);
+
+    // the next changes are appended to the log
+    db.set("key2", &String::from("my string")).unwrap();
+    db.set_with_ttl("key3", &3, Duration::from_secs(100))
+        .unwrap();
+    db.lcreate("list1").unwrap().lextend(&[1, 2, 3]);
+    db.lpop::<i32>("list1", 0).unwrap();
+    db.screate("set1").unwrap();
+    db.sadd("set1", &1).unwrap();
+    db.hset("hash1", "field1", &1).unwrap();
+    db.rem("key1").unwrap();
+    assert!(Path::new(&wal_name).exists());
+    assert_eq!(file_len(&db_name), db_len);
+
+    // loading the DB applies the log
+    let read_db = load_db();
+    assert!(!read_db.exists("key1"));
+    assert_eq!(read_db.get::<String>("key2").unwrap(), "my string");
+    assert_eq!(read_db.get::<i32>("key3").unwrap(), 3);
+    assert_eq!(read_db.lget::<i32>("list1", 0).unwrap(), 2);
+    assert_eq!(read_db.llen("list1"), 2);
+    assert!(read_db.scontains("set1", &1));
+    assert_eq!(read_db.hget::<i32>("hash1", "field1").unwrap(), 1);
+
+    // compact the DB, the log is removed and the data is kept
+    db.compact().unwrap();
+    assert!(!Path::new(&wal_name).exists());
+    let read_db = load_db();
+    assert_eq!(read_db.total_keys(), 6);
+    assert_eq!(read_db.llen("list1"), 2);
+    assert_eq!(read_db.slen("set1"), 1);
+    assert_eq!(read_db.hlen("hash1"), 1);
+
+    // the log is folded back into the file automatically once it becomes larger than the file
+    for i in 0..100 {
+        db.set("key4", &i).unwrap();
+        if Path::new(&wal_name).exists() {
+            assert!(file_len(&wal_name) <= file_len(&db_name) + 100);
+        }
+    }
+    assert_eq!(load_db().get::<i32>("key4").unwrap(), 99);
+
+    // changes to multiple keys at once write the whole file
+    db.set("key5", &5).unwrap();
+    assert!(Path::new(&wal_name).exists());
+    db.rename("key5", "key6").unwrap();
+    assert!(!Path::new(&wal_name).exists());
+    assert_eq!(load_db().get::<i32>("key6").unwrap(), 5);
+    drop(db);
+
+    // simulate a crash in the middle of writing a record to the log
+    let mut db = PickleDbBuilder::new(&db_name)
+        .serialization(ser_method!(ser_method_int))
+        .wal(true)
+        .load()
+        .unwrap();
+    db.set("key7", &7).unwrap();
+    let mut wal_content = std::fs::read(&wal_name).unwrap();
+    wal_content.extend_from_slice(&[0, 100, 0, 0, 0, 1, 2]);
+    std::fs::write(&wal_name, wal_content).unwrap();
+
+    // the records before the partial record are applied
+    let mut db = PickleDbBuilder::new(&db_name)
+        .serialization(ser_method!(ser_method_int))
+        .wal(true)
+        .load()
+        .unwrap();
+    assert_eq!(db.get::<i32>("key7").unwrap(), 7);
+
+    // the next dump writes the whole file and removes the partial record
+    db.set("key8", &8).unwrap();
+    assert!(!Path::new(&wal_name).exists());
+    let read_db = load_db();
+    assert_eq!(read_db.get::<i32>("key7").unwrap(), 7);
+    assert_eq!(read_db.get::<i32>("key8").unwrap(), 8);
+
+    // a DB that doesn't use the log removes it on the next dump
+    let mut db = PickleDbBuilder::new(&db_name)
+        .serialization(ser_method!(ser_method_int))
+        .wal(true)
+        .load()
+        .unwrap();
+    db.set("key9", &9).unwrap();
+    assert!(Path::new(&wal_name).exists());
+    drop(db);
+    let mut db = PickleDb::load(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    )
+    .unwrap();
+    assert_eq!(db.get::<i32>("key9").unwrap(), 9);
+    db.set("key10", &10).unwrap();
+    assert!(!Path::new(&wal_name).exists());
+    assert_eq!(load_db().get::<i32>("key9").unwrap(), 9);
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn stale_wal_test(ser_method_int: i32) {
+    test_setup!("stale_wal_test", ser_method_int, db_name);
+    let wal_name = format!("{}.wal", db_name);
+    set_test_rsc!(&wal_name);
+    let load_db = || {
+        PickleDbBuilder::new(&db_name)
+            .serialization(ser_method!(ser_method_int))
+            .wal(true)
+            .load()
+            .unwrap()
+    };
+
+    let mut db = PickleDbBuilder::new(&db_name)
+        .serialization(ser_method!(ser_method_int))
+        .wal(true)
+        .build();
+    db.set("key0", &"x".repeat(1000)).unwrap();
+    db.set("key1", &1).unwrap();
+    db.set("key2", &1).unwrap();
+    db.set("key1", &2).unwrap();
+    db.rem("key2").unwrap();
+    let stale_wal_content = std::fs::read(&wal_name).unwrap();
+
+    // the DB file is rewritten with newer changes
+    db.set("key1", &3).unwrap();
+    db.set("key2", &5).unwrap();
+    db.compact().unwrap();
+    assert!(!Path::new(&wal_name).exists());
+    drop(db);
+
+    // simulate a crash after the DB file was replaced but before the log was removed
+    std::fs::write(&wal_name, &stale_wal_content).unwrap();
+
+    // the stale log isn't applied on top of the newer file
+    let mut db = load_db();
+    assert_eq!(db.get::<i32>("key1").unwrap(), 3);
+    assert_eq!(db.get::<i32>("key2").unwrap(), 5);
+    assert_eq!(db.total_keys(), 3);
+
+    // the next dump writes the whole file and removes the stale log
+    db.set("key3", &3).unwrap();
+    assert!(!Path::new(&wal_name).exists());
+
+    // a log that is valid for the new file is applied
+    db.set("key4", &4).unwrap();
+    assert!(Path::new(&wal_name).exists());
+    let read_db = load_db();
+    assert_eq!(read_db.get::<i32>("key1").unwrap(), 3);
+    assert_eq!(read_db.get::<i32>("key2").unwrap(), 5);
+    assert_eq!(read_db.get::<i32>("key4").unwrap(), 4);
+    assert_eq!(read_db.total_keys(), 5);
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn compact_test(ser_method_int: i32) {
+    test_setup!("compact_test", ser_method_int, db_name);
+
+    // add and remove many keys and list items
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::DumpUponRequest,
+        ser_method!(ser_method_int),
+    );
+    for i in 0..1000 {
+        db.set(&format!("key{}", i), &i).unwrap();
+        db.lcreate(&format!("list{}", i))
+            .unwrap()
+            .lextend(&[1, 2, 3]);
+    }
+    for i in 0..990 {
+        db.rem(&format!("key{}", i)).unwrap();
+        db.lrem_list(&format!("list{}", i)).unwrap();
+    }
+    db.lextend("list999", &[4, 5, 6]);
+    db.lrem_value("list999", &4).unwrap();
+    db.dump().unwrap();
+
+    // compacting writes the file even if nothing changed since the last dump
+    std::fs::remove_file(&db_name).unwrap();
+    db.compact().unwrap();
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert_eq!(read_db.total_keys(), 20);
+    assert_eq!(read_db.get::<i32>("key999").unwrap(), 999);
+    assert_eq!(read_db.llen("list999"), 5);
+
+    // the data in memory isn't affected
+    assert_eq!(db.total_keys(), 20);
+    assert_eq!(db.get::<i32>("key990").unwrap(), 990);
+    assert_eq!(db.lget::<i32>("list999", 3).unwrap(), 5);
+
+    // compacting a NeverDump DB doesn't write anything
+    let mut db = PickleDb::load(
+        &db_name,
+        PickleDbDumpPolicy::NeverDump,
+        ser_method!(ser_method_int),
+    )
+    .unwrap();
+    db.rem("key999").unwrap();
+    db.compact().unwrap();
+    assert!(!db.exists("key999"));
+    assert!(
+        PickleDb::load_read_only(&db_name, ser_method!(ser_method_int))
+            .unwrap()
+            .exists("key999")
+    );
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn on_dump_test(ser_method_int: i32) {
+    test_setup!("on_dump_test", ser_method_int, db_name);
+
+    let dumps = Arc::new(Mutex::new(Vec::new()));
+    let register = |db: &mut PickleDb| {
+        let dumps = Arc::clone(&dumps);
+        db.on_dump(Box::new(move |path, size| {
+            dumps.lock().unwrap().push((path.to_path_buf(), size))
+        }));
+    };
+
+    // the callback is called after every dump with the path and the size of the file
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::DumpUponRequest,
+        ser_method!(ser_method_int),
+    );
+    register(&mut db);
+    db.set("key1", &1).unwrap();
+    assert!(dumps.lock().unwrap().is_empty());
+    db.dump().unwrap();
+    db.set("key2", &2).unwrap();
+    db.compact().unwrap();
+    {
+        let dumps = dumps.lock().unwrap();
+        assert_eq!(dumps.len(), 2);
+        assert_eq!(dumps[1].0, Path::new(&db_name));
+        assert_eq!(
+            dumps[1].1 as u64,
+            std::fs::metadata(&db_name).unwrap().len()
+        );
+    }
+
+    // the callback isn't called when nothing is written
+    db.dump().unwrap();
+    assert_eq!(dumps.lock().unwrap().len(), 2);
+    let mut db = PickleDb::load(
+        &db_name,
+        PickleDbDumpPolicy::NeverDump,
+        ser_method!(ser_method_int),
+    )
+    .unwrap();
+    register(&mut db);
+    db.set("key3", &3).unwrap();
+    db.dump().unwrap();
+    assert_eq!(dumps.lock().unwrap().len(), 2);
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn dump_identical_content_test(ser_method_int: i32) {
+    test_setup!("dump_identical_content_test", ser_method_int, db_name);
+
+    let dumps = Arc::new(Mutex::new(0));
+    let register = |db: &mut PickleDb| {
+        let dumps = Arc::clone(&dumps);
+        db.on_dump(Box::new(move |_, _| *dumps.lock().unwrap() += 1));
+    };
+
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::DumpUponRequest,
+        ser_method!(ser_method_int),
+    );
+    register(&mut db);
+    db.set("key1", &1).unwrap();
+    db.dump().unwrap();
+    assert_eq!(*dumps.lock().unwrap(), 1);
+
+    // changes that don't change the content of the file don't rewrite it
+    db.set("key1", &2).unwrap();
+    db.set("key1", &1).unwrap();
+    assert!(db.is_dirty());
+    db.dump().unwrap();
+    assert!(!db.is_dirty());
+    assert_eq!(*dumps.lock().unwrap(), 1);
+
+    // a real change rewrites the file
+    db.set("key1", &2).unwrap();
+    db.dump().unwrap();
+    assert_eq!(*dumps.lock().unwrap(), 2);
+
+    // the file is rewritten if it was removed
+    std::fs::remove_file(&db_name).unwrap();
+    db.set("key1", &2).unwrap();
+    db.dump().unwrap();
+    assert_eq!(*dumps.lock().unwrap(), 3);
+    assert!(std::path::Path::new(&db_name).exists());
+
+    // compact() always writes the file
+    db.compact().unwrap();
+    assert_eq!(*dumps.lock().unwrap(), 4);
+
+    // the content of a loaded file is known as well
+    let mut db = PickleDb::load(
+        &db_name,
+        PickleDbDumpPolicy::DumpUponRequest,
+        ser_method!(ser_method_int),
+    )
+    .unwrap();
+    register(&mut db);
+    db.set("key1", &2).unwrap();
+    db.dump().unwrap();
+    assert_eq!(*dumps.lock().unwrap(), 4);
+    assert_eq!(db.get::<i32>("key1").unwrap(), 2);
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn reload_test(ser_method_int: i32) {
+    test_setup!("reload_test", ser_method_int, db_name);
+
+    let mut writer_db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+    writer_db.set("key1", &1).unwrap();
+
+    let mut db = PickleDb::load(
+        &db_name,
+        PickleDbDumpPolicy::DumpUponRequest,
+        ser_method!(ser_method_int),
+    )
+    .unwrap();
+    assert_eq!(db.get::<i32>("key1").unwrap(), 1);
+
+    // the file is changed by the other DB while this DB has changes of its own
+    writer_db.set("key1", &10).unwrap();
+    writer_db.set("key2", &2).unwrap();
+    writer_db.lcreate("list1").unwrap().ladd(&100);
+    db.set("key3", &3).unwrap();
+    assert!(db.is_dirty());
+
+    // the changes that weren't dumped are discarded
+    db.reload().unwrap();
+    assert!(!db.is_dirty());
+    assert_eq!(db.total_keys(), 3);
+    assert_eq!(db.get::<i32>("key1").unwrap(), 10);
+    assert_eq!(db.get::<i32>("key2").unwrap(), 2);
+    assert!(!db.exists("key3"));
+    assert_eq!(db.lget::<i32>("list1", 0).unwrap(), 100);
+
+    // the DB keeps its path and dump policy
+    db.set("key4", &4).unwrap();
+    db.dump().unwrap();
+    writer_db.reload().unwrap();
+    assert_eq!(writer_db.get::<i32>("key4").unwrap(), 4);
+    assert!(!writer_db.exists("key3"));
+
+    // reloading never removes temp files, even stale ones, they're only removed when the DB is loaded
+    let stale_temp_file = format!("{}.temp.4294967295.5678.0", db_name);
+    set_test_rsc!(&stale_temp_file);
+    std::fs::write(&stale_temp_file, "some data").unwrap();
+    db.reload().unwrap();
+    writer_db.reload().unwrap();
+    assert!(Path::new(&stale_temp_file).exists());
+
+    // if the file can't be read the DB is left unchanged
+    std::fs::remove_file(&db_name).unwrap();
+    assert!(db.reload().is_err());
+    assert_eq!(db.total_keys(), 4);
+}
@@ -1,11 +1,14 @@
 use pickledb::error::ErrorType;
-use pickledb::{PickleDb, PickleDbDumpPolicy};
+use pickledb::{
+    PickleDb, PickleDbBuilder, PickleDbDumpPolicy, PickleDbMergePolicy, SerializationMethod,
+};
 
 #[macro_use(matches)]
 extern crate matches;
 extern crate fs2;
 
 use fs2::FileExt;
+use std::collections::HashMap;
 use std::fs::File;
 
 mod common;
@@ -43,6 +46,16 @@ fn load_serialization_error_test() {
     ));
     assert_eq!(load_as_cbor_err.to_string(), "Cannot deserialize DB");
 
+    // try to load the same DB with MessagePack serialization, should fail
+    let load_as_msgpack = PickleDb::load_msgpack("json_db.db", PickleDbDumpPolicy::NeverDump);
+    assert!(load_as_msgpack.is_err());
+    let load_as_msgpack_err = load_as_msgpack.err().unwrap();
+    assert!(matches!(
+        load_as_msgpack_err.get_type(),
+        ErrorType::Serialization
+    ));
+    assert_eq!(load_as_msgpack_err.to_string(), "Cannot deserialize DB");
+
     // try to load the same DB with Yaml serialization, should not fail because YAML is
     // a superset oj JSON
     assert!(PickleDb::load_yaml("json_db.db", PickleDbDumpPolicy::NeverDump).is_ok());
@@ -78,6 +91,8 @@ fn dump_error_test() {
     db.set("string", &String::from("my string")).unwrap();
     db.set("vec", &vec![1, 2, 3]).unwrap();
     db.lcreate("list1").unwrap().lextend(&[1, 2, 3]);
+    db.screate("set1").unwrap();
+    db.hset("hash1", "field1", &1).unwrap();
 
     // lock the DB file so no more writes will be possible
     let db_file = File::open("dump_error_test.db").unwrap();
@@ -91,6 +106,15 @@ fn dump_error_test() {
     // verify the old value is still there
     assert_eq!(db.get::<i32>("num").unwrap(), 100);
 
+    // try set_many, confirm failure
+    let try_set_many = db.set_many(&[("num", &200), ("num2", &300)]);
+    assert!(try_set_many.is_err());
+    let try_set_many_err = try_set_many.err().unwrap();
+    assert!(matches!(try_set_many_err.get_type(), ErrorType::Io));
+    // verify the old value is still there and the new key wasn't added
+    assert_eq!(db.get::<i32>("num").unwrap(), 100);
+    assert!(!db.exists("num2"));
+
     // try dump, confirm failure
     let try_dump = db.dump();
     assert!(try_dump.is_err());
@@ -137,6 +161,13 @@ fn dump_error_test() {
     // confirm list size is still the same
     assert_eq!(db.llen("list1"), 3);
 
+    // try lpop_front_n, confirm failure
+    let try_lpop_front_n = db.lpop_front_n::<i32>("list1", 2);
+    assert!(try_lpop_front_n.is_empty());
+    // confirm the list is still the same
+    assert_eq!(db.llen("list1"), 3);
+    assert_eq!(db.lget::<i32>("list1", 0).unwrap(), 1);
+
     // try lrem_value, confirm failure
     let try_lrem_value = db.lrem_value("list1", &1);
     assert!(try_lrem_value.is_err());
@@ -145,6 +176,935 @@ fn dump_error_test() {
     // verify "list1" is still in the DB
     assert_eq!(db.llen("list1"), 3);
 
+    // try lset, confirm failure
+    let try_lset = db.lset("list1", 0, &100);
+    assert!(try_lset.is_err());
+    let try_lset_err = try_lset.err().unwrap();
+    assert!(matches!(try_lset_err.get_type(), ErrorType::Io));
+    // verify the original item is still in the list
+    assert_eq!(db.lget::<i32>("list1", 0).unwrap(), 1);
+
+    // try linsert, confirm failure
+    let try_linsert = db.linsert("list1", 0, &100);
+    assert!(try_linsert.is_none());
+    // confirm list size is still the same
+    assert_eq!(db.llen("list1"), 3);
+
+    // try lpush_front, confirm failure
+    let try_lpush_front = db.lpush_front("list1", &100);
+    assert!(try_lpush_front.is_none());
+    // confirm the list is still the same
+    assert_eq!(db.llen("list1"), 3);
+    assert_eq!(db.lget::<i32>("list1", 0).unwrap(), 1);
+
+    // try lextend_front, confirm failure
+    let try_lextend_front = db.lextend_front("list1", &[100, 200]);
+    assert!(try_lextend_front.is_none());
+    // confirm the list is still the same
+    assert_eq!(db.llen("list1"), 3);
+    assert_eq!(db.lget::<i32>("list1", 0).unwrap(), 1);
+
+    // try lsort_by, confirm failure
+    let try_lsort_by = db.lsort_by("list1", |a: &i32, b: &i32| b.cmp(a));
+    assert!(try_lsort_by.is_err());
+    let try_lsort_by_err = try_lsort_by.err().unwrap();
+    assert!(matches!(try_lsort_by_err.get_type(), ErrorType::Io));
+    // confirm the list order is still the same
+    assert_eq!(db.lget::<i32>("list1", 0).unwrap(), 1);
+
+    // try lclear, confirm failure
+    let try_lclear = db.lclear("list1");
+    assert!(try_lclear.is_err());
+    let try_lclear_err = try_lclear.err().unwrap();
+    assert!(matches!(try_lclear_err.get_type(), ErrorType::Io));
+    // confirm list size is still the same
+    assert_eq!(db.llen("list1"), 3);
+
+    // try set_with_ttl, confirm failure
+    let try_set_with_ttl = db.set_with_ttl("num", &200, std::time::Duration::from_secs(60));
+    assert!(try_set_with_ttl.is_err());
+    let try_set_with_ttl_err = try_set_with_ttl.err().unwrap();
+    assert!(matches!(try_set_with_ttl_err.get_type(), ErrorType::Io));
+    // verify the old value is still there
+    assert_eq!(db.get::<i32>("num").unwrap(), 100);
+
+    // try merge, confirm failure
+    let mut other_db = PickleDb::new_json("other.db", PickleDbDumpPolicy::NeverDump);
+    other_db.set("num", &200).unwrap();
+    other_db.set("num2", &300).unwrap();
+    let try_merge = db.merge(&other_db, PickleDbMergePolicy::Overwrite);
+    assert!(try_merge.is_err());
+    let try_merge_err = try_merge.err().unwrap();
+    assert!(matches!(try_merge_err.get_type(), ErrorType::Io));
+    // verify the old value is still there and the new key wasn't added
+    assert_eq!(db.get::<i32>("num").unwrap(), 100);
+    assert!(!db.exists("num2"));
+
+    // try rem_many, confirm failure
+    let try_rem_many = db.rem_many(&["num", "list1"]);
+    assert!(try_rem_many.is_err());
+    let try_rem_many_err = try_rem_many.err().unwrap();
+    assert!(matches!(try_rem_many_err.get_type(), ErrorType::Io));
+    // verify the keys are still there
+    assert_eq!(db.get::<i32>("num").unwrap(), 100);
+    assert_eq!(db.llen("list1"), 3);
+
+    // try copy, confirm failure
+    let try_copy = db.copy("num", "num_copy");
+    assert!(try_copy.is_err());
+    let try_copy_err = try_copy.err().unwrap();
+    assert!(matches!(try_copy_err.get_type(), ErrorType::Io));
+    // verify the copy wasn't added
+    assert!(!db.exists("num_copy"));
+
+    // try take, confirm failure
+    let try_take = db.take::<i32>("num");
+    assert!(try_take.is_err());
+    let try_take_err = try_take.err().unwrap();
+    assert!(matches!(try_take_err.get_type(), ErrorType::Io));
+    // verify the key is still there
+    assert_eq!(db.get::<i32>("num").unwrap(), 100);
+
+    // try retain, confirm failure
+    let try_retain = db.retain(|_key, _value: &i32| false);
+    assert!(try_retain.is_err());
+    let try_retain_err = try_retain.err().unwrap();
+    assert!(matches!(try_retain_err.get_type(), ErrorType::Io));
+    // verify the value is still there
+    assert_eq!(db.get::<i32>("num").unwrap(), 100);
+
+    // try update, confirm failure
+    let try_update = db.update("num", |num: Option<i32>| num.unwrap_or(0) + 1);
+    assert!(try_update.is_err());
+    let try_update_err = try_update.err().unwrap();
+    assert!(matches!(try_update_err.get_type(), ErrorType::Io));
+    // verify the old value is still there
+    assert_eq!(db.get::<i32>("num").unwrap(), 100);
+
+    // try swap, confirm failure
+    let try_swap = db.swap("num", "list1");
+    assert!(try_swap.is_err());
+    let try_swap_err = try_swap.err().unwrap();
+    assert!(matches!(try_swap_err.get_type(), ErrorType::Io));
+    // verify the value and the list weren't swapped
+    assert_eq!(db.get::<i32>("num").unwrap(), 100);
+    assert_eq!(db.llen("list1"), 3);
+
+    // try compare_and_swap, confirm failure
+    let try_cas = db.compare_and_swap("num", &100, &200);
+    assert!(try_cas.is_err());
+    let try_cas_err = try_cas.err().unwrap();
+    assert!(matches!(try_cas_err.get_type(), ErrorType::Io));
+    // verify the old value is still there
+    assert_eq!(db.get::<i32>("num").unwrap(), 100);
+
+    // try lmap, confirm failure
+    let try_lmap = db.lmap("list1", |x: i32| x + 1);
+    assert!(try_lmap.is_err());
+    let try_lmap_err = try_lmap.err().unwrap();
+    assert!(matches!(try_lmap_err.get_type(), ErrorType::Io));
+    // verify the items weren't changed
+    assert_eq!(db.lget::<i32>("list1", 0).unwrap(), 1);
+
+    // try ladd_unique, confirm failure
+    let try_ladd_unique = db.ladd_unique("list1", &100);
+    assert!(try_ladd_unique.is_err());
+    let try_ladd_unique_err = try_ladd_unique.err().unwrap();
+    assert!(matches!(try_ladd_unique_err.get_type(), ErrorType::Io));
+    // verify the item wasn't added
+    assert_eq!(db.llen("list1"), 3);
+
+    // try sadd, confirm failure
+    let try_sadd = db.sadd("set1", &100);
+    assert!(try_sadd.is_err());
+    let try_sadd_err = try_sadd.err().unwrap();
+    assert!(matches!(try_sadd_err.get_type(), ErrorType::Io));
+    // verify the member wasn't added
+    assert!(!db.scontains("set1", &100));
+
+    // try hset, confirm failure
+    let try_hset = db.hset("hash1", "field1", &100);
+    assert!(try_hset.is_err());
+    let try_hset_err = try_hset.err().unwrap();
+    assert!(matches!(try_hset_err.get_type(), ErrorType::Io));
+    // verify the field wasn't updated
+    assert_eq!(db.hget::<i32>("hash1", "field1").unwrap(), 1);
+
+    // try replace_all, confirm failure
+    let mut entries = HashMap::new();
+    entries.insert(String::from("num"), 200);
+    let try_replace_all = db.replace_all(entries, true);
+    assert!(try_replace_all.is_err());
+    let try_replace_all_err = try_replace_all.err().unwrap();
+    assert!(matches!(try_replace_all_err.get_type(), ErrorType::Io));
+    // verify the DB wasn't changed
+    assert_eq!(db.get::<i32>("num").unwrap(), 100);
+    assert!(db.exists("string"));
+    assert_eq!(db.llen("list1"), 3);
+
     // unlock the file
     db_file.unlock().unwrap();
 }
+
+#[test]
+fn list_not_found_error_test() {
+    set_test_rsc!("list_not_found_error_test.db");
+
+    let mut db = PickleDb::new_json("list_not_found_error_test.db", PickleDbDumpPolicy::AutoDump);
+    db.lcreate("list1").unwrap().lextend(&[1, 2, 3]);
+
+    // iterate over an existing list
+    assert_eq!(db.try_liter("list1").unwrap().count(), 3);
+
+    // try to iterate over a list that doesn't exist, confirm failure
+    let try_liter = db.try_liter("list2");
+    assert!(try_liter.is_err());
+    let try_liter_err = try_liter.err().unwrap();
+    assert!(matches!(try_liter_err.get_type(), ErrorType::ListNotFound));
+    assert_eq!(try_liter_err.to_string(), "List 'list2' doesn't exist");
+}
+
+#[test]
+fn set_not_found_error_test() {
+    set_test_rsc!("set_not_found_error_test.db");
+
+    let mut db = PickleDb::new_json("set_not_found_error_test.db", PickleDbDumpPolicy::AutoDump);
+
+    // try to add a member to a set that doesn't exist, confirm failure
+    let try_sadd = db.sadd("set1", &1);
+    assert!(try_sadd.is_err());
+    let try_sadd_err = try_sadd.err().unwrap();
+    assert!(matches!(try_sadd_err.get_type(), ErrorType::SetNotFound));
+    assert_eq!(try_sadd_err.to_string(), "Set 'set1' doesn't exist");
+    assert!(!db.sexists("set1"));
+}
+
+#[test]
+fn try_get_error_test() {
+    set_test_rsc!("try_get_error_test.db");
+
+    let mut db = PickleDb::new_json("try_get_error_test.db", PickleDbDumpPolicy::AutoDump);
+    db.set("key1", &String::from("hello")).unwrap();
+
+    // get an existing value with the right type
+    assert_eq!(db.try_get::<String>("key1").unwrap().unwrap(), "hello");
+
+    // get a key that doesn't exist
+    assert!(db.try_get::<String>("key2").unwrap().is_none());
+
+    // get an existing value with a wrong type, confirm failure
+    let try_get = db.try_get::<i32>("key1");
+    assert!(try_get.is_err());
+    let try_get_err = try_get.err().unwrap();
+    assert!(matches!(try_get_err.get_type(), ErrorType::Serialization));
+    assert!(try_get_err.to_string().contains("invalid type"));
+}
+
+#[test]
+fn iterator_item_error_test() {
+    set_test_rsc!("iterator_item_error_test.db");
+
+    let mut db = PickleDb::new_json("iterator_item_error_test.db", PickleDbDumpPolicy::AutoDump);
+    db.set("key1", &String::from("hello")).unwrap();
+    db.lcreate("list1").unwrap().ladd(&String::from("world"));
+
+    // get a value with the right type and with a wrong type, confirm failure
+    for item in db.iter() {
+        assert_eq!(item.try_get_value::<String>().unwrap(), "hello");
+
+        let try_get_value_err = item.try_get_value::<i32>().err().unwrap();
+        assert!(matches!(
+            try_get_value_err.get_type(),
+            ErrorType::Serialization
+        ));
+        assert!(try_get_value_err.to_string().contains("invalid type"));
+    }
+
+    // get a list item with the right type and with a wrong type, confirm failure
+    for item in db.liter("list1") {
+        assert_eq!(item.try_get_item::<String>().unwrap(), "world");
+
+        let try_get_item_err = item.try_get_item::<Vec<i32>>().err().unwrap();
+        assert!(matches!(
+            try_get_item_err.get_type(),
+            ErrorType::Serialization
+        ));
+        assert!(try_get_item_err.to_string().contains("invalid type"));
+    }
+}
+
+#[test]
+fn merge_serialization_mismatch_error_test() {
+    set_test_rsc!("merge_mismatch_error_test.db");
+
+    let mut db = PickleDb::new_json("merge_mismatch_error_test.db", PickleDbDumpPolicy::AutoDump);
+    db.set("key1", &1).unwrap();
+
+    let mut other_db = PickleDb::new_bin("other.db", PickleDbDumpPolicy::NeverDump);
+    other_db.set("key2", &2).unwrap();
+
+    // try to merge a DB with a different serialization method, confirm failure
+    let merge = db.merge(&other_db, PickleDbMergePolicy::Overwrite);
+    assert!(merge.is_err());
+    let merge_err = merge.err().unwrap();
+    assert!(matches!(merge_err.get_type(), ErrorType::Serialization));
+    assert_eq!(
+        merge_err.to_string(),
+        "Cannot merge a DB serialized with Bin into a DB serialized with Json"
+    );
+
+    // nothing was merged
+    assert_eq!(db.total_keys(), 1);
+    assert!(!db.exists("key2"));
+}
+
+#[test]
+fn from_map_serialization_error_test() {
+    set_test_rsc!("from_map_error_test.db");
+
+    // JSON maps must have string keys, so this value can't be serialized
+    let mut bad_value = HashMap::new();
+    bad_value.insert(vec![1, 2], 1);
+    let mut map = HashMap::new();
+    map.insert(String::from("key1"), bad_value);
+
+    // try to create a DB from the map, confirm failure
+    let from_map = PickleDb::from_map(
+        "from_map_error_test.db",
+        PickleDbDumpPolicy::AutoDump,
+        SerializationMethod::Json,
+        &map,
+    );
+    assert!(from_map.is_err());
+    let from_map_err = from_map.err().unwrap();
+    assert!(matches!(from_map_err.get_type(), ErrorType::Serialization));
+
+    // verify the file wasn't created
+    assert!(!std::path::Path::new("from_map_error_test.db").exists());
+}
+
+#[test]
+fn set_serialization_error_test() {
+    set_test_rsc!("set_serialization_error_test.db");
+
+    let mut db = PickleDb::new_json(
+        "set_serialization_error_test.db",
+        PickleDbDumpPolicy::AutoDump,
+    );
+    db.lcreate("list1").unwrap();
+
+    // JSON maps must have string keys, so this value can't be serialized
+    let mut bad_value = HashMap::new();
+    bad_value.insert(vec![1, 2], 1);
+
+    // the error tells which key the value was set to
+    let set_err = db.set("key1", &bad_value).err().unwrap();
+    assert!(matches!(set_err.get_type(), ErrorType::Serialization));
+    assert_eq!(set_err.get_key(), Some("key1"));
+    assert!(set_err
+        .to_string()
+        .starts_with("Cannot serialize the value of 'key1': "));
+
+    let set_many_err = db
+        .set_many(&[("key2", &HashMap::new()), ("key3", &bad_value)])
+        .err()
+        .unwrap();
+    assert_eq!(set_many_err.get_key(), Some("key3"));
+
+    db.ladd("list1", &HashMap::<String, i32>::new());
+    let lset_err = db.lset("list1", 0, &bad_value).err().unwrap();
+    assert_eq!(lset_err.get_key(), Some("list1"));
+
+    // the same goes for the other methods that serialize a value of a key, list, set or hash
+    let cas_err = db
+        .compare_and_swap("key1", &bad_value, &bad_value)
+        .err()
+        .unwrap();
+    assert_eq!(cas_err.get_key(), Some("key1"));
+    let mut entries = HashMap::new();
+    entries.insert(String::from("key4"), bad_value.clone());
+    let replace_all_err = db.replace_all(entries, false).err().unwrap();
+    assert_eq!(replace_all_err.get_key(), Some("key4"));
+    let ladd_unique_err = db.ladd_unique("list1", &bad_value).err().unwrap();
+    assert_eq!(ladd_unique_err.get_key(), Some("list1"));
+    let lrem_value_err = db.lrem_value("list1", &bad_value).err().unwrap();
+    assert_eq!(lrem_value_err.get_key(), Some("list1"));
+    let sadd_err = db.sadd("set1", &bad_value).err().unwrap();
+    assert_eq!(sadd_err.get_key(), Some("set1"));
+    let srem_err = db.srem("set1", &bad_value).err().unwrap();
+    assert_eq!(srem_err.get_key(), Some("set1"));
+    let hset_err = db.hset("hash1", "field1", &bad_value).err().unwrap();
+    assert_eq!(hset_err.get_key(), Some("hash1"));
+    let lsort_err = db
+        .lsort_by("list1", |a: &i32, b: &i32| a.cmp(b))
+        .err()
+        .unwrap();
+    assert_eq!(lsort_err.get_key(), Some("list1"));
+    assert!(lsort_err
+        .to_string()
+        .starts_with("Cannot deserialize the value of 'list1': "));
+
+    // errors that don't relate to a key don't have one
+    let load_err = PickleDb::load_json("no_such_file.db", PickleDbDumpPolicy::NeverDump)
+        .err()
+        .unwrap();
+    assert!(load_err.get_key().is_none());
+
+    assert_eq!(db.total_keys(), 1);
+}
+
+#[test]
+#[should_panic(expected = "Cannot serialize item 1 added to list 'list1'")]
+fn lextend_serialization_error_test() {
+    set_test_rsc!("lextend_serialization_error_test.db");
+
+    let mut db = PickleDb::new_json(
+        "lextend_serialization_error_test.db",
+        PickleDbDumpPolicy::NeverDump,
+    );
+
+    // JSON maps must have string keys, so the second item can't be serialized
+    let mut bad_value = HashMap::new();
+    bad_value.insert(vec![1, 2], 1);
+    db.lcreate("list1")
+        .unwrap()
+        .lextend(&[HashMap::new(), bad_value]);
+}
+
+#[test]
+fn non_utf8_data_error_test() {
+    // JSON and YAML files store the data as strings, so bytes that aren't valid UTF-8 can't be dumped
+    for (ser_method, db_name) in [
+        (
+            SerializationMethod::Json,
+            "non_utf8_data_error_test_json.db",
+        ),
+        (
+            SerializationMethod::Yaml,
+            "non_utf8_data_error_test_yaml.db",
+        ),
+    ] {
+        set_test_rsc!(db_name);
+
+        let mut db = PickleDb::new(db_name, PickleDbDumpPolicy::AutoDump, ser_method);
+        db.set("key1", &1).unwrap();
+
+        // try set_raw, confirm failure
+        let try_set_raw = db.set_raw("key2", vec![0xc1, 0xff]);
+        assert!(try_set_raw.is_err());
+        let try_set_raw_err = try_set_raw.err().unwrap();
+        assert!(matches!(
+            try_set_raw_err.get_type(),
+            ErrorType::Serialization
+        ));
+        assert!(try_set_raw_err.to_string().contains("'key2'"));
+        // verify the value wasn't added
+        assert!(!db.exists("key2"));
+
+        // the invalid data is reported when the DB is dumped later on
+        let mut db = PickleDb::new(db_name, PickleDbDumpPolicy::DumpUponRequest, ser_method);
+        db.set_raw("key2", vec![0xc1, 0xff]).unwrap();
+        let try_dump = db.dump();
+        assert!(try_dump.is_err());
+        assert!(matches!(
+            try_dump.err().unwrap().get_type(),
+            ErrorType::Serialization
+        ));
+
+        // a file that isn't valid UTF-8 can't be loaded
+        std::fs::write(db_name, [0xc1, 0xff]).unwrap();
+        let try_load = PickleDb::load_read_only(db_name, ser_method);
+        assert!(try_load.is_err());
+        assert!(matches!(
+            try_load.err().unwrap().get_type(),
+            ErrorType::Serialization
+        ));
+    }
+}
+
+#[test]
+fn set_over_list_error_test() {
+    set_test_rsc!("set_over_list_error_test.db");
+
+    let mut db = PickleDb::new_json("set_over_list_error_test.db", PickleDbDumpPolicy::AutoDump);
+    db.lcreate("list1").unwrap().lextend(&[1, 2, 3]);
+
+    // temp files are written to a non-existent dir, so dumping the DB always fails
+    let mut db = PickleDbBuilder::new("set_over_list_error_test.db")
+        .temp_dir("non_existent_dir")
+        .load()
+        .unwrap();
+
+    // try set over a list, confirm failure
+    let try_set = db.set("list1", &100);
+    assert!(try_set.is_err());
+    assert!(matches!(try_set.err().unwrap().get_type(), ErrorType::Io));
+    // verify the list is still there
+    assert!(db.get::<i32>("list1").is_none());
+    assert_eq!(db.llen("list1"), 3);
+    assert_eq!(db.lget::<i32>("list1", 2).unwrap(), 3);
+
+    // try set_raw over a list, confirm failure
+    let try_set_raw = db.set_raw("list1", vec![1, 2]);
+    assert!(try_set_raw.is_err());
+    assert_eq!(db.llen("list1"), 3);
+}
+
+#[test]
+fn close_error_test() {
+    set_test_rsc!("close_error_test.db");
+
+    // temp files are written to a non-existent dir, so dumping the DB always fails
+    let mut db = PickleDbBuilder::new("close_error_test.db")
+        .dump_policy(PickleDbDumpPolicy::DumpUponRequest)
+        .temp_dir("non_existent_dir")
+        .build();
+    db.set("num", &100).unwrap();
+
+    // try close, confirm failure
+    let try_close = db.close();
+    assert!(try_close.is_err());
+    let try_close_err = try_close.err().unwrap();
+    assert!(matches!(try_close_err.get_type(), ErrorType::Io));
+
+    // verify the file wasn't created
+    assert!(!std::path::Path::new("close_error_test.db").exists());
+}
+
+#[test]
+fn convert_to_error_test() {
+    set_test_rsc!("convert_to_error_test.db");
+    set_test_rsc!("convert_to_error_test_toml.db");
+
+    let mut db = PickleDb::new_json("convert_to_error_test.db", PickleDbDumpPolicy::AutoDump);
+    db.set("num", &100).unwrap();
+    db.set("none", &None::<i32>).unwrap();
+
+    // TOML doesn't support null values, confirm failure
+    let try_convert = db.convert_to("convert_to_error_test_toml.db", SerializationMethod::Toml);
+    assert!(try_convert.is_err());
+    let try_convert_err = try_convert.err().unwrap();
+    assert!(matches!(
+        try_convert_err.get_type(),
+        ErrorType::Serialization
+    ));
+    // the error specifies which key failed
+    assert!(try_convert_err.to_string().contains("'none'"));
+
+    // verify the file wasn't created
+    assert!(!std::path::Path::new("convert_to_error_test_toml.db").exists());
+}
+
+#[test]
+fn on_change_error_test() {
+    set_test_rsc!("on_change_error_test.db");
+
+    // temp files are written to a non-existent dir, so dumping the DB always fails
+    let mut db = PickleDbBuilder::new("on_change_error_test.db")
+        .temp_dir("non_existent_dir")
+        .build();
+    let changes = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let changes_clone = std::sync::Arc::clone(&changes);
+    db.on_change(move |_| {
+        changes_clone.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    });
+
+    // try set, confirm failure and that the callback wasn't called
+    assert!(db.set("num", &100).is_err());
+    assert!(!db.exists("num"));
+    assert_eq!(changes.load(std::sync::atomic::Ordering::SeqCst), 0);
+}
+
+#[test]
+fn decryption_error_test() {
+    set_test_rsc!("decryption_error_test.db");
+
+    let mut db = PickleDb::new_with_encryption(
+        "decryption_error_test.db",
+        PickleDbDumpPolicy::AutoDump,
+        SerializationMethod::Json,
+        [1u8; 32],
+    );
+    db.set("num", &100).unwrap();
+
+    // try to load with a wrong key, confirm failure
+    let try_load = PickleDb::load_with_encryption(
+        "decryption_error_test.db",
+        PickleDbDumpPolicy::NeverDump,
+        SerializationMethod::Json,
+        [2u8; 32],
+    );
+    assert!(try_load.is_err());
+    assert!(matches!(
+        try_load.err().unwrap().get_type(),
+        ErrorType::Decryption
+    ));
+
+    // try to load without a key, confirm failure
+    let try_load = PickleDb::load_read_only("decryption_error_test.db", SerializationMethod::Json);
+    assert!(try_load.is_err());
+    assert!(matches!(
+        try_load.err().unwrap().get_type(),
+        ErrorType::Decryption
+    ));
+}
+
+#[test]
+fn tampered_ciphertext_error_test() {
+    set_test_rsc!("tampered_ciphertext_error_test.db");
+
+    let mut db = PickleDb::new_with_encryption(
+        "tampered_ciphertext_error_test.db",
+        PickleDbDumpPolicy::AutoDump,
+        SerializationMethod::Json,
+        [1u8; 32],
+    );
+    db.set("num", &100).unwrap();
+
+    // change a single byte of the ciphertext and fix the checksum header so the file isn't
+    // detected as corrupted, which leaves it to the decryption to detect the change
+    let content = std::fs::read("tampered_ciphertext_error_test.db").unwrap();
+    let header_len = "PICKLEDB-CRC32 ".len() + 8 + 1;
+    let mut payload = content[header_len..].to_vec();
+    let last = payload.len() - 1;
+    payload[last] ^= 0x01;
+    let mut tampered_content =
+        format!("PICKLEDB-CRC32 {:08x}\n", crc32fast::hash(&payload)).into_bytes();
+    tampered_content.extend(payload);
+    std::fs::write("tampered_ciphertext_error_test.db", &tampered_content).unwrap();
+
+    // try to load with the right key, confirm failure
+    let try_load = PickleDb::load_with_encryption(
+        "tampered_ciphertext_error_test.db",
+        PickleDbDumpPolicy::NeverDump,
+        SerializationMethod::Json,
+        [1u8; 32],
+    );
+    assert!(try_load.is_err());
+    assert!(matches!(
+        try_load.err().unwrap().get_type(),
+        ErrorType::Decryption
+    ));
+}
+
+#[test]
+fn corrupted_file_error_test() {
+    set_test_rsc!("corrupted_file_error_test.db");
+
+    let mut db = PickleDb::new(
+        "corrupted_file_error_test.db",
+        PickleDbDumpPolicy::AutoDump,
+        SerializationMethod::Bin,
+    );
+    db.set("num", &100).unwrap();
+    db.set("string", &String::from("my string")).unwrap();
+    let content = std::fs::read("corrupted_file_error_test.db").unwrap();
+
+    // change a single byte of the data, confirm failure
+    let mut corrupted_content = content.clone();
+    let last = corrupted_content.len() - 2;
+    corrupted_content[last] ^= 0x01;
+    std::fs::write("corrupted_file_error_test.db", &corrupted_content).unwrap();
+    let try_load =
+        PickleDb::load_read_only("corrupted_file_error_test.db", SerializationMethod::Bin);
+    assert!(try_load.is_err());
+    assert!(matches!(
+        try_load.err().unwrap().get_type(),
+        ErrorType::Corrupted
+    ));
+
+    // truncate the data, confirm failure
+    std::fs::write(
+        "corrupted_file_error_test.db",
+        &content[..content.len() - 5],
+    )
+    .unwrap();
+    let try_load =
+        PickleDb::load_read_only("corrupted_file_error_test.db", SerializationMethod::Bin);
+    assert!(try_load.is_err());
+    assert!(matches!(
+        try_load.err().unwrap().get_type(),
+        ErrorType::Corrupted
+    ));
+
+    // truncate the header, confirm failure
+    std::fs::write("corrupted_file_error_test.db", &content[..18]).unwrap();
+    let try_load =
+        PickleDb::load_read_only("corrupted_file_error_test.db", SerializationMethod::Bin);
+    assert!(try_load.is_err());
+    assert!(matches!(
+        try_load.err().unwrap().get_type(),
+        ErrorType::Corrupted
+    ));
+}
+
+#[test]
+fn parse_serialization_method_error_test() {
+    assert_eq!(
+        "bincode".parse::<SerializationMethod>().unwrap(),
+        SerializationMethod::Bin
+    );
+
+    // parse an unknown serialization method, confirm failure
+    let try_parse = "xml".parse::<SerializationMethod>();
+    assert!(try_parse.is_err());
+    let try_parse_err = try_parse.err().unwrap();
+    assert!(matches!(try_parse_err.get_type(), ErrorType::Serialization));
+    assert_eq!(
+        try_parse_err.to_string(),
+        "Unknown serialization method 'xml'"
+    );
+
+    // a custom serialization method can't be parsed back from its name
+    let try_parse = SerializationMethod::Custom
+        .to_string()
+        .parse::<SerializationMethod>();
+    assert!(matches!(
+        try_parse.err().unwrap().get_type(),
+        ErrorType::Serialization
+    ));
+}
+
+#[test]
+fn disabled_serialization_method_error_test() {
+    set_test_rsc!("disabled_serialization_method_error_test.db");
+
+    // a serialization method that can't be used isn't replaced with another one
+    let mut db = PickleDb::new(
+        "disabled_serialization_method_error_test.db",
+        PickleDbDumpPolicy::AutoDump,
+        SerializationMethod::Custom,
+    );
+    let try_set = db.set("key1", &100);
+    assert!(try_set.is_err());
+    let try_set_err = try_set.err().unwrap();
+    assert!(matches!(try_set_err.get_type(), ErrorType::Serialization));
+    assert_eq!(
+        try_set_err.to_string(),
+        "Cannot serialize the value of 'key1': The Custom serialization method can only be used with a custom serializer"
+    );
+    assert!(!db.exists("key1"));
+
+    // dumping and loading fail as well
+    assert!(db.lcreate("list1").is_err());
+    db.set_flush_on_drop(false);
+    assert!(!std::path::Path::new("disabled_serialization_method_error_test.db").exists());
+    let mut json_db = PickleDb::new_json(
+        "disabled_serialization_method_error_test.db",
+        PickleDbDumpPolicy::AutoDump,
+    );
+    json_db.set("key1", &100).unwrap();
+    let try_load = PickleDb::load_read_only(
+        "disabled_serialization_method_error_test.db",
+        SerializationMethod::Custom,
+    );
+    assert!(try_load.is_err());
+    assert!(matches!(
+        try_load.err().unwrap().get_type(),
+        ErrorType::Serialization
+    ));
+}
+
+#[test]
+fn size_limit_error_test() {
+    set_test_rsc!("size_limit_error_test.db");
+
+    let mut db = PickleDbBuilder::new("size_limit_error_test.db")
+        .dump_policy(PickleDbDumpPolicy::DumpUponRequest)
+        .max_bytes(20)
+        .build();
+
+    // each key-value pair takes the length of the key and its JSON value
+    db.set("key1", &100).unwrap();
+    db.set("key2", &"abcdef").unwrap();
+    assert_eq!(db.memory_footprint(), 19);
+
+    // exceed the limit, confirm failure and that the DB is unchanged
+    let try_set = db.set("key3", &1);
+    assert!(try_set.is_err());
+    let try_set_err = try_set.err().unwrap();
+    assert!(matches!(
+        try_set_err.get_type(),
+        ErrorType::SizeLimitExceeded
+    ));
+    assert_eq!(
+        try_set_err.to_string(),
+        "The DB size limit of 20 bytes would be exceeded"
+    );
+    assert!(!db.exists("key3"));
+    assert!(db
+        .set_with_ttl("key3", &1, std::time::Duration::from_secs(60))
+        .is_err());
+    assert!(db.set_if_absent("key3", &1).is_err());
+    assert!(db.set_many(&[("key1", &1), ("key3", &1)]).is_err());
+    assert_eq!(db.get::<i32>("key1").unwrap(), 100);
+    assert!(db.set("key2", &"abcdefgh").is_err());
+    assert_eq!(db.get::<String>("key2").unwrap(), "abcdef");
+    assert_eq!(db.memory_footprint(), 19);
+
+    // changes that make the DB smaller are allowed
+    db.set("key2", &"a").unwrap();
+    db.set_many(&[("key1", &1)]).unwrap();
+    assert_eq!(db.memory_footprint(), 12);
+
+    // adding list items fails the same way
+    db.lcreate("list1").unwrap();
+    db.lextend("list1", &[1, 2, 3]).unwrap();
+    assert!(db.ladd("list1", &4).is_none());
+    assert!(db.lextend("list1", &[4, 5]).is_none());
+    assert!(db.lpush_front("list1", &0).is_none());
+    assert!(db.linsert("list1", 1, &0).is_none());
+    assert_eq!(db.llen("list1"), 3);
+    assert_eq!(db.memory_footprint(), 20);
+
+    // removing data makes room for more
+    db.rem("key2").unwrap();
+    db.ladd("list1", &4).unwrap();
+    assert_eq!(db.llen("list1"), 4);
+    assert_eq!(db.memory_footprint(), 14);
+
+    // try_ladd() and try_lextend() tell a full DB apart from a list that doesn't exist
+    let try_ladd = db.try_ladd("list1", &12345678);
+    assert!(matches!(
+        try_ladd.err().unwrap().get_type(),
+        ErrorType::SizeLimitExceeded
+    ));
+    let try_lextend = db.try_lextend("list2", &[1]);
+    assert!(matches!(
+        try_lextend.err().unwrap().get_type(),
+        ErrorType::ListNotFound
+    ));
+    db.try_lextend("list1", &[5, 6]).unwrap();
+    assert_eq!(db.llen("list1"), 6);
+    assert_eq!(db.memory_footprint(), 16);
+
+    // creating lists and sets and renaming keys can't exceed the limit either
+    let try_lcreate = db.lcreate("list2");
+    assert!(matches!(
+        try_lcreate.err().unwrap().get_type(),
+        ErrorType::SizeLimitExceeded
+    ));
+    let try_screate = db.screate("set12");
+    assert!(matches!(
+        try_screate.err().unwrap().get_type(),
+        ErrorType::SizeLimitExceeded
+    ));
+    let try_rename = db.rename("key1", "key123456");
+    assert!(matches!(
+        try_rename.err().unwrap().get_type(),
+        ErrorType::SizeLimitExceeded
+    ));
+    assert!(!db.lexists("list2"));
+    assert!(!db.sexists("set12"));
+    assert!(db.exists("key1"));
+    assert_eq!(db.memory_footprint(), 16);
+
+    // the size is kept up to date when data is removed
+    assert_eq!(db.lpop::<i32>("list1", 0).unwrap(), 1);
+    db.rename("key1", "key123").unwrap();
+    assert_eq!(db.memory_footprint(), 17);
+
+    // merging another DB can't exceed the limit either
+    let mut other_db = PickleDb::new_json(
+        "size_limit_error_test_other.db",
+        PickleDbDumpPolicy::NeverDump,
+    );
+    other_db.set("key9", &100).unwrap();
+    let try_merge = db.merge(&other_db, PickleDbMergePolicy::Overwrite);
+    assert!(matches!(
+        try_merge.err().unwrap().get_type(),
+        ErrorType::SizeLimitExceeded
+    ));
+    assert!(!db.exists("key9"));
+    assert_eq!(db.memory_footprint(), 17);
+    other_db.rem("key9").unwrap();
+    other_db.set("k", &1).unwrap();
+    db.merge(&other_db, PickleDbMergePolicy::Overwrite).unwrap();
+    assert_eq!(db.memory_footprint(), 19);
+}
+
+#[test]
+fn lock_error_test() {
+    set_test_rsc!("lock_error_test.db");
+
+    let lock_builder = |dump_policy| {
+        PickleDbBuilder::new("lock_error_test.db")
+            .dump_policy(dump_policy)
+            .lock(true)
+    };
+
+    // a new DB takes the lock when it's first dumped
+    let mut db = lock_builder(PickleDbDumpPolicy::AutoDump).build();
+    db.set("num", &100).unwrap();
+    assert!(std::path::Path::new("lock_error_test.db.lock").exists());
+
+    // try to load the locked DB, confirm failure
+    let try_load = lock_builder(PickleDbDumpPolicy::AutoDump).load();
+    assert!(try_load.is_err());
+    let try_load_err = try_load.err().unwrap();
+    assert!(matches!(try_load_err.get_type(), ErrorType::Locked));
+    assert_eq!(
+        try_load_err.to_string(),
+        "The DB file is locked by 'lock_error_test.db.lock'"
+    );
+
+    // a read-only DB can't be loaded either, but loading without a lock is still possible
+    let try_load = lock_builder(PickleDbDumpPolicy::NeverDump).load();
+    assert!(matches!(
+        try_load.err().unwrap().get_type(),
+        ErrorType::Locked
+    ));
+    let unlocked_db =
+        PickleDb::load_read_only("lock_error_test.db", SerializationMethod::Json).unwrap();
+    assert_eq!(unlocked_db.get::<i32>("num").unwrap(), 100);
+
+    // the lock is released when the DB is dropped
+    drop(db);
+
+    // read-only DBs share the lock
+    let read_db1 = lock_builder(PickleDbDumpPolicy::NeverDump).load().unwrap();
+    let read_db2 = lock_builder(PickleDbDumpPolicy::NeverDump).load().unwrap();
+    assert_eq!(read_db1.get::<i32>("num").unwrap(), 100);
+    assert_eq!(read_db2.get::<i32>("num").unwrap(), 100);
+    assert!(matches!(
+        lock_builder(PickleDbDumpPolicy::AutoDump)
+            .load()
+            .err()
+            .unwrap()
+            .get_type(),
+        ErrorType::Locked
+    ));
+
+    // try to dump a new DB while the file is locked, confirm failure and that the change is rolled back
+    let mut db = lock_builder(PickleDbDumpPolicy::AutoDump).build();
+    let try_set = db.set("num", &200);
+    assert!(matches!(
+        try_set.err().unwrap().get_type(),
+        ErrorType::Locked
+    ));
+    assert!(!db.exists("num"));
+
+    // once the lock is released the DB can be dumped
+    drop(read_db1);
+    drop(read_db2);
+    db.set("num", &200).unwrap();
+    drop(db);
+    let db = lock_builder(PickleDbDumpPolicy::AutoDump).load().unwrap();
+    assert_eq!(db.get::<i32>("num").unwrap(), 200);
+
+    // wait for the lock instead of failing
+    let waiting_thread = std::thread::spawn(|| {
+        PickleDbBuilder::new("lock_error_test.db")
+            .lock(true)
+            .wait_for_lock(true)
+            .load()
+            .unwrap()
+            .get::<i32>("num")
+    });
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    assert!(!waiting_thread.is_finished());
+    drop(db);
+    assert_eq!(waiting_thread.join().unwrap(), Some(200));
+
+    std::fs::remove_file("lock_error_test.db.lock").unwrap();
+}
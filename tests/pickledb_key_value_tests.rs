@@ -1,7 +1,9 @@
 #![allow(clippy::float_cmp)]
 
-use pickledb::{PickleDb, PickleDbDumpPolicy, SerializationMethod};
+use pickledb::{PickleDb, PickleDbDumpPolicy, SerializationMethod, ValueKind};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
 
 mod common;
 
@@ -10,7 +12,7 @@ extern crate rstest;
 
 use rstest::rstest_parametrize;
 
-#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3))]
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
 fn basic_set_get(ser_method_int: i32) {
     test_setup!("basic_set_get", ser_method_int, db_name);
 
@@ -58,7 +60,7 @@ fn basic_set_get(ser_method_int: i32) {
     assert_eq!(db.get::<Coor>("struct").unwrap().y, mycoor.y);
 }
 
-#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3))]
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
 fn set_load_get(ser_method_int: i32) {
     test_setup!("set_load_get", ser_method_int, db_name);
 
@@ -113,7 +115,7 @@ fn set_load_get(ser_method_int: i32) {
     assert_eq!(read_db.get::<Coor>("struct").unwrap().y, mycoor.y);
 }
 
-#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3))]
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
 fn set_load_get_auto_dump(ser_method_int: i32) {
     test_setup!("set_load_get_auto_dump", ser_method_int, db_name);
 
@@ -164,7 +166,7 @@ fn set_load_get_auto_dump(ser_method_int: i32) {
     assert_eq!(read_db.get::<Coor>("struct").unwrap().y, mycoor.y);
 }
 
-#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3))]
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
 fn set_load_get_auto_dump2(ser_method_int: i32) {
     test_setup!("set_load_get_auto_dump2", ser_method_int, db_name);
 
@@ -221,7 +223,7 @@ fn set_load_get_auto_dump2(ser_method_int: i32) {
     }
 }
 
-#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3))]
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
 fn set_special_strings(ser_method_int: i32) {
     test_setup!("set_special_strings", ser_method_int, db_name);
 
@@ -268,7 +270,7 @@ fn set_special_strings(ser_method_int: i32) {
     );
 }
 
-#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3))]
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
 fn edge_cases(ser_method_int: i32) {
     test_setup!("edge_cases", ser_method_int, db_name);
 
@@ -295,7 +297,7 @@ fn edge_cases(ser_method_int: i32) {
     }
 }
 
-#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3))]
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
 fn get_all_keys(ser_method_int: i32) {
     test_setup!("get_all_keys", ser_method_int, db_name);
 
@@ -314,6 +316,8 @@ fn get_all_keys(ser_method_int: i32) {
 
     // verify we have 10 keys
     assert_eq!(db.total_keys(), 10);
+    assert_eq!(db.value_count(), 10);
+    assert_eq!(db.list_count(), 0);
 
     // get all keys
     let keys = db.get_all();
@@ -325,9 +329,48 @@ fn get_all_keys(ser_method_int: i32) {
     for i in 0..9 {
         assert!(keys.iter().any(|key| key == &format!("{}{}", "key", i)));
     }
+
+    // add 2 lists and verify they're counted separately
+    db.lcreate("list1").unwrap();
+    db.lcreate("list2").unwrap().ladd(&num);
+    assert_eq!(db.total_keys(), 12);
+    assert_eq!(db.value_count(), 10);
+    assert_eq!(db.list_count(), 2);
+
+    // get value keys and list keys separately
+    let mut value_keys = db.get_value_keys();
+    value_keys.sort();
+    assert_eq!(
+        value_keys,
+        (0..10)
+            .map(|i| format!("{}{}", "key", i))
+            .collect::<Vec<String>>()
+    );
+    let mut list_keys = db.get_list_keys();
+    list_keys.sort();
+    assert_eq!(list_keys, vec!["list1", "list2"]);
+
+    // iterate over value keys and list keys without cloning them
+    let mut value_keys_iter: Vec<&str> = db.value_keys().collect();
+    value_keys_iter.sort_unstable();
+    assert_eq!(value_keys_iter, value_keys);
+    let mut list_keys_iter: Vec<&str> = db.list_keys().collect();
+    list_keys_iter.sort_unstable();
+    assert_eq!(list_keys_iter, list_keys);
+
+    // iterate over all keys without cloning them
+    let mut all_keys_iter: Vec<&str> = db.all_keys().collect();
+    all_keys_iter.sort_unstable();
+    let mut all_keys = db.get_all();
+    all_keys.sort();
+    assert_eq!(all_keys_iter, all_keys);
+    assert_eq!(
+        db.all_keys().filter(|key| key.starts_with("list")).count(),
+        2
+    );
 }
 
-#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3))]
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
 fn rem_keys(ser_method_int: i32) {
     test_setup!("rem_keys", ser_method_int, db_name);
 
@@ -352,12 +395,12 @@ fn rem_keys(ser_method_int: i32) {
     assert_eq!(db.total_keys(), 8);
 
     // verify both keys were removed
-    for i in vec![5, 8].iter() {
+    for i in [5, 8].iter() {
         assert!(!db.exists(&format!("{}{}", "key", i)));
     }
 
     // verify the other keys are still there
-    for i in vec![0, 1, 2, 3, 4, 6, 7, 9].iter() {
+    for i in [0, 1, 2, 3, 4, 6, 7, 9].iter() {
         assert!(db.exists(&format!("{}{}", "key", i)));
     }
 
@@ -366,7 +409,7 @@ fn rem_keys(ser_method_int: i32) {
     assert_eq!(read_db.total_keys(), 8);
 }
 
-#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3))]
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
 fn iter_test(ser_method_int: i32) {
     test_setup!("iter_test", ser_method_int, db_name);
 
@@ -377,7 +420,7 @@ fn iter_test(ser_method_int: i32) {
         ser_method!(ser_method_int),
     );
 
-    let keys = vec!["key1", "key2", "key3", "key4", "key5"];
+    let keys = ["key1", "key2", "key3", "key4", "key5"];
     // add a few keys and values
     db.set(keys[0], &1).unwrap();
     db.set(keys[1], &1.1).unwrap();
@@ -386,7 +429,7 @@ fn iter_test(ser_method_int: i32) {
     db.set(keys[4], &('a', 'b', 'c')).unwrap();
 
     // iterate the db
-    let mut keys_seen = vec![false, false, false, false, false];
+    let mut keys_seen = [false, false, false, false, false];
     for key_value in db.iter() {
         // find the index of the current key in the keys vec
         let index = keys.iter().position(|&k| k == key_value.get_key()).unwrap();
@@ -413,4 +456,1333 @@ fn iter_test(ser_method_int: i32) {
 
     // verify all 5 keys were seen
     assert_eq!(keys_seen.iter().filter(|&t| *t).count(), 5);
+
+    // the iterator knows how many keys are left
+    let mut db_iter = db.iter();
+    assert_eq!(db_iter.len(), 5);
+    db_iter.next();
+    assert_eq!(db_iter.size_hint(), (4, Some(4)));
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn iter_typed_test(ser_method_int: i32) {
+    test_setup!("iter_typed_test", ser_method_int, db_name);
+
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    db.set("key1", &(1, 2)).unwrap();
+    db.set("key2", &(3, 4)).unwrap();
+    db.set("key3", &true).unwrap();
+    db.lcreate("list1").unwrap().lextend(&[(5, 6)]);
+
+    // only the values that are deserialized successfully are returned, lists are skipped
+    let mut entries = db.iter_typed::<(i32, i32)>().collect::<Vec<_>>();
+    entries.sort_unstable();
+    assert_eq!(entries, vec![("key1", (1, 2)), ("key2", (3, 4))]);
+    assert_eq!(db.iter_typed::<(i32, i32, i32)>().count(), 0);
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn find_keys_by_value_test(ser_method_int: i32) {
+    test_setup!("find_keys_by_value_test", ser_method_int, db_name);
+
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    db.set("key1", &String::from("value1")).unwrap();
+    db.set("key2", &String::from("value2")).unwrap();
+    db.set("key3", &String::from("value1")).unwrap();
+    db.set_with_ttl("key4", &String::from("value2"), Duration::from_secs(0))
+        .unwrap();
+    db.lcreate("list1")
+        .unwrap()
+        .lextend(&[String::from("value3")]);
+
+    // find a single key
+    assert_eq!(db.find_key_by_value(&String::from("value2")), Some("key2"));
+    let key = db.find_key_by_value(&String::from("value1")).unwrap();
+    assert!(key == "key1" || key == "key3");
+
+    // find all keys, expired keys and lists are skipped
+    let mut keys = db.find_keys_by_value(&String::from("value1"));
+    keys.sort_unstable();
+    assert_eq!(keys, vec!["key1", "key3"]);
+    assert_eq!(db.find_keys_by_value(&String::from("value2")), vec!["key2"]);
+
+    // values that aren't found
+    assert!(db.find_key_by_value(&String::from("value3")).is_none());
+    assert!(db.find_keys_by_value(&String::from("value3")).is_empty());
+    assert!(db.find_key_by_value(&100).is_none());
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn set_if_absent_test(ser_method_int: i32) {
+    test_setup!("set_if_absent_test", ser_method_int, db_name);
+
+    // create a db with auto_dump == true
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    // set a key that doesn't exist
+    assert!(db.set_if_absent("num", &100).unwrap());
+    assert_eq!(db.get::<i32>("num").unwrap(), 100);
+
+    // try to set the same key again, the original value should remain
+    assert!(!db.set_if_absent("num", &200).unwrap());
+    assert_eq!(db.get::<i32>("num").unwrap(), 100);
+
+    // a list with the same name is also considered as an existing key
+    db.lcreate("list1").unwrap().ladd(&1);
+    assert!(!db.set_if_absent("list1", &300).unwrap());
+    assert!(db.lexists("list1"));
+    assert!(db.get::<i32>("list1").is_none());
+
+    // verify the change was dumped to the file
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert_eq!(read_db.get::<i32>("num").unwrap(), 100);
+    assert_eq!(read_db.llen("list1"), 1);
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn vexists_test(ser_method_int: i32) {
+    test_setup!("vexists_test", ser_method_int, db_name);
+
+    // create a db with auto_dump == true
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    db.set("num", &100).unwrap();
+    db.lcreate("list1").unwrap().ladd(&1);
+
+    // a key-value pair
+    assert!(db.exists("num"));
+    assert!(db.vexists("num"));
+    assert!(!db.lexists("num"));
+
+    // a list
+    assert!(db.exists("list1"));
+    assert!(!db.vexists("list1"));
+    assert!(db.lexists("list1"));
+
+    // a key that doesn't exist
+    assert!(!db.exists("key1"));
+    assert!(!db.vexists("key1"));
+    assert!(!db.lexists("key1"));
+
+    // replace the value with a list and the list with a value
+    db.lcreate("num").unwrap();
+    db.set("list1", &200).unwrap();
+    assert!(!db.vexists("num"));
+    assert!(db.lexists("num"));
+    assert!(db.vexists("list1"));
+    assert!(!db.lexists("list1"));
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn get_or_insert_with_test(ser_method_int: i32) {
+    test_setup!("get_or_insert_with_test", ser_method_int, db_name);
+
+    // create a db with auto_dump == true
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    // key doesn't exist, the default value should be stored and returned
+    assert_eq!(
+        db.get_or_insert_with("string", || String::from("default"))
+            .unwrap(),
+        String::from("default")
+    );
+
+    // key exists, the stored value should be returned and the closure shouldn't be called
+    assert_eq!(
+        db.get_or_insert_with::<String, _>("string", || panic!())
+            .unwrap(),
+        String::from("default")
+    );
+
+    // key exists but holds a value of a different type, it should be overridden
+    db.set("other", &String::from("abc")).unwrap();
+    assert_eq!(
+        db.get_or_insert_with("other", || vec![1, 2, 3]).unwrap(),
+        vec![1, 2, 3]
+    );
+
+    // verify the values were dumped to the file
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert_eq!(
+        read_db.get::<String>("string").unwrap(),
+        String::from("default")
+    );
+    assert_eq!(read_db.get::<Vec<i32>>("other").unwrap(), vec![1, 2, 3]);
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn get_or_default_test(ser_method_int: i32) {
+    test_setup!("get_or_default_test", ser_method_int, db_name);
+
+    // create a db with auto_dump == true
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    db.set("num", &100).unwrap();
+    db.set("vec", &vec![1, 2, 3]).unwrap();
+    db.lcreate("list1").unwrap().ladd(&1);
+
+    // existing keys return their values
+    assert_eq!(db.get_or_default::<i32>("num"), 100);
+    assert_eq!(db.get_or_default::<Vec<i32>>("vec"), vec![1, 2, 3]);
+
+    // a key that doesn't exist returns the default value
+    assert_eq!(db.get_or_default::<i32>("key1"), 0);
+    assert_eq!(db.get_or_default::<String>("key1"), "");
+    let map: HashMap<String, Vec<i32>> = db.get_or_default("key1");
+    assert!(map.is_empty());
+
+    // a list returns the default value
+    assert!(db.get_or_default::<Vec<i32>>("list1").is_empty());
+
+    // a value of a different type returns the default value
+    // (Bincode isn't self-describing so the vector may be deserialized as a string)
+    if ser_method_int != 1 {
+        assert_eq!(db.get_or_default::<String>("vec"), "");
+    }
+
+    // the DB isn't changed
+    assert!(!db.exists("key1"));
+    assert_eq!(db.total_keys(), 3);
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn get_cached_test(ser_method_int: i32) {
+    test_setup!("get_cached_test", ser_method_int, db_name);
+
+    // create a db with auto_dump == true
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    db.set("num", &100).unwrap();
+    db.set("vec", &vec![1, 2, 3]).unwrap();
+    db.lcreate("list1").unwrap().ladd(&1);
+
+    // cached values are the same as the stored values
+    assert_eq!(db.get_cached::<i32>("num").unwrap(), 100);
+    assert_eq!(db.get_cached::<i32>("num").unwrap(), 100);
+    assert_eq!(db.get_cached::<Vec<i32>>("vec").unwrap(), vec![1, 2, 3]);
+    assert_eq!(db.get_cached::<Vec<i32>>("vec").unwrap(), vec![1, 2, 3]);
+
+    // keys that don't exist and lists aren't found
+    assert!(db.get_cached::<i32>("key1").is_none());
+    assert!(db.get_cached::<Vec<i32>>("list1").is_none());
+
+    // changing a key drops its cached value
+    db.set("num", &200).unwrap();
+    assert_eq!(db.get_cached::<i32>("num").unwrap(), 200);
+    db.batch().set("num", &300).unwrap();
+    assert_eq!(db.get_cached::<i32>("num").unwrap(), 300);
+    assert!(db.rem("num").unwrap());
+    assert!(db.get_cached::<i32>("num").is_none());
+
+    // a key that was created after it wasn't found is read
+    db.set("key1", &1).unwrap();
+    assert_eq!(db.get_cached::<i32>("key1").unwrap(), 1);
+
+    // renaming and swapping keys drops their cached values
+    assert!(db.rename("vec", "vec2").unwrap());
+    assert!(db.get_cached::<Vec<i32>>("vec").is_none());
+    assert_eq!(db.get_cached::<Vec<i32>>("vec2").unwrap(), vec![1, 2, 3]);
+    db.set("vec", &vec![4, 5]).unwrap();
+    assert_eq!(db.get_cached::<Vec<i32>>("vec").unwrap(), vec![4, 5]);
+    assert!(db.swap("vec", "vec2").unwrap());
+    assert_eq!(db.get_cached::<Vec<i32>>("vec").unwrap(), vec![1, 2, 3]);
+    assert_eq!(db.get_cached::<Vec<i32>>("vec2").unwrap(), vec![4, 5]);
+
+    // replacing a value with a list drops the cached value
+    db.lcreate("key1").unwrap();
+    assert!(db.get_cached::<i32>("key1").is_none());
+
+    // clearing the DB drops all cached values
+    db.clear().unwrap();
+    assert!(db.get_cached::<Vec<i32>>("vec").is_none());
+    assert!(db.get_cached::<Vec<i32>>("vec2").is_none());
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn update_test(ser_method_int: i32) {
+    test_setup!("update_test", ser_method_int, db_name);
+
+    // create a db with auto_dump == true
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    // key doesn't exist, the closure should get None
+    assert!(!db
+        .update("counter", |count: Option<i32>| {
+            assert!(count.is_none());
+            count.unwrap_or(0) + 1
+        })
+        .unwrap());
+    assert_eq!(db.get::<i32>("counter").unwrap(), 1);
+
+    // key exists, the closure should get the current value
+    for _ in 0..5 {
+        assert!(db
+            .update("counter", |count: Option<i32>| count.unwrap() + 1)
+            .unwrap());
+    }
+    assert_eq!(db.get::<i32>("counter").unwrap(), 6);
+
+    // update a value of a more complex type
+    db.set("vec", &vec![1, 2, 3]).unwrap();
+    assert!(db
+        .update("vec", |vec: Option<Vec<i32>>| {
+            let mut vec = vec.unwrap();
+            vec.push(4);
+            vec
+        })
+        .unwrap());
+    assert_eq!(db.get::<Vec<i32>>("vec").unwrap(), vec![1, 2, 3, 4]);
+
+    // key holds a value of a different type, the closure should get None and the value is overridden
+    // (Bincode isn't self-describing so the string may be deserialized as a number)
+    if ser_method_int != 1 {
+        db.set("string", &String::from("abc")).unwrap();
+        assert!(!db
+            .update("string", |num: Option<i32>| {
+                assert!(num.is_none());
+                100
+            })
+            .unwrap());
+        assert_eq!(db.get::<i32>("string").unwrap(), 100);
+    }
+
+    // a list with the same name is replaced by the value
+    db.lcreate("list1").unwrap().lextend(&[1, 2, 3]);
+    assert!(!db
+        .update("list1", |num: Option<i32>| num.unwrap_or(10))
+        .unwrap());
+    assert!(!db.lexists("list1"));
+    assert_eq!(db.get::<i32>("list1").unwrap(), 10);
+
+    // verify the values were dumped to the file
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert_eq!(read_db.get::<i32>("counter").unwrap(), 6);
+    assert_eq!(read_db.get::<Vec<i32>>("vec").unwrap(), vec![1, 2, 3, 4]);
+    assert_eq!(read_db.get::<i32>("list1").unwrap(), 10);
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn compare_and_swap_test(ser_method_int: i32) {
+    test_setup!("compare_and_swap_test", ser_method_int, db_name);
+
+    // create a db with auto_dump == true
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    db.set("num", &100).unwrap();
+    db.set("string", &String::from("my string")).unwrap();
+    db.lcreate("list1").unwrap().ladd(&100);
+
+    // the value matches so it's swapped
+    assert!(db.compare_and_swap("num", &100, &200).unwrap());
+    assert_eq!(db.get::<i32>("num").unwrap(), 200);
+    assert!(db
+        .compare_and_swap("string", &String::from("my string"), &String::from("new"))
+        .unwrap());
+    assert_eq!(db.get::<String>("string").unwrap(), "new");
+
+    // the value doesn't match so nothing changes
+    assert!(!db.compare_and_swap("num", &100, &300).unwrap());
+    assert_eq!(db.get::<i32>("num").unwrap(), 200);
+
+    // keys that don't exist and lists never match
+    assert!(!db.compare_and_swap("key1", &100, &300).unwrap());
+    assert!(!db.exists("key1"));
+    assert!(!db.compare_and_swap("list1", &100, &300).unwrap());
+    assert_eq!(db.llen("list1"), 1);
+
+    // an expired key doesn't match
+    db.set_with_ttl("ttl", &1, std::time::Duration::from_millis(1))
+        .unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(10));
+    assert!(!db.compare_and_swap("ttl", &1, &2).unwrap());
+
+    // the swap is dumped to the file
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert_eq!(read_db.get::<i32>("num").unwrap(), 200);
+    assert_eq!(read_db.get::<String>("string").unwrap(), "new");
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn rename_test(ser_method_int: i32) {
+    test_setup!("rename_test", ser_method_int, db_name);
+
+    // create a db with auto_dump == true
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    db.set("key1", &100).unwrap();
+    db.set("key2", &String::from("value2")).unwrap();
+    db.lcreate("list1").unwrap().lextend(&[1, 2, 3]);
+
+    // rename a key that doesn't exist
+    assert!(!db.rename("key3", "key4").unwrap());
+    assert!(!db.exists("key4"));
+
+    // rename a key-value pair
+    assert!(db.rename("key1", "key3").unwrap());
+    assert!(!db.exists("key1"));
+    assert_eq!(db.get::<i32>("key3").unwrap(), 100);
+
+    // rename a key-value pair over an existing key
+    assert!(db.rename("key3", "key2").unwrap());
+    assert!(!db.exists("key3"));
+    assert_eq!(db.get::<i32>("key2").unwrap(), 100);
+
+    // rename a list over an existing key
+    assert!(db.rename("list1", "key2").unwrap());
+    assert!(!db.exists("list1"));
+    assert!(db.get::<i32>("key2").is_none());
+    assert_eq!(db.llen("key2"), 3);
+    assert_eq!(db.lget::<i32>("key2", 2).unwrap(), 3);
+    assert_eq!(db.total_keys(), 1);
+
+    // verify the changes were dumped to the file
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert_eq!(read_db.total_keys(), 1);
+    assert_eq!(read_db.llen("key2"), 3);
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn copy_test(ser_method_int: i32) {
+    test_setup!("copy_test", ser_method_int, db_name);
+
+    // create a db with auto_dump == true
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    db.set("key1", &100).unwrap();
+    db.set("key2", &String::from("value2")).unwrap();
+    db.lcreate("list1").unwrap().lextend(&[1, 2, 3]);
+
+    // copy a key that doesn't exist
+    assert!(!db.copy("key3", "key4").unwrap());
+    assert!(!db.exists("key4"));
+
+    // copy a key-value pair
+    assert!(db.copy("key1", "key3").unwrap());
+    assert_eq!(db.get::<i32>("key1").unwrap(), 100);
+    assert_eq!(db.get::<i32>("key3").unwrap(), 100);
+
+    // the copy is independent of the original
+    db.set("key3", &300).unwrap();
+    assert_eq!(db.get::<i32>("key1").unwrap(), 100);
+
+    // copy a key-value pair over an existing key
+    assert!(db.copy("key1", "key2").unwrap());
+    assert_eq!(db.get::<i32>("key2").unwrap(), 100);
+
+    // copy a list over an existing key
+    assert!(db.copy("list1", "key2").unwrap());
+    assert!(db.get::<i32>("key2").is_none());
+    assert_eq!(db.llen("key2"), 3);
+    db.ladd("key2", &4);
+    assert_eq!(db.llen("list1"), 3);
+    assert_eq!(db.llen("key2"), 4);
+
+    // copying a key onto itself doesn't change it
+    assert!(db.copy("key1", "key1").unwrap());
+    assert_eq!(db.get::<i32>("key1").unwrap(), 100);
+    assert_eq!(db.total_keys(), 4);
+
+    // verify the changes were dumped to the file
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert_eq!(read_db.total_keys(), 4);
+    assert_eq!(read_db.get::<i32>("key1").unwrap(), 100);
+    assert_eq!(read_db.get::<i32>("key3").unwrap(), 300);
+    assert_eq!(read_db.llen("list1"), 3);
+    assert_eq!(read_db.llen("key2"), 4);
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn swap_test(ser_method_int: i32) {
+    test_setup!("swap_test", ser_method_int, db_name);
+
+    // create a db with auto_dump == true
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    db.set("active", &String::from("config A")).unwrap();
+    db.set("staged", &String::from("config B")).unwrap();
+    db.lcreate("list1").unwrap().lextend(&[1, 2, 3]);
+    db.set_with_ttl("ttl_key", &100, Duration::from_secs(3600))
+        .unwrap();
+
+    // swap two values
+    assert!(db.swap("active", "staged").unwrap());
+    assert_eq!(db.get::<String>("active").unwrap(), "config B");
+    assert_eq!(db.get::<String>("staged").unwrap(), "config A");
+
+    // swap a value and a list
+    assert!(db.swap("staged", "list1").unwrap());
+    assert_eq!(db.llen("staged"), 3);
+    assert!(!db.vexists("staged"));
+    assert_eq!(db.get::<String>("list1").unwrap(), "config A");
+    assert!(!db.lexists("list1"));
+
+    // swap a key with itself
+    assert!(db.swap("active", "active").unwrap());
+    assert_eq!(db.get::<String>("active").unwrap(), "config B");
+
+    // swap with a key that doesn't exist, nothing should change
+    assert!(!db.swap("active", "key1").unwrap());
+    assert!(!db.swap("key1", "active").unwrap());
+    assert_eq!(db.get::<String>("active").unwrap(), "config B");
+    assert!(!db.exists("key1"));
+
+    // the expiry time moves with the value
+    assert!(db.swap("ttl_key", "active").unwrap());
+    assert_eq!(db.get::<i32>("active").unwrap(), 100);
+    assert_eq!(db.get::<String>("ttl_key").unwrap(), "config B");
+    db.set_with_ttl("short", &1, Duration::from_millis(100))
+        .unwrap();
+    assert!(db.swap("short", "ttl_key").unwrap());
+    std::thread::sleep(Duration::from_millis(200));
+    assert!(!db.exists("ttl_key"));
+    assert_eq!(db.get::<String>("short").unwrap(), "config B");
+
+    // an expired key is treated as if it doesn't exist
+    assert!(!db.swap("ttl_key", "short").unwrap());
+
+    // verify the changes were dumped to the file
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert_eq!(read_db.get::<i32>("active").unwrap(), 100);
+    assert_eq!(read_db.llen("staged"), 3);
+    assert_eq!(read_db.get::<String>("list1").unwrap(), "config A");
+    assert_eq!(read_db.get::<String>("short").unwrap(), "config B");
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn clear_test(ser_method_int: i32) {
+    test_setup!("clear_test", ser_method_int, db_name);
+
+    // create a db with auto_dump == true
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    // add some keys and lists
+    for i in 0..10 {
+        db.set(&format!("{}{}", "key", i), &i).unwrap();
+    }
+    db.lcreate("list1").unwrap().lextend(&[1, 2, 3]);
+    assert_eq!(db.total_keys(), 11);
+
+    // clear the DB
+    db.clear().unwrap();
+    assert_eq!(db.total_keys(), 0);
+    assert!(!db.exists("key0"));
+    assert!(!db.lexists("list1"));
+
+    // verify the DB is also empty in the file
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert_eq!(read_db.total_keys(), 0);
+
+    // verify the DB can be used after it was cleared
+    db.set("key1", &1).unwrap();
+    assert_eq!(db.get::<i32>("key1").unwrap(), 1);
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn replace_all_test(ser_method_int: i32) {
+    test_setup!("replace_all_test", ser_method_int, db_name);
+
+    // create a db with auto_dump == true
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    db.set("key1", &1).unwrap();
+    db.set_with_ttl("key2", &2, Duration::from_secs(100))
+        .unwrap();
+    db.lcreate("list1").unwrap().lextend(&[1, 2, 3]);
+    db.lcreate("list2").unwrap();
+
+    // replace the values and keep the lists, except the one that is overridden by a value
+    let mut entries = HashMap::new();
+    entries.insert(String::from("key3"), 3);
+    entries.insert(String::from("key4"), 4);
+    entries.insert(String::from("list2"), 5);
+    db.replace_all(entries, false).unwrap();
+    assert_eq!(db.total_keys(), 4);
+    assert!(!db.exists("key1"));
+    assert!(!db.exists("key2"));
+    assert_eq!(db.get::<i32>("key3").unwrap(), 3);
+    assert_eq!(db.get::<i32>("list2").unwrap(), 5);
+    assert!(!db.lexists("list2"));
+    assert_eq!(db.llen("list1"), 3);
+
+    // verify the changes were dumped to the file
+    {
+        let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+        assert_eq!(read_db.total_keys(), 4);
+        assert_eq!(read_db.get::<i32>("key4").unwrap(), 4);
+        assert_eq!(read_db.llen("list1"), 3);
+    }
+
+    // replace everything, including the lists
+    let mut entries = HashMap::new();
+    entries.insert(String::from("key5"), String::from("value5"));
+    db.replace_all(entries, true).unwrap();
+    assert_eq!(db.total_keys(), 1);
+    assert!(!db.lexists("list1"));
+    assert_eq!(db.get::<String>("key5").unwrap(), "value5");
+
+    // replace everything with nothing
+    db.replace_all(HashMap::<String, i32>::new(), true).unwrap();
+    assert!(db.is_empty());
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert!(read_db.is_empty());
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn set_many_test(ser_method_int: i32) {
+    test_setup!("set_many_test", ser_method_int, db_name);
+
+    // create a db with auto_dump == true
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    db.set("key1", &String::from("value1")).unwrap();
+    db.lcreate("key2").unwrap().lextend(&[1, 2, 3]);
+
+    // set multiple keys, overriding an existing value and an existing list
+    let values = [
+        String::from("new value1"),
+        String::from("value2"),
+        String::from("value3"),
+    ];
+    db.set_many(&[
+        ("key1", &values[0]),
+        ("key2", &values[1]),
+        ("key3", &values[2]),
+    ])
+    .unwrap();
+    assert_eq!(db.total_keys(), 3);
+    assert!(!db.lexists("key2"));
+    assert_eq!(db.get::<String>("key1").unwrap(), "new value1");
+    assert_eq!(db.get::<String>("key2").unwrap(), "value2");
+    assert_eq!(db.get::<String>("key3").unwrap(), "value3");
+
+    // set an empty slice
+    db.set_many::<i32>(&[]).unwrap();
+    assert_eq!(db.total_keys(), 3);
+
+    // verify the changes were dumped to the file
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert_eq!(read_db.total_keys(), 3);
+    assert_eq!(read_db.get::<String>("key1").unwrap(), "new value1");
+    assert_eq!(read_db.get::<String>("key3").unwrap(), "value3");
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn set_with_ttl_test(ser_method_int: i32) {
+    test_setup!("set_with_ttl_test", ser_method_int, db_name);
+
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    // set keys with short and long TTLs, and a key without TTL
+    db.set_with_ttl("short", &1, Duration::from_millis(100))
+        .unwrap();
+    db.set_with_ttl("long", &String::from("long"), Duration::from_secs(3600))
+        .unwrap();
+    db.set("no_ttl", &3).unwrap();
+    db.set_with_ttl("overridden", &4, Duration::from_millis(100))
+        .unwrap();
+    db.set("overridden", &5).unwrap();
+
+    assert_eq!(db.get::<i32>("short").unwrap(), 1);
+    assert!(db.exists("short"));
+
+    std::thread::sleep(Duration::from_millis(200));
+
+    // the short TTL key expired
+    assert!(db.get::<i32>("short").is_none());
+    assert!(!db.exists("short"));
+    assert!(!db.vexists("short"));
+
+    // other keys are still there
+    assert_eq!(db.get::<String>("long").unwrap(), "long");
+    assert_eq!(db.get::<i32>("no_ttl").unwrap(), 3);
+    assert_eq!(db.get::<i32>("overridden").unwrap(), 5);
+
+    // TTLs are kept in the file
+    {
+        let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+        assert!(read_db.get::<i32>("short").is_none());
+        assert_eq!(read_db.get::<String>("long").unwrap(), "long");
+        assert_eq!(read_db.get::<i32>("no_ttl").unwrap(), 3);
+        assert_eq!(read_db.get::<i32>("overridden").unwrap(), 5);
+    }
+
+    // an expired key can be set again
+    assert!(db.set_if_absent("short", &6).unwrap());
+    assert_eq!(db.get::<i32>("short").unwrap(), 6);
+    db.set_with_ttl("short", &7, Duration::from_millis(100))
+        .unwrap();
+    std::thread::sleep(Duration::from_millis(200));
+
+    // expired keys are skipped by all the methods that go over keys and values
+    let footprint = db.memory_footprint();
+    assert_eq!(db.total_keys(), 3);
+    assert_eq!(db.value_count(), 3);
+    assert!(!db.get_all().contains(&String::from("short")));
+    assert!(!db.get_value_keys().contains(&String::from("short")));
+    assert!(db.value_keys().all(|key| key != "short"));
+    assert!(db.all_keys().all(|key| key != "short"));
+    assert_eq!(db.iter().len(), 3);
+    assert!(db.iter().all(|kv| kv.get_key() != "short"));
+    assert_eq!(db.entries().len(), 3);
+    assert!(!db.is_empty());
+
+    // purge expired keys, the footprint doesn't change since they weren't counted
+    assert_eq!(db.purge_expired().unwrap(), 1);
+    assert_eq!(db.purge_expired().unwrap(), 0);
+    assert_eq!(db.total_keys(), 3);
+    assert_eq!(db.memory_footprint(), footprint);
+
+    // the purge is dumped to the file
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert_eq!(read_db.total_keys(), 3);
+    assert!(!read_db.exists("short"));
+    assert!(read_db.exists("long"));
+
+    // expired keys are removed when the entire DB is dumped
+    db.set_with_ttl("short", &8, Duration::from_millis(100))
+        .unwrap();
+    std::thread::sleep(Duration::from_millis(200));
+    db.set("no_ttl", &9).unwrap();
+    assert_eq!(db.purge_expired().unwrap(), 0);
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn get_set_raw_test(ser_method_int: i32) {
+    test_setup!("get_set_raw_test", ser_method_int, db_name);
+
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    db.set("key1", &100).unwrap();
+    db.set("key2", &String::from("hello")).unwrap();
+    db.lcreate("list1").unwrap();
+
+    // raw bytes of non-existent keys or lists
+    assert!(db.get_raw("key3").is_none());
+    assert!(db.get_raw("list1").is_none());
+
+    // copy raw bytes to other keys
+    let raw1 = db.get_raw("key1").unwrap().to_vec();
+    let raw2 = db.get_raw("key2").unwrap().to_vec();
+    assert_ne!(raw1, raw2);
+    db.set_raw("key3", raw1).unwrap();
+    db.set_raw("list1", raw2).unwrap();
+
+    assert_eq!(db.get::<i32>("key3").unwrap(), 100);
+    assert_eq!(db.get::<String>("list1").unwrap(), "hello");
+    assert!(!db.lexists("list1"));
+    assert_eq!(db.get_raw("key3").unwrap(), db.get_raw("key1").unwrap());
+
+    // the raw values are dumped to the file
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert_eq!(read_db.get::<i32>("key3").unwrap(), 100);
+    assert_eq!(read_db.get::<String>("list1").unwrap(), "hello");
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn entries_test(ser_method_int: i32) {
+    test_setup!("entries_test", ser_method_int, db_name);
+
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    db.set("key1", &1).unwrap();
+    db.set("key2", &String::from("value2")).unwrap();
+    db.lcreate("list1").unwrap().lextend(&[1, 2, 3]);
+
+    // the entries are a copy of the raw values
+    let mut entries = db.entries();
+    entries.sort();
+    assert_eq!(
+        entries,
+        vec![
+            (String::from("key1"), db.get_raw("key1").unwrap().to_vec()),
+            (String::from("key2"), db.get_raw("key2").unwrap().to_vec()),
+        ]
+    );
+    let list_entries = db.list_entries();
+    assert_eq!(list_entries.len(), 1);
+    assert_eq!(list_entries[0].0, "list1");
+    assert_eq!(list_entries[0].1.len(), 3);
+
+    // the entries don't borrow the DB, so it can be changed and they can be moved to another thread
+    db.rem("key1").unwrap();
+    let restored_db = std::thread::spawn(move || {
+        let mut restored_db = PickleDb::new_in_memory(ser_method!(ser_method_int));
+        for (key, value) in entries {
+            restored_db.set_raw(&key, value).unwrap();
+        }
+        restored_db
+    })
+    .join()
+    .unwrap();
+    assert_eq!(restored_db.get::<i32>("key1").unwrap(), 1);
+    assert_eq!(restored_db.get::<String>("key2").unwrap(), "value2");
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn value_len_test(ser_method_int: i32) {
+    test_setup!("value_len_test", ser_method_int, db_name);
+
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    db.set("key1", &100).unwrap();
+    db.set("key2", &"x".repeat(100)).unwrap();
+    db.lcreate("list1").unwrap().lextend(&[1, 2, 3]);
+    db.lcreate("list2").unwrap();
+
+    // the size of values is the size of their serialized bytes
+    assert_eq!(
+        db.value_len("key1"),
+        Some(db.get_raw("key1").unwrap().len())
+    );
+    assert!(db.value_len("key2").unwrap() >= 100);
+    assert!(db.value_len("key2").unwrap() > db.value_len("key1").unwrap());
+    assert!(db.value_len("key3").is_none());
+    assert!(db.value_len("list1").is_none());
+
+    // the size of a list is the sum of the sizes of its items
+    let item_size = db.list_bytes_len("list1").unwrap();
+    assert!(item_size > 0);
+    db.ladd("list1", &4).unwrap();
+    assert!(db.list_bytes_len("list1").unwrap() > item_size);
+    assert_eq!(db.list_bytes_len("list2"), Some(0));
+    assert!(db.list_bytes_len("list3").is_none());
+    assert!(db.list_bytes_len("key1").is_none());
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn with_prefix_test(ser_method_int: i32) {
+    test_setup!("with_prefix_test", ser_method_int, db_name);
+
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+    db.set("key1", &0).unwrap();
+
+    {
+        let mut users = db.with_prefix("users:");
+        assert_eq!(users.prefix(), "users:");
+        users.set("key1", &1).unwrap();
+        users.set("key2", &2).unwrap();
+        users.set("key3", &3).unwrap();
+
+        assert_eq!(users.get::<i32>("key1").unwrap(), 1);
+        assert!(users.exists("key2"));
+        assert!(!users.exists("key4"));
+        assert!(users.rem("key3").unwrap());
+        assert!(!users.rem("key3").unwrap());
+
+        // iterate over the keys under the prefix only
+        let mut keys: Vec<(String, i32)> = users
+            .iter()
+            .map(|item| (item.get_key().to_string(), item.get_value::<i32>().unwrap()))
+            .collect();
+        keys.sort();
+        assert_eq!(
+            keys,
+            vec![(String::from("key1"), 1), (String::from("key2"), 2)]
+        );
+    }
+
+    // keys in a different prefix don't collide
+    db.with_prefix("groups:").set("key1", &10).unwrap();
+    assert_eq!(db.with_prefix("groups:").iter().count(), 1);
+    assert_eq!(db.get::<i32>("key1").unwrap(), 0);
+    assert_eq!(db.get::<i32>("users:key1").unwrap(), 1);
+    assert_eq!(db.get::<i32>("groups:key1").unwrap(), 10);
+
+    // the prefixed keys are dumped to the file
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert_eq!(read_db.total_keys(), 4);
+    assert_eq!(read_db.get::<i32>("users:key2").unwrap(), 2);
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn from_map_test(ser_method_int: i32) {
+    test_setup!("from_map_test", ser_method_int, db_name);
+
+    // create a DB from a map
+    let mut map = HashMap::new();
+    for i in 0..10 {
+        map.insert(format!("key{}", i), i);
+    }
+    let db = PickleDb::from_map(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+        &map,
+    )
+    .unwrap();
+    assert_eq!(db.total_keys(), 10);
+    assert_eq!(db.get::<i32>("key5").unwrap(), 5);
+
+    // verify the DB was dumped to the file
+    {
+        let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+        assert_eq!(read_db.total_keys(), 10);
+        for i in 0..10 {
+            assert_eq!(read_db.get::<i32>(&format!("key{}", i)).unwrap(), i);
+        }
+    }
+
+    // create a DB from an empty map
+    let db = PickleDb::from_map(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+        &HashMap::<String, i32>::new(),
+    )
+    .unwrap();
+    assert!(db.is_empty());
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert!(read_db.is_empty());
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn to_map_test(ser_method_int: i32) {
+    test_setup!("to_map_test", ser_method_int, db_name);
+
+    // create a DB from a map and convert it back
+    let mut map = HashMap::new();
+    for i in 0..10 {
+        map.insert(format!("key{}", i), (i, i * 2));
+    }
+    let mut db = PickleDb::from_map(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+        &map,
+    )
+    .unwrap();
+    assert_eq!(db.to_map::<(i32, i32)>(), map);
+    assert_eq!(db.try_to_map::<(i32, i32)>().unwrap(), map);
+
+    // lists and expired keys aren't included
+    db.lcreate("list1").unwrap().lextend(&[(1, 2)]);
+    db.set_with_ttl("key10", &(10, 20), Duration::from_secs(0))
+        .unwrap();
+    assert_eq!(db.to_map::<(i32, i32)>(), map);
+
+    // values of another type are skipped or fail the conversion
+    db.set("key11", &true).unwrap();
+    assert_eq!(db.to_map::<(i32, i32)>(), map);
+    let try_to_map = db.try_to_map::<(i32, i32)>();
+    assert!(try_to_map.is_err());
+    assert!(try_to_map.err().unwrap().to_string().contains("'key11'"));
+
+    // an empty DB is converted to an empty map
+    db.clear().unwrap();
+    assert!(db.to_map::<i32>().is_empty());
+    assert!(db.try_to_map::<i32>().unwrap().is_empty());
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn from_entries_test(ser_method_int: i32) {
+    test_setup!("from_entries_test", ser_method_int, db_name);
+
+    let mut source_db = PickleDb::new_in_memory(ser_method!(ser_method_int));
+    source_db.set("key1", &1).unwrap();
+    source_db.set("key2", &String::from("value2")).unwrap();
+    source_db.lcreate("list1").unwrap().lextend(&[1, 2, 3]);
+
+    // create a DB from the raw entries of another DB
+    let mut db = PickleDb::from_entries(
+        &db_name,
+        PickleDbDumpPolicy::DumpUponRequest,
+        ser_method!(ser_method_int),
+        source_db.entries(),
+        source_db.list_entries(),
+    );
+    assert_eq!(db.total_keys(), 3);
+    assert_eq!(db.get::<i32>("key1").unwrap(), 1);
+    assert_eq!(db.get::<String>("key2").unwrap(), "value2");
+    assert_eq!(db.lget::<i32>("list1", 2).unwrap(), 3);
+
+    // nothing is written until the DB is dumped
+    assert!(!std::path::Path::new(&db_name).exists());
+    db.dump().unwrap();
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert_eq!(read_db.total_keys(), 3);
+    assert_eq!(read_db.get::<String>("key2").unwrap(), "value2");
+    assert_eq!(read_db.llen("list1"), 3);
+
+    // a list overrides a value with the same name
+    let db = PickleDb::from_entries(
+        &db_name,
+        PickleDbDumpPolicy::NeverDump,
+        ser_method!(ser_method_int),
+        vec![(
+            String::from("name1"),
+            source_db.get_raw("key1").unwrap().to_vec(),
+        )],
+        vec![(String::from("name1"), Vec::new())],
+    );
+    assert_eq!(db.total_keys(), 1);
+    assert!(db.lexists("name1"));
+    assert!(db.get::<i32>("name1").is_none());
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn value_kind_test(ser_method_int: i32) {
+    test_setup!("value_kind_test", ser_method_int, db_name);
+
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    #[derive(Serialize, Deserialize)]
+    struct Coor {
+        x: i32,
+        y: i32,
+    }
+
+    db.set("int", &100).unwrap();
+    db.set("float", &1.1).unwrap();
+    db.set("bool", &true).unwrap();
+    db.set("string", &String::from("my string")).unwrap();
+    db.set("vec", &vec![1, 2, 3]).unwrap();
+    db.set("struct", &Coor { x: 1, y: 2 }).unwrap();
+    db.lcreate("list1").unwrap().ladd(&1);
+
+    // non-existent keys and lists don't have a value kind
+    assert_eq!(db.value_kind("non_existent_key"), None);
+    assert_eq!(db.value_kind("list1"), None);
+
+    // Bincode isn't self-describing so the kind can't be determined
+    if ser_method_int == 1 {
+        assert_eq!(db.value_kind("int"), None);
+        assert_eq!(db.value_kind("struct"), None);
+        return;
+    }
+
+    assert_eq!(db.value_kind("int"), Some(ValueKind::Number));
+    assert_eq!(db.value_kind("float"), Some(ValueKind::Number));
+    assert_eq!(db.value_kind("bool"), Some(ValueKind::Bool));
+    assert_eq!(db.value_kind("string"), Some(ValueKind::String));
+    assert_eq!(db.value_kind("vec"), Some(ValueKind::Array));
+    assert_eq!(db.value_kind("struct"), Some(ValueKind::Object));
+
+    // TOML can't store null values
+    if ser_method_int != 5 {
+        db.set("none", &None::<i32>).unwrap();
+        assert_eq!(db.value_kind("none"), Some(ValueKind::Null));
+    }
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn validate_test(ser_method_int: i32) {
+    // 0xc1 isn't valid UTF-8, and it isn't a valid start of a CBOR or MessagePack value either
+    let invalid_data = vec![0xc1];
+
+    let mut db = PickleDb::new_in_memory(ser_method!(ser_method_int));
+    db.set("key1", &1).unwrap();
+    db.set("key2", &String::from("value2")).unwrap();
+    db.lcreate("list1").unwrap().lextend(&[1, 2, 3]);
+    db.hset("hash1", "field1", &vec![1, 2]).unwrap();
+
+    // a DB that holds only valid data
+    assert!(db.validate().is_empty());
+
+    // add invalid data
+    let mut lists = db.list_entries();
+    lists[0].1.insert(1, invalid_data.clone());
+    let mut db = PickleDb::from_entries(
+        "validate_test.db",
+        PickleDbDumpPolicy::NeverDump,
+        ser_method!(ser_method_int),
+        db.entries(),
+        lists,
+    );
+    db.set_raw("key3", invalid_data).unwrap();
+
+    // Bincode isn't self-describing so the data can't be checked
+    let mut errors = db.validate();
+    if ser_method_int == 1 {
+        assert!(errors.is_empty());
+        return;
+    }
+
+    errors.sort();
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].0, "key3");
+    assert_eq!(errors[1].0, "list1");
+    assert!(errors[1].1.starts_with("Item 1: "));
+
+    // the invalid data can't be read but doesn't cause a panic
+    assert!(db.get::<i32>("key3").is_none());
+    assert!(db.get_json("key3").is_none());
+    assert!(db.lget::<i32>("list1", 1).is_none());
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn get_json_test(ser_method_int: i32) {
+    test_setup!("get_json_test", ser_method_int, db_name);
+
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    #[derive(Serialize, Deserialize)]
+    struct Coor {
+        x: i32,
+        y: i32,
+    }
+
+    db.set("int", &100).unwrap();
+    db.set("string", &String::from("my string")).unwrap();
+    db.set("vec", &vec![1, 2, 3]).unwrap();
+    db.set("struct", &Coor { x: 1, y: 2 }).unwrap();
+    db.lcreate("list1").unwrap().ladd(&1);
+
+    // non-existent keys and lists can't be converted
+    assert_eq!(db.get_json("non_existent_key"), None);
+    assert_eq!(db.get_json("list1"), None);
+
+    // Bincode isn't self-describing so values can't be converted
+    if ser_method_int == 1 {
+        assert_eq!(db.get_json("int"), None);
+        return;
+    }
+
+    assert_eq!(db.get_json("int").unwrap(), serde_json::json!(100));
+    assert_eq!(
+        db.get_json("string").unwrap(),
+        serde_json::json!("my string")
+    );
+    assert_eq!(db.get_json("vec").unwrap(), serde_json::json!([1, 2, 3]));
+    assert_eq!(
+        db.get_json("struct").unwrap(),
+        serde_json::json!({"x": 1, "y": 2})
+    );
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn get_many_test(ser_method_int: i32) {
+    test_setup!("get_many_test", ser_method_int, db_name);
+
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+    db.set("key1", &1).unwrap();
+    db.set("key2", &2).unwrap();
+    db.set("key3", &String::from("my string")).unwrap();
+    db.lcreate("list1").unwrap().ladd(&4);
+
+    // values are aligned with the keys, missing keys and lists are None
+    assert_eq!(
+        db.get_many::<i32>(&["key2", "non_existent_key", "key1", "list1"]),
+        vec![Some(2), None, Some(1), None]
+    );
+
+    // no keys
+    assert!(db.get_many::<i32>(&[]).is_empty());
+
+    // a value of a different type is None. Bincode isn't self-describing, so skip this check
+    if ser_method_int != 1 {
+        assert_eq!(db.get_many::<i32>(&["key1", "key3"]), vec![Some(1), None]);
+    }
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn rem_many_test(ser_method_int: i32) {
+    test_setup!("rem_many_test", ser_method_int, db_name);
+
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+    db.set("key1", &1).unwrap();
+    db.set("key2", &2).unwrap();
+    db.set("key3", &3).unwrap();
+    db.set_with_ttl("key4", &4, Duration::from_secs(100))
+        .unwrap();
+    db.lcreate("list1").unwrap().ladd(&1);
+
+    // remove some keys, including a list, a key with TTL, a duplicate and a non-existent key
+    assert_eq!(
+        db.rem_many(&["key1", "list1", "key4", "key1", "non_existent_key"])
+            .unwrap(),
+        3
+    );
+    assert!(!db.exists("key1"));
+    assert!(!db.exists("list1"));
+    assert!(!db.exists("key4"));
+    assert_eq!(db.total_keys(), 2);
+
+    // removing no keys or only non-existent keys does nothing
+    assert_eq!(db.rem_many(&[]).unwrap(), 0);
+    assert_eq!(db.rem_many(&["key1", "list1"]).unwrap(), 0);
+
+    // verify the changes were dumped
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert_eq!(read_db.get_all().len(), 2);
+    assert_eq!(read_db.get::<i32>("key2").unwrap(), 2);
+    assert_eq!(read_db.get::<i32>("key3").unwrap(), 3);
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn take_test(ser_method_int: i32) {
+    test_setup!("take_test", ser_method_int, db_name);
+
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+    db.set("key1", &100).unwrap();
+    db.set("key2", &String::from("hello")).unwrap();
+    db.set_with_ttl("key3", &vec![1, 2, 3], Duration::from_secs(100))
+        .unwrap();
+    db.lcreate("list1").unwrap().ladd(&1);
+
+    // take values of different types, including a key with TTL
+    assert_eq!(db.take::<i32>("key1").unwrap(), Some(100));
+    assert_eq!(db.take::<Vec<i32>>("key3").unwrap(), Some(vec![1, 2, 3]));
+    assert!(!db.exists("key1"));
+    assert!(!db.exists("key3"));
+
+    // a key that doesn't exist or holds a list isn't found
+    assert!(db.take::<i32>("key1").unwrap().is_none());
+    assert!(db.take::<Vec<i32>>("list1").unwrap().is_none());
+    assert!(db.lexists("list1"));
+
+    // a value of a different type isn't removed
+    // (Bincode isn't self-describing so the string may be deserialized as a vector)
+    if ser_method_int != 1 {
+        assert!(db.take::<i32>("key2").unwrap().is_none());
+        assert!(db.exists("key2"));
+    }
+    assert_eq!(db.take::<String>("key2").unwrap().unwrap(), "hello");
+
+    // verify the changes were dumped
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert_eq!(read_db.total_keys(), 1);
+    assert!(read_db.lexists("list1"));
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn retain_test(ser_method_int: i32) {
+    test_setup!("retain_test", ser_method_int, db_name);
+
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+    for i in 0..10 {
+        db.set(&format!("key{}", i), &i).unwrap();
+    }
+    db.lcreate("list1").unwrap().ladd(&1);
+
+    // remove all the odd numbers
+    assert_eq!(db.retain(|_key, value: &i32| value % 2 == 0).unwrap(), 5);
+    assert_eq!(db.total_keys(), 6);
+    assert!(db.exists("key0"));
+    assert!(!db.exists("key1"));
+
+    // the key is passed to the predicate too
+    assert_eq!(db.retain(|key, _value: &i32| key != "key8").unwrap(), 1);
+    assert!(!db.exists("key8"));
+
+    // nothing to remove
+    assert_eq!(db.retain(|_key, _value: &i32| true).unwrap(), 0);
+
+    // verify the changes were dumped
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert_eq!(read_db.total_keys(), 5);
+    assert_eq!(read_db.llen("list1"), 1);
+
+    // values of other types are left untouched. Bincode isn't self-describing, so skip this check
+    if ser_method_int != 1 {
+        db.set("string", &String::from("my string")).unwrap();
+        assert_eq!(db.retain(|_key, _value: &i32| false).unwrap(), 4);
+        assert_eq!(db.get::<String>("string").unwrap(), "my string");
+        assert!(db.lexists("list1"));
+    }
 }
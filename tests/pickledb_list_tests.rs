@@ -1,6 +1,6 @@
 #![allow(clippy::float_cmp)]
 
-use pickledb::{PickleDb, PickleDbDumpPolicy, SerializationMethod};
+use pickledb::{PickleDb, PickleDbDumpPolicy, PickleValue, SerializationMethod};
 use serde::{Deserialize, Serialize};
 
 mod common;
@@ -11,7 +11,7 @@ extern crate rstest;
 use rstest::rstest_parametrize;
 
 #[allow(clippy::cognitive_complexity)]
-#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3))]
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
 fn basic_lists(ser_method_int: i32) {
     test_setup!("basic_lists", ser_method_int, db_name);
 
@@ -122,7 +122,7 @@ fn basic_lists(ser_method_int: i32) {
     assert_eq!(read_db.lget::<Coor>("list1", 4).unwrap().y, mycoor.y);
 }
 
-#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3))]
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
 fn add_and_extend_lists(ser_method_int: i32) {
     test_setup!("add_and_extend_lists", ser_method_int, db_name);
 
@@ -184,7 +184,7 @@ fn add_and_extend_lists(ser_method_int: i32) {
     }
 }
 
-#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3))]
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
 fn override_lists(ser_method_int: i32) {
     test_setup!("override_lists", ser_method_int, db_name);
 
@@ -239,7 +239,7 @@ fn override_lists(ser_method_int: i32) {
     }
 }
 
-#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3))]
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
 fn lget_corner_cases(ser_method_int: i32) {
     test_setup!("lget_corner_cases", ser_method_int, db_name);
 
@@ -281,7 +281,7 @@ fn lget_corner_cases(ser_method_int: i32) {
     assert!(db.lget::<i32>("list2", 5).is_none());
 }
 
-#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3))]
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
 fn add_to_non_existent_list(ser_method_int: i32) {
     test_setup!("lget_corner_cases", ser_method_int, db_name);
 
@@ -317,7 +317,7 @@ fn add_to_non_existent_list(ser_method_int: i32) {
     assert!(db.lextend("list1", &vec_of_nums).is_none());
 }
 
-#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3))]
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
 fn remove_list(ser_method_int: i32) {
     test_setup!("remove_list", ser_method_int, db_name);
 
@@ -390,7 +390,7 @@ fn remove_list(ser_method_int: i32) {
     }
 }
 
-#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3))]
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
 fn remove_values_from_list(ser_method_int: i32) {
     test_setup!("remove_values_from_list", ser_method_int, db_name);
 
@@ -497,7 +497,7 @@ fn remove_values_from_list(ser_method_int: i32) {
     }
 }
 
-#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3))]
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
 fn list_with_special_strings(ser_method_int: i32) {
     test_setup!("list_with_special_strings", ser_method_int, db_name);
 
@@ -567,7 +567,7 @@ fn list_with_special_strings(ser_method_int: i32) {
     );
 }
 
-#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3))]
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
 fn list_iter_test(ser_method_int: i32) {
     test_setup!("list_iter_test", ser_method_int, db_name);
 
@@ -599,7 +599,8 @@ fn list_iter_test(ser_method_int: i32) {
 
     // iterate over the list
     for item in db.liter("list1") {
-        // check each item
+        // check each item and its position
+        assert_eq!(item.index(), index);
         match index {
             0 => assert_eq!(item.get_item::<i32>().unwrap(), values.0),
             1 => assert_eq!(item.get_item::<f32>().unwrap(), values.1),
@@ -613,11 +614,74 @@ fn list_iter_test(ser_method_int: i32) {
 
     // verify iterator went over all the items
     assert_eq!(index, 5);
+
+    // iterate over the list in reverse order
+    let mut reversed = db.liter("list1").rev();
+    let item = reversed.next().unwrap();
+    assert_eq!(item.get_item::<(char, char, char)>().unwrap(), values.4);
+    assert_eq!(item.index(), 4);
+    let item = reversed.nth(3).unwrap();
+    assert_eq!(item.get_item::<i32>().unwrap(), values.0);
+    assert_eq!(item.index(), 0);
+    assert_eq!(db.liter("list1").rev().count(), 5);
+
+    // iterate from both ends
+    let mut list_iter = db.liter("list1");
+    assert_eq!(
+        list_iter.next().unwrap().get_item::<i32>().unwrap(),
+        values.0
+    );
+    assert_eq!(
+        list_iter
+            .next_back()
+            .unwrap()
+            .get_item::<(char, char, char)>()
+            .unwrap(),
+        values.4
+    );
+    assert_eq!(
+        list_iter
+            .next_back()
+            .unwrap()
+            .get_item::<Vec<i32>>()
+            .unwrap(),
+        values.3
+    );
+    assert_eq!(
+        list_iter.next().unwrap().get_item::<f32>().unwrap(),
+        values.1
+    );
+    assert_eq!(
+        list_iter.next().unwrap().get_item::<String>().unwrap(),
+        values.2
+    );
+    assert!(list_iter.next().is_none());
+    assert!(list_iter.next_back().is_none());
+
+    // the positions are kept when iterating from both ends
+    let mut list_iter = db.liter("list1");
+    assert_eq!(list_iter.next_back().unwrap().index(), 4);
+    assert_eq!(list_iter.next().unwrap().index(), 0);
+    assert_eq!(list_iter.next().unwrap().index(), 1);
+    assert_eq!(list_iter.next_back().unwrap().index(), 3);
+    assert_eq!(list_iter.next_back().unwrap().index(), 2);
+    assert!(list_iter.next_back().is_none());
+
+    // the iterator knows how many items are left
+    let mut list_iter = db.liter("list1");
+    assert_eq!(list_iter.len(), 5);
+    list_iter.next();
+    list_iter.next_back();
+    assert_eq!(list_iter.len(), 3);
+    assert_eq!(list_iter.size_hint(), (3, Some(3)));
+    let items = list_iter.collect::<Vec<_>>();
+    assert_eq!(items.len(), 3);
+    assert_eq!(db.liter_all().len(), 1);
 }
 
 #[allow(unused_attributes)]
 #[should_panic]
-#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3))]
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
 fn list_doesnt_exist_iter_test(ser_method_int: i32) {
     test_setup!("list_doesnt_exist_iter_test", ser_method_int, db_name);
 
@@ -648,3 +712,960 @@ fn list_doesnt_exist_iter_test(ser_method_int: i32) {
     // iterate over a non-existent list - should panic here
     for _item in db.liter("list2") {}
 }
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn list_or_empty_iter_test(ser_method_int: i32) {
+    test_setup!("list_or_empty_iter_test", ser_method_int, db_name);
+
+    // create a db with auto_dump == true
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    db.lcreate("list1").unwrap().lextend(&[1, 2, 3]);
+    db.lcreate("list2").unwrap();
+    db.set("key1", &1).unwrap();
+
+    // an existing list is iterated over as usual
+    let items: Vec<i32> = db
+        .liter_or_empty("list1")
+        .map(|item| item.get_item::<i32>().unwrap())
+        .collect();
+    assert_eq!(items, vec![1, 2, 3]);
+    assert_eq!(db.liter_or_empty("list1").next_back().unwrap().index(), 2);
+
+    // an empty list, a list that doesn't exist and a value don't yield any item
+    assert_eq!(db.liter_or_empty("list2").count(), 0);
+    assert_eq!(db.liter_or_empty("list3").count(), 0);
+    assert_eq!(db.liter_or_empty("list3").len(), 0);
+    assert!(db.liter_or_empty("list3").next_back().is_none());
+    assert_eq!(db.liter_or_empty("key1").count(), 0);
+
+    // the DB isn't changed
+    assert!(!db.lexists("list3"));
+    assert_eq!(db.total_keys(), 3);
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn all_lists_iter_test(ser_method_int: i32) {
+    test_setup!("all_lists_iter_test", ser_method_int, db_name);
+
+    // create a db with auto_dump == true
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    // create a few lists and a value that shouldn't be iterated
+    db.lcreate("list1").unwrap().lextend(&[1, 2, 3]);
+    db.lcreate("list2").unwrap().lextend(&[4, 5]);
+    db.lcreate("list3").unwrap();
+    db.set("key1", &100).unwrap();
+
+    let mut lists_seen = vec![];
+
+    // iterate over all lists and their items
+    for list in db.liter_all() {
+        let items: Vec<i32> = list
+            .iter()
+            .map(|item| item.get_item::<i32>().unwrap())
+            .collect();
+        match list.name() {
+            "list1" => assert_eq!(items, vec![1, 2, 3]),
+            "list2" => assert_eq!(items, vec![4, 5]),
+            "list3" => assert!(items.is_empty()),
+            _ => panic!(),
+        }
+        lists_seen.push(String::from(list.name()));
+
+        // a list item can also be iterated directly
+        let mut count = 0;
+        for _item in list {
+            count += 1;
+        }
+        assert_eq!(count, items.len());
+    }
+
+    // verify the iterator went over all the lists
+    lists_seen.sort();
+    assert_eq!(lists_seen, vec!["list1", "list2", "list3"]);
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn lset_test(ser_method_int: i32) {
+    test_setup!("lset_test", ser_method_int, db_name);
+
+    // create a db with auto_dump == true
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    db.lcreate("list1").unwrap().lextend(&[1, 2, 3]);
+
+    // replace items in the list
+    assert!(db.lset("list1", 0, &100).unwrap());
+    assert!(db.lset("list1", 2, &String::from("three")).unwrap());
+    assert_eq!(db.llen("list1"), 3);
+    assert_eq!(db.lget::<i32>("list1", 0).unwrap(), 100);
+    assert_eq!(db.lget::<i32>("list1", 1).unwrap(), 2);
+    assert_eq!(db.lget::<String>("list1", 2).unwrap(), "three");
+
+    // replace an item out of bounds
+    assert!(!db.lset("list1", 3, &4).unwrap());
+    assert_eq!(db.llen("list1"), 3);
+
+    // replace an item in a list that doesn't exist
+    assert!(!db.lset("list2", 0, &4).unwrap());
+    assert!(!db.lexists("list2"));
+
+    // verify the changes were dumped to the file
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert_eq!(read_db.lget::<i32>("list1", 0).unwrap(), 100);
+    assert_eq!(read_db.lget::<String>("list1", 2).unwrap(), "three");
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn linsert_test(ser_method_int: i32) {
+    test_setup!("linsert_test", ser_method_int, db_name);
+
+    // create a db with auto_dump == true
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    db.lcreate("list1").unwrap().lextend(&[2, 4]);
+
+    // insert in the beginning, in the middle and in the end of the list
+    assert!(db.linsert("list1", 0, &1).is_some());
+    db.linsert("list1", 2, &3).unwrap().linsert(4, &5);
+    assert_eq!(db.llen("list1"), 5);
+    for i in 0..5 {
+        assert_eq!(db.lget::<i32>("list1", i).unwrap(), i as i32 + 1);
+    }
+
+    // insert out of bounds
+    assert!(db.linsert("list1", 6, &7).is_none());
+    assert_eq!(db.llen("list1"), 5);
+
+    // insert to a list that doesn't exist
+    assert!(db.linsert("list2", 0, &1).is_none());
+    assert!(!db.lexists("list2"));
+
+    // verify the changes were dumped to the file
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert_eq!(read_db.llen("list1"), 5);
+    for i in 0..5 {
+        assert_eq!(read_db.lget::<i32>("list1", i).unwrap(), i as i32 + 1);
+    }
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn lcontains_test(ser_method_int: i32) {
+    test_setup!("lcontains_test", ser_method_int, db_name);
+
+    // create a db with auto_dump == true
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    db.lcreate("list1")
+        .unwrap()
+        .lextend(&[1, 2, 3])
+        .ladd(&String::from("hello"))
+        .ladd(&vec![4, 5]);
+
+    // check values that are in the list
+    assert!(db.lcontains("list1", &2));
+    assert!(db.lcontains("list1", &String::from("hello")));
+    assert!(db.lcontains("list1", &vec![4, 5]));
+
+    // check values that aren't in the list
+    assert!(!db.lcontains("list1", &4));
+    assert!(!db.lcontains("list1", &String::from("world")));
+    assert!(!db.lcontains("list1", &vec![4]));
+
+    // check a list that doesn't exist
+    assert!(!db.lcontains("list2", &1));
+
+    // check the same values in a DB loaded from the file
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert!(read_db.lcontains("list1", &2));
+    assert!(read_db.lcontains("list1", &String::from("hello")));
+    assert!(!read_db.lcontains("list1", &4));
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn ladd_unique_test(ser_method_int: i32) {
+    test_setup!("ladd_unique_test", ser_method_int, db_name);
+
+    // create a db with auto_dump == true
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    db.lcreate("list1").unwrap().lextend(&["red", "green"]);
+
+    // values that are already in the list aren't added
+    assert!(!db.ladd_unique("list1", &"red").unwrap());
+    assert!(!db.ladd_unique("list1", &String::from("green")).unwrap());
+    assert_eq!(db.llen("list1"), 2);
+
+    // new values are added to the end of the list, only once
+    assert!(db.ladd_unique("list1", &"blue").unwrap());
+    assert!(!db.ladd_unique("list1", &"blue").unwrap());
+    assert!(db.ladd_unique("list1", &vec![1, 2]).unwrap());
+    assert_eq!(db.llen("list1"), 4);
+    assert_eq!(db.lget::<String>("list1", 2).unwrap(), "blue");
+    assert_eq!(db.lget::<Vec<i32>>("list1", 3).unwrap(), vec![1, 2]);
+
+    // verify the changes were dumped to the file
+    {
+        let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+        assert_eq!(read_db.llen("list1"), 4);
+        assert_eq!(read_db.lget::<String>("list1", 2).unwrap(), "blue");
+    }
+
+    // add to a list that doesn't exist, confirm failure
+    assert!(db.ladd_unique("list2", &"red").is_err());
+    assert!(!db.lexists("list2"));
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn lindex_of_test(ser_method_int: i32) {
+    test_setup!("lindex_of_test", ser_method_int, db_name);
+
+    // create a db with auto_dump == true
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    db.lcreate("list1")
+        .unwrap()
+        .lextend(&[1, 2, 3, 2])
+        .ladd(&String::from("hello"));
+
+    // find values that are in the list, the first match should be returned
+    assert_eq!(db.lindex_of("list1", &1), Some(0));
+    assert_eq!(db.lindex_of("list1", &2), Some(1));
+    assert_eq!(db.lindex_of("list1", &String::from("hello")), Some(4));
+
+    // find values that aren't in the list
+    assert_eq!(db.lindex_of("list1", &4), None);
+    assert_eq!(db.lindex_of("list1", &String::from("world")), None);
+
+    // find a value in a list that doesn't exist
+    assert_eq!(db.lindex_of("list2", &1), None);
+
+    // verify nothing was removed from the list
+    assert_eq!(db.llen("list1"), 5);
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn lclear_test(ser_method_int: i32) {
+    test_setup!("lclear_test", ser_method_int, db_name);
+
+    // create a db with auto_dump == true
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    db.lcreate("list1").unwrap().lextend(&[1, 2, 3]);
+
+    // clear the list, it should still exist
+    assert_eq!(db.lclear("list1").unwrap(), 3);
+    assert!(db.lexists("list1"));
+    assert_eq!(db.llen("list1"), 0);
+
+    // clear an empty list
+    assert_eq!(db.lclear("list1").unwrap(), 0);
+    assert!(db.lexists("list1"));
+
+    // clear a list that doesn't exist
+    assert_eq!(db.lclear("list2").unwrap(), 0);
+    assert!(!db.lexists("list2"));
+
+    // the list can be used after it was cleared
+    db.ladd("list1", &4);
+    assert_eq!(db.llen("list1"), 1);
+    db.lclear("list1").unwrap();
+
+    // verify the changes were dumped to the file
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert!(read_db.lexists("list1"));
+    assert_eq!(read_db.llen("list1"), 0);
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn lpush_front_test(ser_method_int: i32) {
+    test_setup!("lpush_front_test", ser_method_int, db_name);
+
+    // create a db with auto_dump == true
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    db.lcreate("list1").unwrap().lextend(&[7, 8]);
+
+    // push and extend the front of the list, also through the extender
+    db.lpush_front("list1", &6)
+        .unwrap()
+        .lextend_front(&[4, 5])
+        .lpush_front(&3);
+    assert!(db.lextend_front("list1", &[1, 2]).is_some());
+    assert_eq!(db.llen("list1"), 8);
+    for i in 0..8 {
+        assert_eq!(db.lget::<i32>("list1", i).unwrap(), i as i32 + 1);
+    }
+
+    // add to a list that doesn't exist
+    assert!(db.lpush_front("list2", &1).is_none());
+    assert!(db.lextend_front("list2", &[1, 2]).is_none());
+    assert!(!db.lexists("list2"));
+
+    // verify the changes were dumped to the file
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert_eq!(read_db.llen("list1"), 8);
+    for i in 0..8 {
+        assert_eq!(read_db.lget::<i32>("list1", i).unwrap(), i as i32 + 1);
+    }
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn lpop_front_back_test(ser_method_int: i32) {
+    test_setup!("lpop_front_back_test", ser_method_int, db_name);
+
+    // create a db with auto_dump == true
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    db.lcreate("list1").unwrap().lextend(&[1, 2, 3, 4]);
+
+    // pop from both ends of the list
+    assert_eq!(db.lpop_front::<i32>("list1").unwrap(), 1);
+    assert_eq!(db.lpop_back::<i32>("list1").unwrap(), 4);
+    assert_eq!(db.llen("list1"), 2);
+
+    // verify the changes were dumped to the file
+    {
+        let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+        assert_eq!(read_db.llen("list1"), 2);
+        assert_eq!(read_db.lget::<i32>("list1", 0).unwrap(), 2);
+        assert_eq!(read_db.lget::<i32>("list1", 1).unwrap(), 3);
+    }
+
+    // empty the list
+    assert_eq!(db.lpop_back::<i32>("list1").unwrap(), 3);
+    assert_eq!(db.lpop_front::<i32>("list1").unwrap(), 2);
+
+    // pop from an empty list
+    assert!(db.lpop_front::<i32>("list1").is_none());
+    assert!(db.lpop_back::<i32>("list1").is_none());
+    assert!(db.lexists("list1"));
+
+    // pop from a list that doesn't exist
+    assert!(db.lpop_front::<i32>("list2").is_none());
+    assert!(db.lpop_back::<i32>("list2").is_none());
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn lpop_front_n_test(ser_method_int: i32) {
+    test_setup!("lpop_front_n_test", ser_method_int, db_name);
+
+    // create a db with auto_dump == true
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    db.lcreate("list1").unwrap().lextend(&[1, 2, 3, 4, 5]);
+
+    // pop a few items from the beginning of the list
+    assert_eq!(db.lpop_front_n::<i32>("list1", 3), vec![1, 2, 3]);
+    assert_eq!(db.llen("list1"), 2);
+    assert!(db.lpop_front_n::<i32>("list1", 0).is_empty());
+
+    // verify the changes were dumped to the file
+    {
+        let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+        assert_eq!(read_db.llen("list1"), 2);
+        assert_eq!(read_db.lget::<i32>("list1", 0).unwrap(), 4);
+    }
+
+    // pop more items than the list has
+    assert_eq!(db.lpop_front_n::<i32>("list1", 10), vec![4, 5]);
+    assert!(db.lexists("list1"));
+    assert!(db.lpop_front_n::<i32>("list1", 10).is_empty());
+
+    // pop from a list that doesn't exist
+    assert!(db.lpop_front_n::<i32>("list2", 1).is_empty());
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn lpop_at_test(ser_method_int: i32) {
+    test_setup!("lpop_at_test", ser_method_int, db_name);
+
+    // create a db with auto_dump == true
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    db.lcreate("list1").unwrap().lextend(&[1, 2, 3, 4, 5, 6]);
+
+    // negative positions count from the end of the list
+    assert_eq!(db.lpop_at::<i32>("list1", -1).unwrap(), 6);
+    assert_eq!(db.lpop_at::<i32>("list1", -2).unwrap(), 4);
+    assert_eq!(db.lpop_at::<i32>("list1", -4).unwrap(), 1);
+    assert_eq!(db.lpop_at::<i32>("list1", 1).unwrap(), 3);
+    assert_eq!(db.llen("list1"), 2);
+
+    // positions out of bounds
+    assert!(db.lpop_at::<i32>("list1", -3).is_none());
+    assert!(db.lpop_at::<i32>("list1", 2).is_none());
+    assert!(db.lpop_at::<i32>("list1", isize::MIN).is_none());
+    assert_eq!(db.llen("list1"), 2);
+
+    // verify the changes were dumped to the file
+    {
+        let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+        assert_eq!(read_db.llen("list1"), 2);
+        assert_eq!(read_db.lget::<i32>("list1", 0).unwrap(), 2);
+        assert_eq!(read_db.lget::<i32>("list1", 1).unwrap(), 5);
+    }
+
+    // empty the list
+    assert_eq!(db.lpop_at::<i32>("list1", -2).unwrap(), 2);
+    assert_eq!(db.lpop_at::<i32>("list1", 0).unwrap(), 5);
+    assert!(db.lpop_at::<i32>("list1", -1).is_none());
+    assert!(db.lpop_at::<i32>("list1", 0).is_none());
+
+    // pop from a list that doesn't exist
+    assert!(db.lpop_at::<i32>("list2", -1).is_none());
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn lsort_test(ser_method_int: i32) {
+    test_setup!("lsort_test", ser_method_int, db_name);
+
+    // create a db with auto_dump == true
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    db.lcreate("list1").unwrap().lextend(&[3, 5, 1, 4, 2]);
+    db.lcreate("list2")
+        .unwrap()
+        .lextend(&[2.5, 9.1, 4.7])
+        .ladd(&String::from("not a number"));
+    db.lcreate("list3").unwrap();
+
+    // sort in ascending order
+    assert!(db.lsort::<i32>("list1").unwrap());
+    for i in 0..5 {
+        assert_eq!(db.lget::<i32>("list1", i).unwrap(), i as i32 + 1);
+    }
+
+    // sort in descending order
+    assert!(db.lsort_by("list1", |a: &i32, b: &i32| b.cmp(a)).unwrap());
+    for i in 0..5 {
+        assert_eq!(db.lget::<i32>("list1", i).unwrap(), 5 - i as i32);
+    }
+
+    // verify the changes were dumped to the file
+    {
+        let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+        for i in 0..5 {
+            assert_eq!(read_db.lget::<i32>("list1", i).unwrap(), 5 - i as i32);
+        }
+    }
+
+    // sort an empty list and a list that doesn't exist
+    assert!(db.lsort::<i32>("list3").unwrap());
+    assert!(!db.lsort::<i32>("list4").unwrap());
+
+    // sort a list with an item that can't be deserialized, confirm failure
+    // (Bincode isn't self-describing so the string may be deserialized as a number)
+    if ser_method_int != 1 {
+        assert!(db
+            .lsort_by("list2", |a: &f64, b: &f64| a.partial_cmp(b).unwrap())
+            .is_err());
+        assert_eq!(db.lget::<f64>("list2", 0).unwrap(), 2.5);
+        assert_eq!(db.llen("list2"), 4);
+    }
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn lmap_test(ser_method_int: i32) {
+    test_setup!("lmap_test", ser_method_int, db_name);
+
+    // create a db with auto_dump == true
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    db.lcreate("list1").unwrap().lextend(&[1, 2, 3]);
+    db.lcreate("list2")
+        .unwrap()
+        .lextend(&[2.5, 9.1, 4.7])
+        .ladd(&String::from("not a number"));
+    db.lcreate("list3").unwrap();
+
+    // transform all items
+    assert_eq!(db.lmap("list1", |x: i32| x * 10).unwrap(), 3);
+    for i in 0..3 {
+        assert_eq!(db.lget::<i32>("list1", i).unwrap(), (i as i32 + 1) * 10);
+    }
+
+    // the closure can keep state
+    let mut index = 0;
+    db.lmap("list1", |x: i32| {
+        index += 1;
+        x + index
+    })
+    .unwrap();
+    assert_eq!(db.lget::<i32>("list1", 0).unwrap(), 11);
+    assert_eq!(db.lget::<i32>("list1", 2).unwrap(), 33);
+
+    // verify the changes were dumped to the file
+    {
+        let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+        assert_eq!(read_db.lget::<i32>("list1", 1).unwrap(), 22);
+    }
+
+    // an empty list and a list that doesn't exist
+    assert_eq!(db.lmap("list3", |x: i32| x + 1).unwrap(), 0);
+    assert_eq!(db.lmap("list4", |x: i32| x + 1).unwrap(), 0);
+    assert!(!db.lexists("list4"));
+
+    // transform a list with an item that can't be deserialized, confirm failure
+    // (Bincode isn't self-describing so the string may be deserialized as a number)
+    if ser_method_int != 1 {
+        let try_lmap = db.lmap("list2", |x: f64| x * 2.0);
+        assert!(try_lmap.is_err());
+        assert!(try_lmap
+            .err()
+            .unwrap()
+            .to_string()
+            .starts_with("Cannot deserialize item 3 of list 'list2'"));
+        assert_eq!(db.lget::<f64>("list2", 0).unwrap(), 2.5);
+        assert_eq!(db.llen("list2"), 4);
+    }
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn lrotate_test(ser_method_int: i32) {
+    test_setup!("lrotate_test", ser_method_int, db_name);
+
+    // create a db with auto_dump == true
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    db.lcreate("list1").unwrap().lextend(&[1, 2, 3, 4, 5]);
+    db.lcreate("list2").unwrap();
+
+    let items = |db: &PickleDb| {
+        db.liter("list1")
+            .map(|item| item.get_item::<i32>().unwrap())
+            .collect::<Vec<_>>()
+    };
+
+    // rotate to the left and to the right
+    assert!(db.lrotate("list1", 1).unwrap());
+    assert_eq!(items(&db), vec![2, 3, 4, 5, 1]);
+    assert!(db.lrotate("list1", -2).unwrap());
+    assert_eq!(items(&db), vec![5, 1, 2, 3, 4]);
+
+    // rotating by more than the length of the list wraps around
+    assert!(db.lrotate("list1", 11).unwrap());
+    assert_eq!(items(&db), vec![1, 2, 3, 4, 5]);
+    assert!(db.lrotate("list1", -7).unwrap());
+    assert_eq!(items(&db), vec![4, 5, 1, 2, 3]);
+    assert!(db.lrotate("list1", 0).unwrap());
+    assert!(db.lrotate("list1", isize::MIN).unwrap());
+    assert_eq!(db.llen("list1"), 5);
+
+    // verify the changes were dumped to the file
+    {
+        let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+        assert_eq!(items(&read_db), items(&db));
+    }
+
+    // an empty list and a list that doesn't exist
+    assert!(!db.lrotate("list2", 1).unwrap());
+    assert!(!db.lrotate("list3", 1).unwrap());
+    assert!(!db.lexists("list3"));
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn ltruncate_test(ser_method_int: i32) {
+    test_setup!("ltruncate_test", ser_method_int, db_name);
+
+    // create a db with auto_dump == true
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    db.lcreate("list1").unwrap().lextend(&[1, 2, 3, 4, 5, 6]);
+
+    let items = |db: &PickleDb| {
+        db.liter("list1")
+            .map(|item| item.get_item::<i32>().unwrap())
+            .collect::<Vec<_>>()
+    };
+
+    // keep the last items
+    assert_eq!(db.ltruncate_front("list1", 4).unwrap(), 2);
+    assert_eq!(items(&db), vec![3, 4, 5, 6]);
+
+    // keep the first items
+    assert_eq!(db.ltruncate_back("list1", 3).unwrap(), 1);
+    assert_eq!(items(&db), vec![3, 4, 5]);
+
+    // verify the changes were dumped to the file
+    {
+        let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+        assert_eq!(items(&read_db), vec![3, 4, 5]);
+    }
+
+    // lists that aren't longer than the maximal length aren't changed
+    assert_eq!(db.ltruncate_front("list1", 3).unwrap(), 0);
+    assert_eq!(db.ltruncate_back("list1", 10).unwrap(), 0);
+    assert_eq!(items(&db), vec![3, 4, 5]);
+
+    // truncate the list to an empty list, it's still kept
+    assert_eq!(db.ltruncate_front("list1", 0).unwrap(), 3);
+    assert!(db.lexists("list1"));
+    assert_eq!(db.llen("list1"), 0);
+
+    // a list that doesn't exist
+    assert_eq!(db.ltruncate_front("list2", 0).unwrap(), 0);
+    assert_eq!(db.ltruncate_back("list2", 0).unwrap(), 0);
+    assert!(!db.lexists("list2"));
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn is_empty_test(ser_method_int: i32) {
+    test_setup!("is_empty_test", ser_method_int, db_name);
+
+    // create a db with auto_dump == true
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    // a new DB is empty
+    assert!(db.is_empty());
+
+    // an empty list
+    db.lcreate("list1").unwrap();
+    assert!(!db.is_empty());
+    assert!(db.lis_empty("list1"));
+
+    // a list with items
+    db.ladd("list1", &1);
+    assert!(!db.lis_empty("list1"));
+
+    // a list that doesn't exist
+    assert!(db.lis_empty("list2"));
+
+    // a key that isn't a list
+    db.set("key1", &1).unwrap();
+    assert!(db.lis_empty("key1"));
+
+    // remove everything
+    db.lrem_list("list1").unwrap();
+    assert!(!db.is_empty());
+    db.rem("key1").unwrap();
+    assert!(db.is_empty());
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn lget_range_test(ser_method_int: i32) {
+    test_setup!("lget_range_test", ser_method_int, db_name);
+
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    db.lcreate("list1")
+        .unwrap()
+        .lextend(&(0..50).collect::<Vec<i32>>());
+
+    // get a range in the middle of the list
+    assert_eq!(
+        db.lget_range::<i32>("list1", 20, 40).unwrap(),
+        (20..40).collect::<Vec<i32>>()
+    );
+
+    // get the whole list
+    assert_eq!(
+        db.lget_range::<i32>("list1", 0, 50).unwrap(),
+        (0..50).collect::<Vec<i32>>()
+    );
+
+    // an end that is out of bounds is clamped to the list length
+    assert_eq!(
+        db.lget_range::<i32>("list1", 45, 100).unwrap(),
+        (45..50).collect::<Vec<i32>>()
+    );
+
+    // empty ranges
+    assert!(db.lget_range::<i32>("list1", 30, 30).unwrap().is_empty());
+    assert!(db.lget_range::<i32>("list1", 40, 20).unwrap().is_empty());
+    assert!(db.lget_range::<i32>("list1", 60, 70).unwrap().is_empty());
+
+    // an empty list
+    db.lcreate("list2").unwrap();
+    assert!(db.lget_range::<i32>("list2", 0, 10).unwrap().is_empty());
+
+    // a list that doesn't exist
+    assert!(db.lget_range::<i32>("list3", 0, 10).is_none());
+
+    // a range with an item of a different type
+    // (Bincode isn't self-describing so the string may be deserialized as a number)
+    if ser_method_int != 1 {
+        db.ladd("list1", &String::from("my string"));
+        assert!(db.lget_range::<i32>("list1", 40, 51).is_none());
+        assert!(db.lget_range::<i32>("list1", 40, 50).is_some());
+    }
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn pickle_value_test(ser_method_int: i32) {
+    test_setup!("pickle_value_test", ser_method_int, db_name);
+
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    let values = vec![
+        PickleValue::from(100),
+        PickleValue::from(-1.5),
+        PickleValue::from("my string"),
+        PickleValue::from(true),
+        PickleValue::from(vec![1, 2, 3]),
+        PickleValue::List(vec![
+            PickleValue::from('a'.to_string()),
+            PickleValue::Int(2),
+        ]),
+        PickleValue::from(None::<i32>),
+    ];
+
+    // create a list of tagged values of different types
+    db.lcreate("list1")
+        .unwrap()
+        .ladd_value(&values[0])
+        .ladd_value(&values[1]);
+    for value in values.iter().skip(2) {
+        assert!(db.ladd_value("list1", value).is_some());
+    }
+    assert!(db.ladd_value("list2", &values[0]).is_none());
+
+    // read the items back without knowing their types
+    for (pos, value) in values.iter().enumerate() {
+        assert_eq!(db.lget_value("list1", pos).unwrap(), *value);
+    }
+    assert!(db.lget_value("list1", values.len()).is_none());
+    assert!(db.lget_value("list2", 0).is_none());
+
+    // match on the items while iterating over the list
+    let mut ints = 0;
+    let mut strings = 0;
+    for item in db.liter("list1") {
+        match item.get_item::<PickleValue>().unwrap() {
+            PickleValue::Int(num) => {
+                assert_eq!(num, 100);
+                ints += 1;
+            }
+            PickleValue::String(s) => {
+                assert_eq!(s, "my string");
+                strings += 1;
+            }
+            _ => (),
+        }
+    }
+    assert_eq!(ints, 1);
+    assert_eq!(strings, 1);
+
+    // verify the items were dumped to the file
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    let read_values = (0..read_db.llen("list1"))
+        .map(|pos| read_db.lget_value("list1", pos).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(read_values, values);
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn sets_test(ser_method_int: i32) {
+    test_setup!("sets_test", ser_method_int, db_name);
+
+    // create a db with auto_dump == true
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    // create a set and add members, each one is stored only once
+    db.screate("set1").unwrap();
+    assert!(db.sexists("set1"));
+    assert!(db.exists("set1"));
+    assert!(!db.lexists("set1"));
+    assert_eq!(db.slen("set1"), 0);
+    assert!(db.sadd("set1", &1).unwrap());
+    assert!(db.sadd("set1", &2).unwrap());
+    assert!(db.sadd("set1", &3).unwrap());
+    assert!(!db.sadd("set1", &2).unwrap());
+    assert_eq!(db.slen("set1"), 3);
+
+    // check the members
+    assert!(db.scontains("set1", &1));
+    assert!(!db.scontains("set1", &4));
+    assert!(!db.scontains("set2", &1));
+    let mut members = db.smembers::<i32>("set1").unwrap();
+    members.sort_unstable();
+    assert_eq!(members, vec![1, 2, 3]);
+    assert!(db.smembers::<i32>("set2").is_none());
+
+    // remove members
+    assert!(db.srem("set1", &1).unwrap());
+    assert!(!db.srem("set1", &1).unwrap());
+    assert!(!db.srem("set2", &1).unwrap());
+    assert_eq!(db.slen("set1"), 2);
+
+    // verify the set was dumped to the file
+    {
+        let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+        assert_eq!(read_db.slen("set1"), 2);
+        assert!(read_db.scontains("set1", &2));
+        assert!(!read_db.scontains("set1", &1));
+        assert_eq!(read_db.total_keys(), 1);
+    }
+
+    // a value overrides a set with the same name, and a set overrides a list
+    db.screate("set2").unwrap();
+    db.set("set2", &100).unwrap();
+    assert!(!db.sexists("set2"));
+    assert_eq!(db.get::<i32>("set2").unwrap(), 100);
+    db.lcreate("list1").unwrap().lextend(&[1, 2]);
+    db.screate("list1").unwrap();
+    assert!(!db.lexists("list1"));
+    assert_eq!(db.slen("list1"), 0);
+
+    // remove the set
+    assert!(db.rem("set1").unwrap());
+    assert!(!db.sexists("set1"));
+    assert!(!db.exists("set1"));
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert!(!read_db.sexists("set1"));
+    assert!(read_db.sexists("list1"));
+}
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn hashes_test(ser_method_int: i32) {
+    test_setup!("hashes_test", ser_method_int, db_name);
+
+    // create a db with auto_dump == true
+    let mut db = PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    );
+
+    // set fields, the hash is created on the first one
+    assert!(db.hset("hash1", "field1", &1).unwrap());
+    assert!(db.hset("hash1", "field2", &2).unwrap());
+    assert!(db.exists("hash1"));
+    assert!(!db.lexists("hash1"));
+    assert_eq!(db.hlen("hash1"), 2);
+    assert_eq!(db.hlen("hash2"), 0);
+
+    // update a field
+    assert!(!db.hset("hash1", "field1", &10).unwrap());
+    assert_eq!(db.hlen("hash1"), 2);
+
+    // get fields
+    assert_eq!(db.hget::<i32>("hash1", "field1").unwrap(), 10);
+    assert_eq!(db.hget::<i32>("hash1", "field2").unwrap(), 2);
+    assert!(db.hget::<i32>("hash1", "field3").is_none());
+    assert!(db.hget::<i32>("hash2", "field1").is_none());
+
+    // get all fields
+    let all = db.hgetall::<i32>("hash1").unwrap();
+    assert_eq!(all.len(), 2);
+    assert_eq!(all["field1"], 10);
+    assert_eq!(all["field2"], 2);
+    assert!(db.hgetall::<i32>("hash2").is_none());
+
+    // delete fields, the hash is kept even when it becomes empty
+    assert!(db.hdel("hash1", "field2").unwrap());
+    assert!(!db.hdel("hash1", "field2").unwrap());
+    assert!(!db.hdel("hash2", "field1").unwrap());
+    assert_eq!(db.hlen("hash1"), 1);
+    db.hset("hash3", "field1", &String::from("value")).unwrap();
+    assert!(db.hdel("hash3", "field1").unwrap());
+    assert!(db.exists("hash3"));
+    assert_eq!(db.hlen("hash3"), 0);
+
+    // verify the hashes were dumped to the file
+    {
+        let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+        assert_eq!(read_db.hlen("hash1"), 1);
+        assert_eq!(read_db.hget::<i32>("hash1", "field1").unwrap(), 10);
+        assert!(read_db.hget::<i32>("hash1", "field2").is_none());
+        assert!(read_db.exists("hash3"));
+        assert_eq!(read_db.total_keys(), 2);
+    }
+
+    // a value overrides a hash with the same name, and a hash overrides a list
+    db.set("hash3", &100).unwrap();
+    assert_eq!(db.hlen("hash3"), 0);
+    assert_eq!(db.get::<i32>("hash3").unwrap(), 100);
+    db.lcreate("list1").unwrap().lextend(&[1, 2]);
+    db.hset("list1", "field1", &1).unwrap();
+    assert!(!db.lexists("list1"));
+    assert_eq!(db.hlen("list1"), 1);
+
+    // remove the hash
+    assert!(db.rem("hash1").unwrap());
+    assert!(!db.exists("hash1"));
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert!(!read_db.exists("hash1"));
+    assert_eq!(read_db.hget::<i32>("list1", "field1").unwrap(), 1);
+}
@@ -0,0 +1,95 @@
+use pickledb::{PickleDb, PickleDbDumpPolicy, SerializationMethod, SyncPickleDb};
+use std::thread;
+
+mod common;
+
+#[cfg(test)]
+extern crate rstest;
+
+use rstest::rstest_parametrize;
+
+#[rstest_parametrize(ser_method_int, case(0), case(1), case(2), case(3), case(4), case(5))]
+fn sync_threads_test(ser_method_int: i32) {
+    test_setup!("sync_threads_test", ser_method_int, db_name);
+
+    let db = SyncPickleDb::new(PickleDb::new(
+        &db_name,
+        PickleDbDumpPolicy::AutoDump,
+        ser_method!(ser_method_int),
+    ));
+    db.lcreate("list1").unwrap();
+
+    // set values and add list items from multiple threads
+    let handles: Vec<_> = (0..8)
+        .map(|i| {
+            let db = db.clone();
+            thread::spawn(move || {
+                for j in 0..10 {
+                    db.set(&format!("key{}_{}", i, j), &(i * 10 + j)).unwrap();
+                    assert!(db.ladd("list1", &(i * 10 + j)));
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    // all values and list items are there
+    assert_eq!(db.total_keys(), 81);
+    assert_eq!(db.get::<i32>("key3_4").unwrap(), 34);
+    assert_eq!(db.llen("list1"), 80);
+    assert!(db.exists("key7_9"));
+
+    // iterate over the list while holding the lock
+    {
+        let locked_db = db.lock();
+        let sum: i32 = locked_db
+            .liter("list1")
+            .map(|item| item.get_item::<i32>().unwrap())
+            .sum();
+        assert_eq!(sum, (0..80).sum::<i32>());
+    }
+
+    // the rest of the forwarded methods
+    assert!(db.rem("key0_0").unwrap());
+    assert!(!db.exists("key0_0"));
+    assert!(db.lextend("list1", &[100, 200]));
+    assert!(!db.ladd("list2", &1));
+    assert_eq!(db.lget::<i32>("list1", 81).unwrap(), 200);
+    assert_eq!(db.lpop::<i32>("list1", 81).unwrap(), 200);
+    assert!(db.lrem_value("list1", &100).unwrap());
+    assert_eq!(db.lrem_list("list1").unwrap(), 80);
+    assert!(!db.lexists("list1"));
+    assert_eq!(db.get_all().len(), 79);
+    db.dump().unwrap();
+
+    // all changes were dumped to the file
+    let read_db = PickleDb::load_read_only(&db_name, ser_method!(ser_method_int)).unwrap();
+    assert_eq!(read_db.total_keys(), 79);
+    assert_eq!(read_db.get::<i32>("key5_5").unwrap(), 55);
+}
+
+#[test]
+fn sync_poisoned_lock_test() {
+    set_test_rsc!("sync_poisoned_lock_test.db");
+
+    let db: SyncPickleDb =
+        PickleDb::new_json("sync_poisoned_lock_test.db", PickleDbDumpPolicy::AutoDump).into();
+    db.set("key1", &1).unwrap();
+
+    // panic while holding the lock
+    let db_clone = db.clone();
+    let result = thread::spawn(move || {
+        let _locked_db = db_clone.lock();
+        panic!("panic while holding the lock");
+    })
+    .join();
+    assert!(result.is_err());
+
+    // the DB can still be used
+    assert_eq!(db.get::<i32>("key1").unwrap(), 1);
+    db.set("key2", &2).unwrap();
+    assert_eq!(db.total_keys(), 2);
+}